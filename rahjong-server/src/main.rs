@@ -0,0 +1,112 @@
+//! A WebSocket server hosting rooms of riichi mahjong over the rahjong engine.
+//!
+//! Run it with the address to listen on, `127.0.0.1:9000` by default,
+//! and connect to `ws://127.0.0.1:9000`.
+//! Messages are JSON texts of the protocol of [rahjong::protocol],
+//! where the first message of a client joins a room by its name.
+
+mod room;
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use futures_util::{SinkExt, StreamExt};
+use rahjong::protocol::{self, ClientMessage, ServerMessage};
+use tokio::{
+    net::{TcpListener, TcpStream},
+    sync::mpsc,
+};
+use tokio_tungstenite::tungstenite::Message;
+
+use room::Room;
+
+/// The rooms of the server by name.
+type Rooms = Arc<Mutex<HashMap<String, Room>>>;
+
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    let addr = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "127.0.0.1:9000".to_string());
+    let listener = TcpListener::bind(&addr).await?;
+    println!("listening on {addr}");
+    let rooms = Rooms::default();
+    loop {
+        let (stream, _) = listener.accept().await?;
+        tokio::spawn(serve(stream, rooms.clone()));
+    }
+}
+
+/// Serves a client from the handshake until it leaves.
+async fn serve(stream: TcpStream, rooms: Rooms) {
+    let Ok(socket) = tokio_tungstenite::accept_async(stream).await else {
+        return;
+    };
+    let (mut sink, mut source) = socket.split();
+    let (client, mut outgoing) = mpsc::unbounded_channel::<String>();
+    tokio::spawn(async move {
+        while let Some(text) = outgoing.recv().await {
+            if sink.send(Message::text(text)).await.is_err() {
+                break;
+            }
+        }
+    });
+    let reject = |message: String| room::send(&client, &ServerMessage::Error { message });
+
+    let mut joined = None;
+    while let Some(Ok(message)) = source.next().await {
+        let Message::Text(text) = message else {
+            continue;
+        };
+        let message = match serde_json::from_str::<ClientMessage>(&text) {
+            Ok(message) => message,
+            Err(e) => {
+                reject(e.to_string());
+                continue;
+            }
+        };
+        match (message, &joined) {
+            (ClientMessage::Join { version, room }, None) => {
+                if version != protocol::VERSION {
+                    reject(format!("protocol version {} expected", protocol::VERSION));
+                    continue;
+                }
+                let seat = rooms
+                    .lock()
+                    .unwrap()
+                    .entry(room.clone())
+                    .or_default()
+                    .join(client.clone());
+                match seat {
+                    Some(seat) => joined = Some((room, seat)),
+                    None => reject("the room is full".to_string()),
+                }
+            }
+            (ClientMessage::Action { action }, Some((room, seat))) => {
+                let mut rooms = rooms.lock().unwrap();
+                let res = match rooms.get_mut(room) {
+                    Some(room) => room.apply(*seat, action),
+                    None => Err("the room is closed"),
+                };
+                if let Err(message) = res {
+                    reject(message.to_string());
+                }
+            }
+            (ClientMessage::Join { .. }, Some(_)) => reject("already in a room".to_string()),
+            (ClientMessage::Action { .. }, None) => reject("not in a room".to_string()),
+        }
+    }
+
+    let Some((name, seat)) = joined else {
+        return;
+    };
+    let mut rooms = rooms.lock().unwrap();
+    if let Some(room) = rooms.get_mut(&name) {
+        room.leave(seat);
+        if room.is_empty() {
+            rooms.remove(&name);
+        }
+    }
+}
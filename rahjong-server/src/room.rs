@@ -0,0 +1,133 @@
+//! Rooms of four players.
+//!
+//! A [Room] seats the clients in the order they join, 东 first,
+//! and starts a hand(局) of riichi mahjong once the four seats are taken.
+//! Every action is applied by the [Engine] as the action of the client's own seat,
+//! so the turn order and the claims on a card played are decided by the server,
+//! see [rahjong::claim::ClaimArbitrator].
+//! After each action, every client is sent the events shown to its seat,
+//! see [rahjong::protocol].
+
+use rahjong::{
+    card_type::FengType,
+    cards::Cards,
+    engine::{Action, Engine},
+    game::{Game, GameLength},
+    protocol::{self, ServerMessage},
+};
+use tokio::sync::mpsc::UnboundedSender;
+
+/// The struct hosting a hand(局) for four clients.
+#[derive(Default)]
+pub struct Room {
+    /// The hand being played, or `None` until the four seats are taken.
+    pub engine: Option<Engine>,
+    /// The channels to the clients, in the order of players.
+    pub seats: [Option<UnboundedSender<String>>; 4],
+    /// The number of events of the hand sent to the clients.
+    pub sent: usize,
+}
+
+impl Room {
+    /// Seats the client at the first free seat, and starts the hand if the room is full.
+    /// The client is sent the state of the hand if it has started, see [ServerMessage::State].
+    ///
+    /// Returns `None` if no seat is free.
+    pub fn join(&mut self, client: UnboundedSender<String>) -> Option<FengType> {
+        let i = self.seats.iter().position(Option::is_none)?;
+        let seat = FengType::ALL[i];
+        let version = protocol::VERSION;
+        send(&client, &ServerMessage::Welcome { version, seat });
+        // A client taking a seat freed during the hand picks the hand up where it is.
+        if let Some(engine) = &self.engine {
+            send_state(engine, seat, &client);
+        }
+        self.seats[i] = Some(client);
+        if self.engine.is_none() && self.seats.iter().all(Option::is_some) {
+            let cards = Cards::with_riichi_rules();
+            let engine = Engine::new(Game::new(cards, GameLength::default()));
+            self.sent = engine.events.len();
+            for (seat, client) in FengType::ALL.into_iter().zip(&self.seats) {
+                if let Some(client) = client {
+                    send_state(&engine, seat, client);
+                }
+            }
+            self.engine = Some(engine);
+        }
+        Some(seat)
+    }
+
+    /// Frees the seat of the client, and closes the hand once the room is empty.
+    pub fn leave(&mut self, seat: FengType) {
+        self.seats[index_of(seat)] = None;
+        if self.is_empty() {
+            self.engine = None;
+        }
+    }
+
+    /// Checks if no client is in the room.
+    pub fn is_empty(&self) -> bool {
+        self.seats.iter().all(Option::is_none)
+    }
+
+    /// Applies the action of the seat, and sends the events caused to every client.
+    ///
+    /// Returns an error telling why if the action is rejected.
+    pub fn apply(&mut self, seat: FengType, action: Action) -> Result<(), &'static str> {
+        let engine = self.engine.as_mut().ok_or("the hand has not started")?;
+        if !engine.apply(seat, action) {
+            return Err("the action is not legal at the moment");
+        }
+        self.broadcast();
+        Ok(())
+    }
+
+    /// Sends every client the events not sent yet, shown to its seat,
+    /// and the result if the hand has ended.
+    pub fn broadcast(&mut self) {
+        let Some(engine) = &self.engine else {
+            return;
+        };
+        let events = &engine.events[self.sent.min(engine.events.len())..];
+        for (seat, client) in FengType::ALL.into_iter().zip(&self.seats) {
+            let Some(client) = client else {
+                continue;
+            };
+            let message = ServerMessage::Delta {
+                events: events
+                    .iter()
+                    .filter_map(|e| protocol::redact(e, seat))
+                    .collect(),
+                actions: engine.legal_actions(seat),
+            };
+            send(client, &message);
+            if let Some(end) = &engine.result {
+                let scores = FengType::ALL.map(|side| engine.cards().score(side));
+                let end = end.clone();
+                send(client, &ServerMessage::Result { end, scores });
+            }
+        }
+        self.sent = engine.events.len();
+    }
+}
+
+/// Sends the client the whole state of the hand shown to its seat.
+fn send_state(engine: &Engine, seat: FengType, client: &UnboundedSender<String>) {
+    let message = ServerMessage::State {
+        view: Box::new(engine.cards().view(seat)),
+        actions: engine.legal_actions(seat),
+    };
+    send(client, &message);
+}
+
+/// Sends the message to the client as JSON, ignoring a client gone.
+pub fn send(client: &UnboundedSender<String>, message: &ServerMessage) {
+    if let Ok(text) = serde_json::to_string(message) {
+        let _ = client.send(text);
+    }
+}
+
+/// Returns the index of the seat in the order of players.
+fn index_of(seat: FengType) -> usize {
+    FengType::ALL.iter().position(|&s| s == seat).unwrap_or(0)
+}
@@ -0,0 +1,134 @@
+//! Plays a hand(局) of riichi mahjong in the terminal, as 东 against three bots, see [rahjong::bot::EfficiencyBot].
+//!
+//! Run it by `cargo run --example terminal`, and choose each action by its number.
+//! With `--auto`, a bot plays 东 as well, so the hand runs to its end unattended;
+//! with `--seed <n>`, the wall is built from the seed.
+
+use std::io::{self, BufRead, Write};
+
+use rahjong::{
+    bot::{Bot, EfficiencyBot},
+    card_type::FengType,
+    cards::Cards,
+    engine::{Action, Engine, HandEnd},
+    game::{Game, GameLength},
+    glyph,
+};
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let auto = args.iter().any(|a| a == "--auto");
+    let seed = args
+        .iter()
+        .position(|a| a == "--seed")
+        .and_then(|i| args.get(i + 1)?.parse().ok());
+    let human = (!auto).then_some(FengType::Dong);
+
+    let cards = match seed {
+        Some(seed) => Cards::with_riichi_rules_seeded(seed),
+        None => Cards::with_riichi_rules(),
+    };
+    let mut engine = Engine::new(Game::new(cards, GameLength::default()));
+    let mut input = io::stdin().lock();
+
+    while engine.result.is_none() {
+        let acting: Vec<FengType> = match &engine.claims {
+            Some(_) => FengType::ALL
+                .into_iter()
+                .filter(|&side| !engine.responded.contains(&side))
+                .collect(),
+            None => vec![engine.cards().active_player],
+        };
+        for side in acting {
+            let actions = engine.legal_actions(side);
+            if actions.is_empty() {
+                continue;
+            }
+            let action = if Some(side) == human && actions != [Action::Draw] {
+                show(&engine, side);
+                choose(&mut input, &actions)
+            } else {
+                EfficiencyBot.choose_action(&engine.cards().view(side), &actions)
+            };
+            if action != Action::Pass && action != Action::Draw {
+                println!("{side}: {}", describe(action));
+            }
+            engine.apply(side, action);
+        }
+    }
+
+    println!();
+    print!("{}", glyph::render(engine.cards()));
+    match &engine.result {
+        Some(HandEnd::Tsumo(side)) => println!("{side} wins by tsumo"),
+        Some(HandEnd::Ron { winners, from }) => {
+            for side in winners {
+                println!("{side} wins by ron from {from}");
+            }
+        }
+        Some(HandEnd::Draw(_)) => println!("the hand is drawn"),
+        None => {}
+    }
+}
+
+/// Shows the state of the hand seen by the player.
+fn show(engine: &Engine, side: FengType) {
+    let view = engine.cards().view(side);
+    println!();
+    for seat in &view.seats {
+        let river: String = seat.river.iter().map(|r| format!("{r} ")).collect();
+        println!("{} {:>6}  river: {river}", seat.side, seat.score);
+    }
+    let open: String = view
+        .seats
+        .iter()
+        .filter(|seat| seat.side == side)
+        .flat_map(|seat| seat.open.iter().map(|c| format!("{c} ")))
+        .collect();
+    println!("hand: {}  {open}", glyph::hand(&view.hand));
+    if let Some((from, card)) = view.last_discard {
+        println!("{from} played {card}");
+    }
+}
+
+/// Asks the player to choose one of the actions by its number.
+fn choose(input: &mut impl BufRead, actions: &[Action]) -> Action {
+    for (i, &action) in actions.iter().enumerate() {
+        println!("  {i}: {}", describe(action));
+    }
+    loop {
+        print!("> ");
+        let _ = io::stdout().flush();
+        let mut line = String::new();
+        if input.read_line(&mut line).unwrap_or(0) == 0 {
+            return actions[0];
+        }
+        if let Some(&action) = line
+            .trim()
+            .parse::<usize>()
+            .ok()
+            .and_then(|i| actions.get(i))
+        {
+            return action;
+        }
+    }
+}
+
+/// Returns the action in words.
+fn describe(action: Action) -> String {
+    match action {
+        Action::Draw => "draw".to_string(),
+        Action::Discard(card) => format!("play {card}"),
+        Action::Riichi(card) => format!("riichi with {card}"),
+        Action::OpenRiichi(card) => format!("open riichi with {card}"),
+        Action::Chi(start) => format!("chi from {start}"),
+        Action::Pon => "pon".to_string(),
+        Action::Kan(card) => format!("kan {card}"),
+        Action::AnKan(card) => format!("ankan {card}"),
+        Action::Tsumo => "tsumo".to_string(),
+        Action::Ron => "ron".to_string(),
+        Action::Pass => "pass".to_string(),
+        Action::Nuki => "nuki".to_string(),
+        Action::DeclareVoid(suit) => format!("declare {suit} void"),
+    }
+}
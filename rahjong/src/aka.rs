@@ -0,0 +1,161 @@
+//! Red fives(赤宝牌) and their associated functions.
+//!
+//! A red five is a 5 of 万, 条 or 筒 counting as an extra dora(宝牌).
+//! It is a [CardType] as any other five, so it makes cases(面子) and waits as a regular five does,
+//! and the [Aka] struct tracks which of the fives are red as they move around.
+//!
+//! A player keeps red fives as long as possible,
+//! i.e. a red five is only played or used in a call action
+//! when there is no regular five of the same suit left in hand.
+
+use std::collections::BTreeMap;
+
+use rand::{seq::IteratorRandom, Rng};
+
+use crate::card_type::{CardType, FengType, RankType};
+
+/// The struct tracking the red fives(赤宝牌) of the game.
+#[derive(Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Aka {
+    /// Whether each card in mountain is a red five,
+    /// in the same order as [crate::cards::Cards::card_mountain].
+    pub mountain: Vec<bool>,
+    /// Whether each card in the dead wall is a red five,
+    /// in the same order as [crate::cards::Cards::dead_wall].
+    pub dead_wall: Vec<bool>,
+    /// The red fives in each player's hand.
+    pub in_hand: BTreeMap<FengType, Vec<CardType>>,
+    /// The number of red fives in each player's open.
+    pub in_open: BTreeMap<FengType, u8>,
+    /// Whether the card last played is a red five.
+    pub last_played: bool,
+}
+
+impl Aka {
+    /// Marks the red fives of a shuffled wall, chosen at random among the fives of each suit,
+    /// given the number of red fives of 万, 条 and 筒, see [crate::rule_config::RuleConfig::aka],
+    /// and clears the red fives of players.
+    pub fn mark<R: Rng + ?Sized>(&mut self, wall: &[CardType], per_suit: [u8; 3], rng: &mut R) {
+        let mut flags = vec![false; wall.len()];
+        for (five, n) in fives().into_iter().zip(per_suit) {
+            let positions = wall.iter().enumerate().filter(|&(_, &c)| c == five);
+            for (i, _) in positions.choose_multiple(rng, n.min(4) as usize) {
+                flags[i] = true;
+            }
+        }
+        self.mark_with(flags);
+    }
+
+    /// Marks the red fives of a wall by the flags given, in the same order as the wall,
+    /// and clears the red fives of players.
+    pub fn mark_with(&mut self, flags: Vec<bool>) {
+        self.mountain = flags;
+        self.dead_wall.clear();
+        self.in_hand.clear();
+        self.in_open.clear();
+        self.last_played = false;
+    }
+
+    /// Deals the red fives among the last cards of the wall to the player,
+    /// given the number of cards dealt, before the cards are dealt.
+    pub fn deal(&mut self, side: FengType, wall: &[CardType], size: usize) {
+        let start = wall.len().saturating_sub(size);
+        let flags = self.mountain.split_off(start.min(self.mountain.len()));
+        let reds = wall[start..]
+            .iter()
+            .zip(flags)
+            .filter(|&(_, red)| red)
+            .map(|(&c, _)| c);
+        self.in_hand.entry(side).or_default().extend(reds);
+    }
+
+    /// Sets apart the flags of the dead wall(王牌) from the mountain,
+    /// before the dead wall is built.
+    pub fn build_dead_wall(&mut self) {
+        let n = self.mountain.len().min(14);
+        self.dead_wall = self.mountain.drain(..n).collect();
+    }
+
+    /// Draws the last card of the mountain into the player's hand.
+    pub fn draw(&mut self, side: FengType, card: CardType) {
+        if self.mountain.pop().unwrap_or(false) {
+            self.in_hand.entry(side).or_default().push(card);
+        }
+    }
+
+    /// Draws the first card of the dead wall into the player's hand,
+    /// and the last card of the mountain joins the dead wall.
+    pub fn draw_replacement(&mut self, side: FengType, card: CardType) {
+        if !self.dead_wall.is_empty() && self.dead_wall.remove(0) {
+            self.in_hand.entry(side).or_default().push(card);
+        }
+        if !self.mountain.is_empty() {
+            let last = self.mountain.remove(0);
+            self.dead_wall.push(last);
+        }
+    }
+
+    /// Draws the first card of the mountain into the player's hand,
+    /// i.e. the replacement card after setting aside a 北 in 三麻.
+    pub fn draw_first(&mut self, side: FengType, card: CardType) {
+        if !self.mountain.is_empty() && self.mountain.remove(0) {
+            self.in_hand.entry(side).or_default().push(card);
+        }
+    }
+
+    /// Returns the number of red fives of the card in the player's hand.
+    fn count_in_hand(&self, side: FengType, card: CardType) -> u8 {
+        self.in_hand
+            .get(&side)
+            .map_or(0, |v| v.iter().filter(|&&c| c == card).count() as u8)
+    }
+
+    /// Moves the cards out of the player's hand,
+    /// given the number of the card in hand before and after, regular fives first.
+    ///
+    /// Returns the number of red fives moved.
+    pub fn leave_hand(&mut self, side: FengType, card: CardType, before: u8, after: u8) -> u8 {
+        let reds = self.count_in_hand(side, card);
+        let regular = before.saturating_sub(reds);
+        let moved = before
+            .saturating_sub(after)
+            .saturating_sub(regular)
+            .min(reds);
+        if let Some(v) = self.in_hand.get_mut(&side) {
+            for _ in 0..moved {
+                if let Some(pos) = v.iter().position(|&c| c == card) {
+                    v.remove(pos);
+                }
+            }
+        }
+        moved
+    }
+
+    /// Puts red fives into the player's open.
+    pub fn add_to_open(&mut self, side: FengType, n: u8) {
+        *self.in_open.entry(side).or_default() += n;
+    }
+
+    /// Takes red fives out of the player's open, e.g. when an 加杠 is robbed.
+    pub fn remove_from_open(&mut self, side: FengType, n: u8) {
+        if let Some(count) = self.in_open.get_mut(&side) {
+            *count = count.saturating_sub(n);
+        }
+    }
+
+    /// Returns the number of red fives the player has, in hand or in open.
+    pub fn count(&self, side: FengType) -> u16 {
+        self.in_hand.get(&side).map_or(0, Vec::len) as u16
+            + self.in_open.get(&side).copied().unwrap_or(0) as u16
+    }
+}
+
+/// Returns the fives of 万, 条 and 筒, which can be red.
+pub fn fives() -> [CardType; 3] {
+    [
+        CardType::Wan(RankType::Five),
+        CardType::Tiao(RankType::Five),
+        CardType::Tong(RankType::Five),
+    ]
+}
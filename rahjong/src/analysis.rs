@@ -0,0 +1,711 @@
+//! Hand analysis and their associated functions.
+//!
+//! Functions in this module only look at the shape of the cards,
+//! i.e. the hand and the open of a player,
+//! regardless of any situation or completion.
+
+use crate::{
+    card_type::{CardType, RankType},
+    cards::{Hand, Open, WinContext},
+    case_type::CaseType,
+    shanten_table,
+    tile_counts::TileCounts,
+};
+
+/// The `WaitType` type. Represents the shape the winning card completes, known as 听牌型 in Chinese.
+#[derive(PartialEq, Eq, Clone, Copy, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WaitType {
+    /// The 两面 type, completing a 顺子 from either side.
+    LiangMian,
+    /// The 嵌张 type, completing a 顺子 in the middle.
+    QianZhang,
+    /// The 边张 type, completing 123 with 3 or 789 with 7.
+    BianZhang,
+    /// The 双碰 type, completing a 刻子 while another pair is the 雀头.
+    ShuangPeng,
+    /// The 单骑 type, completing the 雀头.
+    DanQi,
+}
+
+/// A way the concealed hand of a complete standard hand splits into cases(面子) and a pair(雀头).
+///
+/// Decompositions are ordered by the pair first and then the cases,
+/// which is the canonical order used to break ties between equally scored decompositions.
+#[derive(PartialEq, Eq, Clone, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Decomposition {
+    /// The pair(雀头).
+    pub pair: CardType,
+    /// The cases formed by the concealed hand, either [CaseType::Ke] or [CaseType::Shun].
+    ///
+    /// Cases in the open are not included. The cases have been sorted.
+    pub cases: Vec<CaseType>,
+}
+
+impl Decomposition {
+    /// Returns all the wait shapes the winning card can be interpreted as completing,
+    /// with the cases and the pair fixed.
+    ///
+    /// The returned array has been sorted and deduplicated.
+    pub fn wait_types(&self, winning: CardType) -> Vec<WaitType> {
+        let mut res = Vec::new();
+        if self.pair == winning {
+            res.push(WaitType::DanQi);
+        }
+        for &case in &self.cases {
+            match case {
+                CaseType::Ke(card) if card == winning => res.push(WaitType::ShuangPeng),
+                CaseType::Shun(start) => {
+                    let cards = case.cards();
+                    if cards[1] == winning {
+                        res.push(WaitType::QianZhang);
+                    } else if cards[0] == winning && start.rank() == Some(RankType::Seven)
+                        || cards[2] == winning && start.rank() == Some(RankType::One)
+                    {
+                        res.push(WaitType::BianZhang);
+                    } else if cards[0] == winning || cards[2] == winning {
+                        res.push(WaitType::LiangMian);
+                    }
+                }
+                _ => {}
+            }
+        }
+        res.sort_unstable();
+        res.dedup();
+        res
+    }
+
+    /// Returns the number of concealed 刻子 and 暗杠, known as 暗刻 in Chinese,
+    /// given the open of the player and the winning card.
+    ///
+    /// A 刻子 completed by 荣和 is not concealed,
+    /// unless the winning card can also be taken as completing another case.
+    pub fn concealed_triplets(&self, open: &Open, winning: CardType, is_tsumo: bool) -> usize {
+        let an_gang = open
+            .iter()
+            .filter(|c| matches!(c, CaseType::AnGang(_)))
+            .count();
+        let mut res = self
+            .cases
+            .iter()
+            .filter(|c| matches!(c, CaseType::Ke(_)))
+            .count();
+        if !is_tsumo && self.wait_types(winning) == [WaitType::ShuangPeng] {
+            res -= 1;
+        }
+        res + an_gang
+    }
+
+    /// Returns the number of concealed 刻子 and 暗杠 like [Decomposition::concealed_triplets],
+    /// given the wait shape the winning card is taken as completing.
+    pub fn concealed_triplets_by(&self, open: &Open, wait: WaitType, is_tsumo: bool) -> usize {
+        let an_gang = open
+            .iter()
+            .filter(|c| matches!(c, CaseType::AnGang(_)))
+            .count();
+        let kes = self
+            .cases
+            .iter()
+            .filter(|c| matches!(c, CaseType::Ke(_)))
+            .count();
+        if !is_tsumo && wait == WaitType::ShuangPeng {
+            kes - 1 + an_gang
+        } else {
+            kes + an_gang
+        }
+    }
+}
+
+/// The `FormType` type. Represents a way a complete hand splits, known as 和牌型 in Chinese.
+#[derive(PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FormType {
+    /// The standard form of four cases(面子), or five in 台湾麻将, and a pair(雀头).
+    /// Contains a [Decomposition] of the concealed hand.
+    Standard(Decomposition),
+    /// The 七对子 form. Contains the seven different pairs, sorted.
+    QiDui(Vec<CardType>),
+    /// The 国士无双 form. Contains the card of the pair.
+    GuoShi(CardType),
+    /// The 全不靠 form of fourteen different cards, including 七星不靠, see [knitted_forms].
+    /// Contains the cards, sorted.
+    QuanBuKao(Vec<CardType>),
+    /// The 组合龙 form of a knitted straight with a case(面子) and a pair(雀头), see [knitted_forms].
+    /// Contains a [Decomposition] of the concealed cards other than the knitted straight.
+    ZuHeLong(Decomposition),
+}
+
+/// A way to read a complete hand, i.e. its form and the wait shape the winning card completes.
+///
+/// A complete hand is scored by one of its interpretations, see [interpretations].
+#[derive(PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Interpretation {
+    /// The form of the hand.
+    pub form: FormType,
+    /// The wait shape the winning card is taken as completing in a [FormType::Standard] form,
+    /// see [Decomposition::wait_types].
+    ///
+    /// `None` for the other forms.
+    pub wait: Option<WaitType>,
+}
+
+/// The shanten numbers(向听数) of each form of complete hands, see [shanten_breakdown].
+///
+/// A shanten number is how many cards the hand still needs to exchange
+/// to be in drawing hand(听牌) state, so it is `0` for a drawing hand,
+/// and `-1` for a complete hand.
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub struct ShantenBreakdown {
+    /// The shanten number of standard hands, i.e. four cases(面子), or five in 台湾麻将, and a pair(雀头).
+    pub standard: i8,
+    /// The shanten number of seven pairs(七对子).
+    ///
+    /// `None` if the player has done any call action or holds 16 cards.
+    pub seven_pairs: Option<i8>,
+    /// The shanten number of thirteen orphans(国士无双).
+    ///
+    /// `None` if the player has done any call action or holds 16 cards.
+    pub thirteen_orphans: Option<i8>,
+}
+
+impl ShantenBreakdown {
+    /// Returns the minimum shanten number of all forms, i.e. the shanten number of the hand.
+    pub fn min(&self) -> i8 {
+        [Some(self.standard), self.seven_pairs, self.thirteen_orphans]
+            .into_iter()
+            .flatten()
+            .min()
+            .unwrap_or(self.standard)
+    }
+}
+
+/// The acceptance of playing a card, known as 进张 in Chinese.
+#[derive(PartialEq, Eq, Clone)]
+pub struct Acceptance {
+    /// The card being played.
+    pub discard: CardType,
+    /// The shanten number(向听数) after the card being played.
+    pub shanten: i8,
+    /// The cards reducing the shanten number when drawn, see [useful_tiles],
+    /// together with the number of their copies not visible to the player.
+    pub tiles: Vec<(CardType, u8)>,
+}
+
+impl Acceptance {
+    /// Returns the total number of copies of the accepted cards not visible to the player.
+    pub fn total(&self) -> usize {
+        self.tiles.iter().map(|&(_, n)| n as usize).sum()
+    }
+}
+
+/// Counts of each card type, indexed by [CardType::index].
+type Counts = [u8; CardType::COUNT];
+
+/// Converts a hand into counts.
+fn counts(hand: &Hand) -> Counts {
+    TileCounts::from(hand).0
+}
+
+/// Collects all the ways the counts split into cases(面子) only.
+fn split_cases(counts: &mut Counts, cases: &mut Vec<CaseType>, res: &mut Vec<Vec<CaseType>>) {
+    let Some(i) = counts.iter().position(|&n| n > 0) else {
+        res.push(cases.clone());
+        return;
+    };
+    let Some(card) = CardType::from_index(i) else {
+        return;
+    };
+    if counts[i] >= 3 {
+        counts[i] -= 3;
+        cases.push(CaseType::Ke(card));
+        split_cases(counts, cases, res);
+        cases.pop();
+        counts[i] += 3;
+    }
+    if i < 27 && i % 9 <= 6 && counts[i + 1] > 0 && counts[i + 2] > 0 {
+        counts[i] -= 1;
+        counts[i + 1] -= 1;
+        counts[i + 2] -= 1;
+        cases.push(CaseType::Shun(card));
+        split_cases(counts, cases, res);
+        cases.pop();
+        counts[i] += 1;
+        counts[i + 1] += 1;
+        counts[i + 2] += 1;
+    }
+}
+
+/// Returns the number of cases(面子) a complete standard hand of the size needs,
+/// where the size counts the cards in hand and each case in open as 3 cards,
+/// with or without the card just drawn.
+///
+/// It is four for the 13 cards of most rules, and five for the 16 cards of 台湾麻将,
+/// see [crate::ruleset::TaiwaneseRules].
+/// Smaller sizes, e.g. of a hand being dealt, are taken as 13 cards,
+/// and a hand with a card more than it should is taken as its size without the card.
+pub fn cases_needed(size: usize) -> usize {
+    (size.saturating_sub(1) / 3).max(4)
+}
+
+/// Returns the number of cards in the counts.
+fn total(counts: &Counts) -> usize {
+    counts.iter().map(|&n| n as usize).sum()
+}
+
+/// Returns the shanten number of standard hands.
+fn standard_shanten(counts: &mut Counts, open: usize) -> i8 {
+    let best = shanten_table::standard_shanten(&TileCounts(*counts), open);
+    if best == 0 && (total(counts) + 3 * open) % 3 == 1 && !has_standard_wait(counts) {
+        // Waiting only for a card of which the hand contains all four(空听).
+        return 1;
+    }
+    best
+}
+
+/// Checks if any card, of which the counts do not contain all four, completes a standard hand.
+fn has_standard_wait(counts: &mut Counts) -> bool {
+    (0..CardType::COUNT).any(|i| {
+        if counts[i] >= 4 {
+            return false;
+        }
+        counts[i] += 1;
+        let res = is_standard_complete(counts);
+        counts[i] -= 1;
+        res
+    })
+}
+
+/// Returns the shanten number of seven pairs(七对子).
+fn seven_pairs_shanten(counts: &Counts) -> i8 {
+    let pairs = counts.iter().filter(|&&n| n >= 2).count() as i8;
+    let kinds = counts.iter().filter(|&&n| n > 0).count() as i8;
+    6 - pairs + (7 - kinds).max(0)
+}
+
+/// Returns the shanten number of thirteen orphans(国士无双).
+fn thirteen_orphans_shanten(counts: &Counts) -> i8 {
+    let yao_jiu = counts
+        .iter()
+        .enumerate()
+        .filter(|&(i, _)| CardType::from_index(i).is_some_and(|c| c.is_yao_jiu()));
+    let kinds = yao_jiu.clone().filter(|&(_, &n)| n > 0).count() as i8;
+    let pair = yao_jiu.clone().any(|(_, &n)| n >= 2);
+    13 - kinds - pair as i8
+}
+
+/// Returns the shanten numbers(向听数) of each form of complete hands.
+///
+/// The hand may or may not contain the card just drawn,
+/// i.e. count 13 or 14 cards together with the open,
+/// where each case(面子) in open counts as 3 cards.
+/// A hand of 16 or 17 cards needs five cases, see [cases_needed],
+/// and has no special forms.
+///
+/// A hand only waiting for a card of which it contains all four(空听)
+/// is not counted as in drawing hand(听牌) state.
+pub fn shanten_breakdown(hand: &Hand, open: &Open) -> ShantenBreakdown {
+    counts_shanten_breakdown(&mut counts(hand), open.len())
+}
+
+/// Returns the shanten numbers of the counts with the number of cases(面子) in open.
+fn counts_shanten_breakdown(counts: &mut Counts, melds: usize) -> ShantenBreakdown {
+    let concealed = melds == 0 && cases_needed(total(counts)) == 4;
+    ShantenBreakdown {
+        standard: standard_shanten(counts, melds),
+        seven_pairs: concealed.then(|| seven_pairs_shanten(counts)),
+        thirteen_orphans: concealed.then(|| thirteen_orphans_shanten(counts)),
+    }
+}
+
+/// Returns the shanten number(向听数) of the hand, i.e. the minimum of all forms.
+///
+/// See [shanten_breakdown] for details.
+pub fn shanten(hand: &Hand, open: &Open) -> i8 {
+    counts_shanten(&mut counts(hand), open.len())
+}
+
+/// Returns the shanten number of the counts with the number of cases(面子) in open.
+pub(crate) fn counts_shanten(counts: &mut Counts, melds: usize) -> i8 {
+    counts_shanten_breakdown(counts, melds).min()
+}
+
+/// Returns the cards that reduce the shanten number(向听数) of the hand when drawn,
+/// known as 有效牌 in Chinese.
+///
+/// The hand should not contain the card just drawn, i.e. count 13 cards together with the open.
+/// Cards of which the hand already contains all four are excluded.
+///
+/// The returned array has been sorted.
+pub fn useful_tiles(hand: &Hand, open: &Open) -> Vec<CardType> {
+    counts_useful_tiles(&mut counts(hand), open.len())
+}
+
+/// Returns the cards that reduce the shanten number of the counts with the number of cases(面子) in open.
+pub(crate) fn counts_useful_tiles(counts: &mut Counts, melds: usize) -> Vec<CardType> {
+    let current = counts_shanten(counts, melds);
+    let mut res = Vec::new();
+    for i in 0..CardType::COUNT {
+        if counts[i] >= 4 {
+            continue;
+        }
+        counts[i] += 1;
+        if counts_shanten(counts, melds) < current {
+            res.extend(CardType::from_index(i));
+        }
+        counts[i] -= 1;
+    }
+    res
+}
+
+/// Checks if the counts form cases(面子) and exactly one pair(雀头).
+fn is_standard_complete(counts: &Counts) -> bool {
+    shanten_table::is_standard_complete(&TileCounts(*counts))
+}
+
+/// Checks if the counts form seven different pairs(七对子).
+fn is_qi_dui_complete(counts: &Counts) -> bool {
+    counts.iter().filter(|&&n| n == 2).count() == 7
+}
+
+/// Checks if the counts form thirteen orphans(国士无双).
+fn is_guo_shi_complete(counts: &Counts) -> bool {
+    let mut pair = false;
+    for (i, &n) in counts.iter().enumerate() {
+        let yao_jiu = CardType::from_index(i).is_some_and(|c| c.is_yao_jiu());
+        match n {
+            0 if yao_jiu => return false,
+            0 => {}
+            1 if yao_jiu => {}
+            2 if yao_jiu && !pair => pair = true,
+            _ => return false,
+        }
+    }
+    pair
+}
+
+/// The offsets in [CardType::index] of the three number suits, in each order.
+const SUIT_ORDERS: [[usize; 3]; 6] = [
+    [0, 9, 18],
+    [0, 18, 9],
+    [9, 0, 18],
+    [9, 18, 0],
+    [18, 0, 9],
+    [18, 9, 0],
+];
+
+/// Returns the indices of the knitted straight(组合龙) of the suits in the order given,
+/// i.e. 147 of the first suit, 258 of the second and 369 of the third.
+fn knitted_straight(order: [usize; 3]) -> [usize; 9] {
+    std::array::from_fn(|i| order[i / 3] + i / 3 + 3 * (i % 3))
+}
+
+/// Checks if the counts are fourteen different cards, known as 全不靠 in Chinese,
+/// whose number cards are in the knitted straight of the suits in the order given.
+fn is_quan_bu_kao_complete(counts: &Counts, order: [usize; 3]) -> bool {
+    let straight = knitted_straight(order);
+    total(counts) == 14
+        && counts
+            .iter()
+            .enumerate()
+            .all(|(i, &n)| n == 0 || n == 1 && (i >= 27 || straight.contains(&i)))
+}
+
+/// Checks if the counts, together with the number of cases(面子) in open, are complete(和牌型).
+pub(crate) fn is_counts_complete(counts: &mut Counts, melds: usize) -> bool {
+    let size = total(counts) + 3 * melds;
+    if size % 3 != 2 {
+        return false;
+    }
+    is_standard_complete(counts)
+        || size == 14 && (is_qi_dui_complete(counts) || is_guo_shi_complete(counts))
+}
+
+/// Checks if the hand, together with the open, is complete(和牌型).
+///
+/// The hand should contain the winning card.
+/// Standard hands, seven pairs(七对子) and thirteen orphans(国士无双) are checked,
+/// where a standard hand of 17 cards needs five cases(面子), see [cases_needed].
+pub fn is_complete(hand: &Hand, open: &Open) -> bool {
+    is_counts_complete(&mut counts(hand), open.len())
+}
+
+/// Returns all the ways the hand, together with the open,
+/// splits into four cases(面子) and a pair(雀头),
+/// or five cases for the 17 cards of 台湾麻将, see [cases_needed].
+///
+/// The hand should contain the winning card.
+/// Returns an empty array if the hand is not a complete standard hand,
+/// e.g. seven pairs(七对子) and thirteen orphans(国士无双) that are not also standard.
+///
+/// The returned array has been sorted in the canonical order of [Decomposition],
+/// so when several decompositions score the same,
+/// taking the first one of them gives a reproducible result.
+pub fn decompositions(hand: &Hand, open: &Open) -> Vec<Decomposition> {
+    let mut counts = counts(hand);
+    let mut res = Vec::new();
+    if (total(&counts) + 3 * open.len()) % 3 != 2 {
+        return res;
+    }
+    for i in 0..CardType::COUNT {
+        let Some(pair) = CardType::from_index(i) else {
+            continue;
+        };
+        if counts[i] >= 2 {
+            counts[i] -= 2;
+            let mut splits = Vec::new();
+            split_cases(&mut counts, &mut Vec::new(), &mut splits);
+            res.extend(splits.into_iter().map(|mut cases| {
+                cases.sort_unstable();
+                Decomposition { pair, cases }
+            }));
+            counts[i] += 2;
+        }
+    }
+    res.sort_unstable();
+    res
+}
+
+/// Returns all the ways the hand, together with the open, is complete(和牌型),
+/// including the special forms, i.e. seven pairs(七对子) and thirteen orphans(国士无双),
+/// and the knitted forms of MCR(国标麻将), see [knitted_forms].
+///
+/// The hand should contain the winning card.
+/// A hand can be complete in several forms, e.g. 二杯口 is also 七对子,
+/// so completions scoring differently can choose among them.
+///
+/// Standard forms come first in the order of [decompositions], and the knitted forms last.
+pub fn complete_forms(hand: &Hand, open: &Open) -> Vec<FormType> {
+    let counts = counts(hand);
+    let mut res: Vec<_> = decompositions(hand, open)
+        .into_iter()
+        .map(FormType::Standard)
+        .collect();
+    if open.is_empty() && total(&counts) == 14 {
+        if is_qi_dui_complete(&counts) {
+            res.push(FormType::QiDui(
+                hand.iter()
+                    .filter(|&(_, &n)| n > 0)
+                    .map(|(&c, _)| c)
+                    .collect(),
+            ));
+        }
+        if is_guo_shi_complete(&counts) {
+            res.extend(
+                hand.iter()
+                    .find(|&(_, &n)| n == 2)
+                    .map(|(&c, _)| FormType::GuoShi(c)),
+            );
+        }
+    }
+    res.extend(knitted_forms(hand, open));
+    res
+}
+
+/// Returns all the ways the hand, together with the open, is complete in the knitted forms of MCR(国标麻将),
+/// built on the knitted straight(组合龙), i.e. 147, 258 and 369 each of a different number suit:
+/// fourteen different cards of a knitted straight and honors(字牌), known as 全不靠,
+/// and a whole knitted straight with a case and a pair, known as 组合龙,
+/// where the case may be in open.
+///
+/// The hand should contain the winning card.
+/// Other rules do not know of these forms, see [crate::ruleset::Ruleset::has_knitted_forms],
+/// so they are not checked by [is_complete] but are in [complete_forms].
+pub fn knitted_forms(hand: &Hand, open: &Open) -> Vec<FormType> {
+    let counts = counts(hand);
+    let mut res = Vec::new();
+    if total(&counts) + 3 * open.len() != 14 {
+        return res;
+    }
+    if open.is_empty()
+        && SUIT_ORDERS
+            .iter()
+            .any(|&order| is_quan_bu_kao_complete(&counts, order))
+    {
+        res.push(FormType::QuanBuKao(
+            hand.iter()
+                .filter(|&(_, &n)| n > 0)
+                .map(|(&c, _)| c)
+                .collect(),
+        ));
+    }
+    for order in SUIT_ORDERS {
+        let mut rest = counts;
+        let straight = knitted_straight(order);
+        if straight.iter().any(|&i| rest[i] == 0) {
+            continue;
+        }
+        for i in straight {
+            rest[i] -= 1;
+        }
+        res.extend(
+            decompositions(&Hand::from(TileCounts(rest)), open)
+                .into_iter()
+                .map(FormType::ZuHeLong),
+        );
+    }
+    res
+}
+
+/// Returns all the interpretations of the complete hand with the winning card,
+/// i.e. each complete form, see [complete_forms],
+/// and for a standard form each wait shape the winning card can be taken as completing.
+///
+/// The hand should contain the winning card.
+/// The returned array is in the order of [complete_forms] and then [WaitType],
+/// so when several interpretations score the same,
+/// taking the first one of them gives a reproducible result.
+pub fn interpretations(hand: &Hand, open: &Open, winning: CardType) -> Vec<Interpretation> {
+    let mut res = Vec::new();
+    for form in complete_forms(hand, open) {
+        let waits = match &form {
+            FormType::Standard(d) | FormType::ZuHeLong(d) => d.wait_types(winning),
+            _ => Vec::new(),
+        };
+        if waits.is_empty() {
+            res.push(Interpretation { form, wait: None });
+        } else {
+            res.extend(waits.into_iter().map(|wait| Interpretation {
+                form: form.clone(),
+                wait: Some(wait),
+            }));
+        }
+    }
+    res
+}
+
+/// Returns the cards that complete the hand, known as 听牌 in Chinese.
+///
+/// The hand should not contain the winning card.
+/// Cards of which the hand already contains all four are excluded.
+///
+/// The returned array has been sorted.
+pub fn waits(hand: &Hand, open: &Open) -> Vec<CardType> {
+    counts_waits(&mut counts(hand), open.len())
+}
+
+/// Returns the cards that complete the counts with the number of cases(面子) in open.
+pub(crate) fn counts_waits(counts: &mut Counts, melds: usize) -> Vec<CardType> {
+    let mut res = Vec::new();
+    for i in 0..CardType::COUNT {
+        if counts[i] >= 4 {
+            continue;
+        }
+        counts[i] += 1;
+        if is_counts_complete(counts, melds) {
+            res.extend(CardType::from_index(i));
+        }
+        counts[i] -= 1;
+    }
+    res
+}
+
+/// Checks if the hand is in drawing hand(听牌) state, i.e. any card completes it.
+pub fn is_tenpai(hand: &Hand, open: &Open) -> bool {
+    !waits(hand, open).is_empty()
+}
+
+/// Returns the hand with the last card, and whether the last card has been drawn(自摸).
+///
+/// The hand of a player who has drawn already contains the last card,
+/// i.e. it counts 14 cards with the open, or 17 in 台湾麻将.
+pub(crate) fn complete_hand(hand: &Hand, open: &Open, last_card: CardType) -> (Hand, bool) {
+    let num: usize = hand.values().map(|&n| n as usize).sum();
+    let mut hand = hand.clone();
+    if (num + 3 * open.len()) % 3 == 2 {
+        (hand, true)
+    } else {
+        *hand.entry(last_card).or_default() += 1;
+        (hand, false)
+    }
+}
+
+/// Returns all the cards of the hand with the last card and the open.
+pub(crate) fn all_cards(hand: &Hand, open: &Open, last_card: CardType) -> Vec<CardType> {
+    let (hand, _) = complete_hand(hand, open, last_card);
+    hand.iter()
+        .flat_map(|(&c, &n)| std::iter::repeat_n(c, n as usize))
+        .chain(open.iter().flat_map(CaseType::cards))
+        .collect()
+}
+
+/// Returns the decompositions of the hand with the last card,
+/// or only the one of the interpretation being checked, see [WinContext::interpretation].
+///
+/// The decomposition of a [FormType::ZuHeLong] leaves the knitted straight out,
+/// so it has fewer cases(面子) than a standard one.
+pub(crate) fn winning_decompositions(
+    hand: &Hand,
+    open: &Open,
+    last_card: CardType,
+    context: &WinContext,
+) -> Vec<Decomposition> {
+    match &context.interpretation {
+        Some(Interpretation {
+            form: FormType::Standard(d) | FormType::ZuHeLong(d),
+            ..
+        }) => vec![d.clone()],
+        Some(_) => Vec::new(),
+        None => {
+            let (hand, _) = complete_hand(hand, open, last_card);
+            decompositions(&hand, open)
+        }
+    }
+}
+
+/// Returns the complete forms of the hand with the last card,
+/// or only the one of the interpretation being checked, see [WinContext::interpretation].
+pub(crate) fn winning_forms(
+    hand: &Hand,
+    open: &Open,
+    last_card: CardType,
+    context: &WinContext,
+) -> Vec<FormType> {
+    match &context.interpretation {
+        Some(interpretation) => vec![interpretation.form.clone()],
+        None => {
+            let (hand, _) = complete_hand(hand, open, last_card);
+            complete_forms(&hand, open)
+        }
+    }
+}
+
+/// Returns the wait shapes the last card can be taken as completing in the decomposition,
+/// or only the one of the interpretation being checked, see [WinContext::interpretation].
+pub(crate) fn winning_waits(
+    decomposition: &Decomposition,
+    last_card: CardType,
+    context: &WinContext,
+) -> Vec<WaitType> {
+    match context.interpretation.as_ref().and_then(|i| i.wait) {
+        Some(wait) => vec![wait],
+        None => decomposition.wait_types(last_card),
+    }
+}
+
+/// Returns the number of concealed 刻子 and 暗杠 of the decomposition with the last card,
+/// taking the wait shape giving the most of them, see [winning_waits].
+pub(crate) fn winning_concealed_triplets(
+    decomposition: &Decomposition,
+    open: &Open,
+    last_card: CardType,
+    is_tsumo: bool,
+    context: &WinContext,
+) -> usize {
+    winning_waits(decomposition, last_card, context)
+        .into_iter()
+        .map(|wait| decomposition.concealed_triplets_by(open, wait, is_tsumo))
+        .max()
+        .unwrap_or_else(|| decomposition.concealed_triplets(open, last_card, is_tsumo))
+}
+
+/// Returns all the cases of a decomposition, including those in open.
+pub(crate) fn all_cases(decomposition: &Decomposition, open: &Open) -> Vec<CaseType> {
+    decomposition
+        .cases
+        .iter()
+        .chain(open.iter())
+        .copied()
+        .collect()
+}
@@ -0,0 +1,158 @@
+//! Bots choosing the actions of players, and a simple one based on tile efficiency(牌效).
+//!
+//! A [Bot] sees only what its player is shown, see [PlayerView],
+//! together with the actions legal at the moment, see [crate::engine::Engine::legal_actions],
+//! so it can not cheat even when run by the engine directly.
+//! The [EfficiencyBot] is a reference for custom bots, and an opponent out of the box.
+
+use std::cmp::Reverse;
+
+use crate::{
+    card_type::{CardType, SuitType, ZiType},
+    cards::Open,
+    engine::Action,
+    packed_hand::PackedHand,
+    view::PlayerView,
+};
+
+/// The trait of bots, choosing an action for the player seeing the view.
+pub trait Bot {
+    /// Chooses one of the actions legal at the moment, which is never empty.
+    fn choose_action(&mut self, view: &PlayerView, actions: &[Action]) -> Action;
+}
+
+/// The bot playing for tile efficiency(牌效).
+///
+/// It completes whenever possible, declares the suit it holds the fewest of as void in 血战到底,
+/// sets aside 北 in 三麻 whenever possible, declares 立直 whenever possible,
+/// and plays the card leaving the lowest shanten number(向听数)
+/// and then the most useful tiles(有效牌) not seen yet.
+/// It only calls 碰 on dragons(三元牌), which always make the hand valid(役牌),
+/// and only if the call brings the hand closer to ready.
+#[derive(Default, Clone, Copy)]
+pub struct EfficiencyBot;
+
+impl Bot for EfficiencyBot {
+    fn choose_action(&mut self, view: &PlayerView, actions: &[Action]) -> Action {
+        if let Some(&win) = actions
+            .iter()
+            .find(|a| matches!(a, Action::Tsumo | Action::Ron))
+        {
+            return win;
+        }
+        if let Some((_, void)) = actions
+            .iter()
+            .filter_map(|&a| match a {
+                Action::DeclareVoid(suit) => Some((suit_count(view, suit), a)),
+                _ => None,
+            })
+            .min_by_key(|&(n, _)| n)
+        {
+            return void;
+        }
+        if actions.contains(&Action::Nuki) {
+            return Action::Nuki;
+        }
+        let open = view
+            .seats
+            .iter()
+            .find(|seat| seat.side == view.side)
+            .map(|seat| seat.open.clone())
+            .unwrap_or_default();
+        let riichi = actions
+            .iter()
+            .filter_map(|&a| match a {
+                Action::Riichi(card) => Some((card, a)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        if !riichi.is_empty() {
+            return best_discard(view, &open, &riichi).unwrap_or(actions[0]);
+        }
+        let discards = actions
+            .iter()
+            .filter_map(|&a| match a {
+                Action::Discard(card) => Some((card, a)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        if let Some(action) = best_discard(view, &open, &discards) {
+            return action;
+        }
+        if actions.contains(&Action::Pon) && worth_pon(view, &open) {
+            return Action::Pon;
+        }
+        if actions.contains(&Action::Pass) {
+            return Action::Pass;
+        }
+        actions[0]
+    }
+}
+
+/// Returns the action playing the card which leaves the lowest shanten number(向听数),
+/// and then the most useful tiles(有效牌) not seen yet,
+/// or `None` if no card can be played.
+fn best_discard(view: &PlayerView, open: &Open, choices: &[(CardType, Action)]) -> Option<Action> {
+    let packed = PackedHand::from(&view.hand);
+    let mut best: Option<((i8, Reverse<usize>), Action)> = None;
+    for &(card, action) in choices {
+        let mut hand = packed;
+        hand.remove(card);
+        let shanten = hand.shanten(open.len());
+        let useful = hand
+            .useful_tiles(open.len())
+            .into_iter()
+            .map(|c| unseen(view, &hand, c))
+            .sum();
+        let key = (shanten, Reverse(useful));
+        if best.is_none_or(|(k, _)| key < k) {
+            best = Some((key, action));
+        }
+    }
+    best.map(|(_, action)| action)
+}
+
+/// Checks if calling 碰 on the card played is worth it,
+/// i.e. the card is a dragon(三元牌) and the call reduces the shanten number(向听数).
+fn worth_pon(view: &PlayerView, open: &Open) -> bool {
+    let Some((_, card)) = view.last_discard else {
+        return false;
+    };
+    if !matches!(card, CardType::Zi(ZiType::Jian(_))) {
+        return false;
+    }
+    let mut hand = PackedHand::from(&view.hand);
+    let before = hand.shanten(open.len());
+    hand.remove(card);
+    hand.remove(card);
+    // The card played after the call is chosen later, so the best one is assumed.
+    let after = view
+        .hand
+        .keys()
+        .filter(|&&c| hand.count(c) > 0)
+        .map(|&c| {
+            let mut rest = hand;
+            rest.remove(c);
+            rest.shanten(open.len() + 1)
+        })
+        .min()
+        .unwrap_or(before);
+    after < before
+}
+
+/// Returns the number of the cards of the suit in the player's hand.
+fn suit_count(view: &PlayerView, suit: SuitType) -> usize {
+    view.hand
+        .iter()
+        .filter(|(c, _)| c.suit() == suit)
+        .map(|(_, &n)| n as usize)
+        .sum()
+}
+
+/// Returns the number of the card not seen by the player, i.e. not shown nor in hand.
+fn unseen(view: &PlayerView, hand: &PackedHand, card: CardType) -> usize {
+    view.rules
+        .ruleset()
+        .copies(card)
+        .saturating_sub(view.seen_tiles[card] + hand.count(card)) as usize
+}
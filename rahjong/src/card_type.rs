@@ -2,9 +2,23 @@
 //!
 //! The core of this module is the [CardType] enum, which, as its name suggests,
 //! is the type of a card, contianing both the suit and the rank.
+//!
+//! Cards are displayed in the standard notation:
+//! a number card is its rank followed by `m` for 万, `s` for 条 or `p` for 筒, e.g. `3m`,
+//! and a 字 is its Chinese character, e.g. `东` or `中`.
+//!
+//! Cards can be parsed from the same notation, see [parse_cards],
+//! where ranks of the same suit can share the suit, e.g. `123m`,
+//! `0` stands for a five,
+//! and a 字 can also be written as `1z` to `7z` for 东, 南, 西, 北, 白, 发 and 中.
+
+use std::{fmt, str::FromStr};
+
+use crate::error::ParseCardError;
 
 /// The `CardType` type. See [the module level documentation](self) for more.
 #[derive(PartialEq, Eq, Copy, Clone, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CardType {
     /// The 万 suit. Contains a [RankType] indicating the rank of the card.
     Wan(RankType),
@@ -18,6 +32,7 @@ pub enum CardType {
 
 /// The `RankType` type. Represents the rank of a card.
 #[derive(PartialEq, Eq, Clone, Copy, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RankType {
     One,
     Two,
@@ -30,8 +45,23 @@ pub enum RankType {
     Nine,
 }
 
+/// The `SuitType` type. Represents the suit of a card, regardless of its rank.
+#[derive(PartialEq, Eq, Clone, Copy, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SuitType {
+    /// The 万 suit.
+    Wan,
+    /// The 条 suit.
+    Tiao,
+    /// The 筒 suit.
+    Tong,
+    /// The 字 suit.
+    Zi,
+}
+
 /// The `ZiType` suit. Represents the type of 字, which includes both 箭牌 and 风牌.
 #[derive(PartialEq, Eq, Clone, Copy, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ZiType {
     /// The 箭 type. Contains a [JianType] indicating the rank of the card.
     Jian(JianType),
@@ -41,6 +71,7 @@ pub enum ZiType {
 
 /// The `JianType` type. Represents the rank of a card.
 #[derive(PartialEq, Eq, Clone, Copy, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum JianType {
     /// The 白 type.
     Bai,
@@ -52,10 +83,12 @@ pub enum JianType {
 
 /// The `FengType` type. Represents the rank of a card.
 /// Also used to represent the player.
-#[derive(PartialEq, Eq, Clone, Copy, PartialOrd, Ord)]
+#[derive(PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FengType {
     /// The 东 type.
     /// Also the 东 player.
+    #[default]
     Dong,
     /// The 南 type.
     /// Also the 南 player.
@@ -68,6 +101,43 @@ pub enum FengType {
     Bei,
 }
 
+/// The `HuaType` type. Represents a flower card(花牌),
+/// which is not a [CardType] since it never stays in hand.
+#[derive(PartialEq, Eq, Clone, Copy, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HuaType {
+    /// The 春 type.
+    Chun,
+    /// The 夏 type.
+    Xia,
+    /// The 秋 type.
+    Qiu,
+    /// The 冬 type.
+    Dong,
+    /// The 梅 type.
+    Mei,
+    /// The 兰 type.
+    Lan,
+    /// The 竹 type.
+    Zhu,
+    /// The 菊 type.
+    Ju,
+}
+
+impl HuaType {
+    /// All the 花 types, i.e. the four seasons followed by the four flowers.
+    pub const ALL: [HuaType; 8] = [
+        Self::Chun,
+        Self::Xia,
+        Self::Qiu,
+        Self::Dong,
+        Self::Mei,
+        Self::Lan,
+        Self::Zhu,
+        Self::Ju,
+    ];
+}
+
 /// The trait implemented by card types
 /// which contains a function
 /// indicating the cycled next card type.
@@ -133,8 +203,246 @@ impl Next for FengType {
     }
 }
 
-impl Default for FengType {
-    fn default() -> Self {
-        Self::Dong
+impl FengType {
+    /// All the 风 types, in the order of players.
+    pub const ALL: [FengType; 4] = [Self::Dong, Self::Nan, Self::Xi, Self::Bei];
+}
+
+impl CardType {
+    /// The number of different card types.
+    pub const COUNT: usize = 34;
+
+    /// Returns the dora(宝牌) indicated by the card as a dora indicator(宝牌指示牌),
+    /// i.e. the cycled next card type, see [Next].
+    pub fn dora(&self) -> CardType {
+        self.next()
+    }
+
+    /// Returns the index of the card type, ranged in `0..CardType::COUNT`.
+    ///
+    /// The index follows the order of card types, i.e. 万, 条, 筒, 箭 and 风.
+    pub fn index(&self) -> usize {
+        match *self {
+            Self::Wan(n) => n as usize,
+            Self::Tiao(n) => 9 + n as usize,
+            Self::Tong(n) => 18 + n as usize,
+            Self::Zi(ZiType::Jian(jian)) => 27 + jian as usize,
+            Self::Zi(ZiType::Feng(feng)) => 30 + feng as usize,
+        }
+    }
+
+    /// Returns the card type of the index, the reverse of [CardType::index].
+    ///
+    /// Returns `None` if the index is out of range.
+    pub fn from_index(index: usize) -> Option<Self> {
+        const RANKS: [RankType; 9] = [
+            RankType::One,
+            RankType::Two,
+            RankType::Three,
+            RankType::Four,
+            RankType::Five,
+            RankType::Six,
+            RankType::Seven,
+            RankType::Eight,
+            RankType::Nine,
+        ];
+        const JIANS: [JianType; 3] = [JianType::Bai, JianType::Fa, JianType::Zhong];
+        const FENGS: [FengType; 4] = [FengType::Dong, FengType::Nan, FengType::Xi, FengType::Bei];
+        Some(match index {
+            0..=8 => Self::Wan(RANKS[index]),
+            9..=17 => Self::Tiao(RANKS[index - 9]),
+            18..=26 => Self::Tong(RANKS[index - 18]),
+            27..=29 => Self::Zi(ZiType::Jian(JIANS[index - 27])),
+            30..=33 => Self::Zi(ZiType::Feng(FENGS[index - 30])),
+            _ => return None,
+        })
+    }
+
+    /// Returns the suit of the card.
+    pub fn suit(&self) -> SuitType {
+        match *self {
+            Self::Wan(_) => SuitType::Wan,
+            Self::Tiao(_) => SuitType::Tiao,
+            Self::Tong(_) => SuitType::Tong,
+            Self::Zi(_) => SuitType::Zi,
+        }
+    }
+
+    /// Returns the rank of the card, or `None` if the card is a 字.
+    pub fn rank(&self) -> Option<RankType> {
+        match *self {
+            Self::Wan(n) | Self::Tiao(n) | Self::Tong(n) => Some(n),
+            Self::Zi(_) => None,
+        }
+    }
+
+    /// Checks if the card is a simple(中张牌), i.e. a number card ranked from 2 to 8.
+    pub fn is_simple(&self) -> bool {
+        matches!(self.rank(), Some(n) if n != RankType::One && n != RankType::Nine)
+    }
+
+    /// Checks if the card is a terminal or an honor(幺九牌), i.e. not a simple.
+    pub fn is_yao_jiu(&self) -> bool {
+        !self.is_simple()
+    }
+}
+
+impl fmt::Display for RankType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", *self as u8 + 1)
+    }
+}
+
+impl fmt::Display for SuitType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Wan => "m",
+            Self::Tiao => "s",
+            Self::Tong => "p",
+            Self::Zi => "z",
+        })
+    }
+}
+
+impl fmt::Display for JianType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Bai => "白",
+            Self::Fa => "发",
+            Self::Zhong => "中",
+        })
+    }
+}
+
+impl fmt::Display for FengType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Dong => "东",
+            Self::Nan => "南",
+            Self::Xi => "西",
+            Self::Bei => "北",
+        })
+    }
+}
+
+impl fmt::Display for ZiType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Jian(jian) => jian.fmt(f),
+            Self::Feng(feng) => feng.fmt(f),
+        }
+    }
+}
+
+impl fmt::Display for HuaType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Chun => "春",
+            Self::Xia => "夏",
+            Self::Qiu => "秋",
+            Self::Dong => "冬",
+            Self::Mei => "梅",
+            Self::Lan => "兰",
+            Self::Zhu => "竹",
+            Self::Ju => "菊",
+        })
+    }
+}
+
+impl fmt::Display for CardType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Wan(n) | Self::Tiao(n) | Self::Tong(n) => write!(f, "{n}{}", self.suit()),
+            Self::Zi(zi) => zi.fmt(f),
+        }
+    }
+}
+
+/// Debugs the types of cards in the same notation as they are displayed.
+macro_rules! debug_as_display {
+    ($($t:ty),*) => {
+        $(
+            impl fmt::Debug for $t {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    fmt::Display::fmt(self, f)
+                }
+            }
+        )*
+    };
+}
+
+debug_as_display!(CardType, RankType, SuitType, ZiType, JianType, FengType, HuaType);
+
+/// Returns the 字 of the Chinese character, if any.
+fn zi_of_char(c: char) -> Option<ZiType> {
+    Some(match c {
+        '东' => ZiType::Feng(FengType::Dong),
+        '南' => ZiType::Feng(FengType::Nan),
+        '西' => ZiType::Feng(FengType::Xi),
+        '北' => ZiType::Feng(FengType::Bei),
+        '白' => ZiType::Jian(JianType::Bai),
+        '发' => ZiType::Jian(JianType::Fa),
+        '中' => ZiType::Jian(JianType::Zhong),
+        _ => return None,
+    })
+}
+
+/// Returns the card of the rank in the suit, given as characters in the standard notation.
+fn card_of_chars(rank: char, suit: char) -> Result<CardType, ParseCardError> {
+    let n = match rank {
+        '0' => 5,
+        _ => rank.to_digit(10).ok_or(ParseCardError::InvalidChar(rank))? as usize,
+    };
+    let index = match suit {
+        'm' if (1..=9).contains(&n) => n - 1,
+        's' if (1..=9).contains(&n) => 8 + n,
+        'p' if (1..=9).contains(&n) => 17 + n,
+        'z' if (1..=4).contains(&n) => 29 + n,
+        'z' if (5..=7).contains(&n) => 22 + n,
+        'm' | 's' | 'p' | 'z' => return Err(ParseCardError::InvalidRank(rank)),
+        _ => return Err(ParseCardError::InvalidChar(suit)),
+    };
+    CardType::from_index(index).ok_or(ParseCardError::InvalidRank(rank))
+}
+
+/// Parses the cards in the standard notation, see [the module level documentation](self),
+/// e.g. `123m456p789s东东白白白`, in the order written.
+///
+/// Whitespaces are ignored.
+pub fn parse_cards(s: &str) -> Result<Vec<CardType>, ParseCardError> {
+    let mut res = Vec::new();
+    let mut ranks = Vec::new();
+    for c in s.chars().filter(|c| !c.is_whitespace()) {
+        if c.is_ascii_digit() {
+            ranks.push(c);
+        } else if let Some(zi) = zi_of_char(c) {
+            if !ranks.is_empty() {
+                return Err(ParseCardError::MissingSuit);
+            }
+            res.push(CardType::Zi(zi));
+        } else {
+            if ranks.is_empty() {
+                return Err(ParseCardError::InvalidChar(c));
+            }
+            for rank in ranks.drain(..) {
+                res.push(card_of_chars(rank, c)?);
+            }
+        }
+    }
+    if !ranks.is_empty() {
+        return Err(ParseCardError::MissingSuit);
+    }
+    Ok(res)
+}
+
+impl FromStr for CardType {
+    type Err = ParseCardError;
+
+    /// Parses a single card in the standard notation, e.g. `5s`, `1z` or `中`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match parse_cards(s)?.as_slice() {
+            &[card] => Ok(card),
+            _ => Err(ParseCardError::NotSingleCard),
+        }
     }
 }
@@ -2,23 +2,133 @@
 //!
 //! The core of this module is the [Cards] struct, which contains the states of the game.
 
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    ops::{Index, IndexMut},
+    sync::Arc,
+};
 
-use rand::seq::SliceRandom;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
 
 use crate::{
-    card_type::{CardType, FengType, JianType, Next, RankType, ZiType},
+    aka::{self, Aka},
+    analysis::{self, FormType, Interpretation},
+    card_type::{CardType, FengType, JianType, Next, RankType, SuitType, ZiType},
     case_type::CaseType,
-    river_type::RiverType,
+    dice::{self, Dice},
+    draw_reason::DrawReason,
+    error::{CallError, ParseCardError, PlayError},
+    flower::Flowers,
+    game::RIICHI_DEPOSIT,
+    riichi::{DoraBreakdown, YAKUMAN_FAN},
+    river_type::{RiverCard, RiverType},
+    rule_config::{KanDoraTiming, RuleConfig},
+    ruleset::{
+        HkosRules, McrRules, RiichiRules, Ruleset, SanmaRules, SharedRuleset, SichuanRules,
+        TaiwaneseRules, ZungJungRules,
+    },
+    scoring::{self, AgariResult, Payment},
+    tile_counts::TileCounts,
+    view::{PlayerView, SeatView},
 };
 
-type Hand = BTreeMap<CardType, u8>;
+/// The cards in hand, mapping each card type to its count.
+pub type Hand = BTreeMap<CardType, u8>;
+
+/// Parses the cards of a hand in the standard notation, e.g. `123m456p77z`,
+/// see [crate::card_type::parse_cards].
+///
+/// Returns an error if a card appears more than 4 times.
+pub fn parse_hand(s: &str) -> Result<Hand, ParseCardError> {
+    let mut hand = Hand::new();
+    for card in crate::card_type::parse_cards(s)? {
+        let count = hand.entry(card).or_default();
+        *count += 1;
+        if *count > 4 {
+            return Err(ParseCardError::TooManyCopies);
+        }
+    }
+    Ok(hand)
+}
+
+/// The cards in river, in the order of being played.
+pub type River = Vec<RiverCard>;
 
-type River = Vec<RiverType>;
+/// The cases(面子) of open, in the order of being called.
+pub type Open = Vec<CaseType>;
 
-type Open = Vec<CaseType>;
+/// A function checking if a situation holds for the hand, river and open of a player with the last card,
+/// given where the last card comes from.
+///
+/// It may capture what the situation depends on, e.g. the card of a 役牌 or the options of the rules,
+/// while a plain function is a [SituationFn], see [crate::situations::collect_checkers].
+pub type SituationChecker =
+    Box<dyn Fn(&Hand, &River, &Open, CardType, &WinContext) -> bool + Send + Sync>;
+
+/// A plain function checking if a situation holds, capturing nothing, see [SituationChecker].
+pub type SituationFn =
+    fn(hand: &Hand, river: &River, open: &Open, draw: CardType, context: &WinContext) -> bool;
 
-type SituationChecker = fn(hand: &Hand, river: &River, open: &Open, draw: CardType) -> bool;
+/// The `CardSource` type. Represents where the last card checked for completion comes from.
+#[derive(PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CardSource {
+    /// The card is drawn from the mountain.
+    #[default]
+    Mountain,
+    /// The card is a replacement card(岭上牌) drawn after a 杠.
+    Replacement,
+    /// The card is played by another player.
+    River,
+    /// The card is added to an 加杠 by another player, known as 抢杠 in Chinese.
+    Robbed,
+}
+
+/// The context of the last card checked for completion, given to situation checkers,
+/// see [Cards::win_context].
+#[derive(PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WinContext {
+    /// Where the last card comes from.
+    pub source: CardSource,
+    /// Whether no cards are left in mountain,
+    /// i.e. the last card is the last one drawn(海底) or played(河底) in the hand.
+    pub is_last: bool,
+    /// The winds counted as 役牌, i.e. the wind of the round and the wind of the seat in this order,
+    /// see [Cards::yakuhai_winds].
+    pub winds: Vec<FengType>,
+    /// The number of each card type shown to all players in rivers and in open, 暗杠 excluded,
+    /// not counting the last card itself, e.g. 3 for the last card when it is the last copy(绝张).
+    pub shown: TileCounts,
+    /// The interpretation of the hand being checked, see [Cards::interpret].
+    ///
+    /// Situations depending on how the hand is read only look at this interpretation,
+    /// or at any of them if `None`.
+    pub interpretation: Option<Interpretation>,
+}
+
+impl WinContext {
+    /// Checks if the last card has been drawn(自摸), including a replacement card(岭上牌).
+    pub fn is_tsumo(&self) -> bool {
+        matches!(self.source, CardSource::Mountain | CardSource::Replacement)
+    }
+}
+
+/// The `Reading` type. Represents the interpretation of a complete hand chosen for scoring,
+/// and what it scores, see [Cards::interpret].
+pub struct Reading<'a> {
+    /// The interpretation of the hand.
+    ///
+    /// `None` if the hand is complete in none of the forms of [analysis::complete_forms],
+    /// in which case the situations are checked against any interpretation.
+    pub interpretation: Option<Interpretation>,
+    /// The completions met.
+    pub completions: Vec<&'a Completion>,
+    /// The total fan(番数) of the completions.
+    pub fan: u16,
+    /// The fu(符) of the interpretation, see [crate::riichi::fu], or `0` if there is no interpretation.
+    pub fu: u8,
+}
 
 /// Checks if a completion is met.
 pub struct Completion {
@@ -34,51 +144,329 @@ pub struct Completion {
     pub fan: u16,
     /// The name of the completion.
     pub name: &'static str,
+    /// Whether the completion is declared in full, always `true` when built by [Completion::builder].
     pub valid: bool,
 }
 
+impl Completion {
+    /// Starts building a completion of the name, without situations and of 0 fan,
+    /// see [CompletionBuilder] and [crate::completion!].
+    pub fn builder(name: &'static str) -> CompletionBuilder {
+        CompletionBuilder {
+            completion: Self {
+                required: Vec::new(),
+                forbidden: Vec::new(),
+                fan: 0,
+                name,
+                valid: true,
+            },
+        }
+    }
+}
+
+/// The builder of a [Completion], see [Completion::builder].
+pub struct CompletionBuilder {
+    completion: Completion,
+}
+
+impl CompletionBuilder {
+    /// Requires the situation for the completion.
+    pub fn require(mut self, situation: &'static str) -> Self {
+        self.completion.required.push(situation);
+        self
+    }
+
+    /// Requires all the situations for the completion.
+    pub fn require_all(mut self, situations: impl IntoIterator<Item = &'static str>) -> Self {
+        self.completion.required.extend(situations);
+        self
+    }
+
+    /// Forbids the situation for the completion.
+    pub fn forbid(mut self, situation: &'static str) -> Self {
+        self.completion.forbidden.push(situation);
+        self
+    }
+
+    /// Forbids all the situations for the completion.
+    pub fn forbid_all(mut self, situations: impl IntoIterator<Item = &'static str>) -> Self {
+        self.completion.forbidden.extend(situations);
+        self
+    }
+
+    /// Sets the fan(番数) of the completion.
+    pub fn fan(mut self, fan: u16) -> Self {
+        self.completion.fan = fan;
+        self
+    }
+
+    /// Returns the completion built.
+    pub fn build(self) -> Completion {
+        self.completion
+    }
+}
+
+/// Declares a [Completion] by its name and fan(番数),
+/// followed by the situations required and forbidden if any, see [Completion::builder],
+/// e.g. `completion!("一杯口", 1, requires ["一杯口"], forbids ["二杯口"])`.
+#[macro_export]
+macro_rules! completion {
+    (
+        $name:expr, $fan:expr
+        $(, requires [$($required:expr),* $(,)?])?
+        $(, forbids [$($forbidden:expr),* $(,)?])?
+        $(,)?
+    ) => {
+        $crate::cards::Completion::builder($name)
+            .fan($fan)
+            $($(.require($required))*)?
+            $($(.forbid($forbidden))*)?
+            .build()
+    };
+}
+
+/// The struct of the cards and points of a player, see [Cards::players].
+#[derive(PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Player {
+    /// The cards in the player's hand, not shown to other players, known as 手牌 in Chinese, sorted by default.
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "serialize_hand",
+            deserialize_with = "deserialize_hand"
+        )
+    )]
+    pub hand: Hand,
+    /// The cards in the player's river, shown to other players, known as 牌河 in Chinese.
+    pub river: River,
+    /// The cards not in the player's river, shown to other players, known as 副露 in Chinese.
+    pub open: Open,
+    /// The points of the player, known as 点数 in Chinese.
+    pub score: i32,
+    /// The cards played by the others after the player's 立直,
+    /// safe against the player for the rest of the hand as those in the river, see [Cards::safe_tiles].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub safe: BTreeSet<CardType>,
+    /// The cards played by the others since the player's last play,
+    /// safe against the player until the next play for 同巡振听, see [Cards::safe_tiles].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub passed: BTreeSet<CardType>,
+    /// The card called from a river into each case(面子) in open, in the order of [Player::open],
+    /// or `None` for an 暗杠.
+    ///
+    /// An 加杠 keeps the card called into the 刻子 it is added to.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub called: Vec<Option<CalledCard>>,
+}
+
+/// The struct of a card called from a river into a case(面子) in open, see [Player::called].
+#[derive(PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CalledCard {
+    /// The player who played the card.
+    pub from: FengType,
+    /// The card called.
+    pub card: CardType,
+    /// Whether the card called is a red five(赤宝牌).
+    pub red: bool,
+}
+
 /// The struct containing card states of the game.
 #[derive(Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Cards {
     /// The cards in mountain, known as 牌山 in Chinese.
+    ///
+    /// The dead wall is not included, so the hand is drawn(流局) when it becomes empty.
     pub card_mountain: Vec<CardType>,
-    /// The cards in player 东's hand, not shown to other players, known as 手牌 in Chinese, sorted by default.
-    pub dong_hand: Hand,
-    /// The cards in player 南's hand, not shown to other players, known as 手牌 in Chinese, sorted by default.
-    pub nan_hand: Hand,
-    /// The cards in player 西's hand, not shown to other players, known as 手牌 in Chinese, sorted by default.
-    pub xi_hand: Hand,
-    /// The cards in player 北's hand, not shown to other players, known as 手牌 in Chinese, sorted by default.
-    pub bei_hand: Hand,
-    /// The cards in player 东's river, shown to other players, known as 牌河 in Chinese.
-    pub dong_river: River,
-    /// The cards in player 南's river, shown to other players, known as 牌河 in Chinese.
-    pub nan_river: River,
-    /// The cards in player 西's river, shown to other players, known as 牌河 in Chinese.
-    pub xi_river: River,
-    /// The cards in player 北's river, shown to other players, known as 牌河 in Chinese.
-    pub bei_river: River,
-    /// The cards not in player 东's river, shown to other players, known as 副露 in Chinese.
-    pub dong_open: Open,
-    /// The cards not in player 南's river, shown to other players, known as 副露 in Chinese.
-    pub nan_open: Open,
-    /// The cards not in player 西's river, shown to other players, known as 副露 in Chinese.
-    pub xi_open: Open,
-    /// The cards not in player 北's river, shown to other players, known as 副露 in Chinese.
-    pub bei_open: Open,
+    /// The 14 cards set apart from the mountain, known as 王牌 in Chinese.
+    ///
+    /// The first cards are the replacement cards(岭上牌) drawn after a 杠, see [Cards::draw_replacement],
+    /// followed by the five dora indicators(宝牌指示牌) and the five ura dora indicators(里宝牌指示牌) under them.
+    pub dead_wall: Vec<CardType>,
+    /// The number of replacement cards(岭上牌) drawn in this hand.
+    pub replacements_drawn: u8,
+    /// The number of dora indicators(宝牌指示牌) revealed in this hand, see [Cards::dora_indicators].
+    pub dora_revealed: u8,
+    /// The number of dora indicators of 杠 to reveal when the active player plays the next card,
+    /// see [KanDoraTiming::AfterDiscard].
+    pub pending_dora: u8,
+    /// The red fives(赤宝牌) of the game.
+    pub aka: Aka,
+    /// The flower cards(花牌) of the game.
+    pub flowers: Flowers,
+    /// The dice rolled at the start of the hand, telling where the wall is broken, see [crate::dice].
+    ///
+    /// Rolled when the wall is built, or given with the cards on the table, see [Cards::reset_with_table].
+    pub dice: Dice,
+    /// The cards and points of each player, in the order of [FengType::ALL],
+    /// also indexed by the side, e.g. `cards[FengType::Nan].hand`,
+    /// see [Cards::hand], [Cards::river], [Cards::open] and [Cards::score].
+    ///
+    /// There are always four of them, while the players of the game are given by [Cards::players].
+    pub players: [Player; 4],
     /// The player who should play a card.
     pub active_player: FengType,
+    /// The wind of the current round, known as 场风 in Chinese, see [Cards::yakuhai_winds].
+    pub round_wind: FengType,
+    /// The dealer of the current hand, known as 庄家 in Chinese,
+    /// who is the active player when the hand starts, see [Cards::seat_wind].
+    pub dealer: FengType,
+    /// The number of cards played by all players in the hand,
+    /// i.e. the turn of the next card played, see [RiverCard::turn].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub turn: u16,
+    /// The number of each card type shown to all players.
+    ///
+    /// Counts cards in rivers and cards revealed from hands by call actions,
+    /// so the card being called is counted only once.
+    pub seen_tiles: TileCounts,
+    /// The card the active player has drawn this turn, if any.
+    pub drawn: Option<CardType>,
+    /// Whether the active player is drawing or has drawn a replacement card(岭上牌),
+    /// i.e. has done a 杠 and not yet played a card.
+    pub rinshan: bool,
+    /// The players still eligible for 一发,
+    /// i.e. having declared 立直 and no call action or their next play happened since.
+    pub ippatsu: BTreeSet<FengType>,
+    /// Whether any call action has happened in the hand,
+    /// including 暗杠, which interrupts the first go-around(第一巡).
+    pub interrupted: bool,
+    /// The players who have declared 立直 with their first play in the first go-around(第一巡),
+    /// known as 两立直 in Chinese.
+    pub double_riichi: BTreeSet<FengType>,
+    /// The players who have declared 立直 openly, known as 开立直 in Chinese,
+    /// showing their waits to the other players, see [Cards::declare_open_riichi].
+    pub open_riichi: BTreeSet<FengType>,
+    /// The players who have passed on a 荣和 since their last play,
+    /// known as 同巡振听 in Chinese.
+    pub passed_ron: BTreeSet<FengType>,
+    /// The players who have passed on a 荣和 after declaring 立直,
+    /// staying in 振听 state until the end of the hand, known as 立直振听 in Chinese.
+    pub riichi_furiten: BTreeSet<FengType>,
+    /// The card last played and the player who played it,
+    /// until the other players have passed on it.
+    pub last_discard: Option<(FengType, CardType)>,
+    /// The 加杠 just done and the player who did it,
+    /// which other players can rob(抢杠) until the replacement card(岭上牌) is drawn,
+    /// see [Cards::check_chankan].
+    pub jia_gang: Option<(FengType, CardType)>,
+    /// The cards the active player can not play right after a 吃 or 碰, known as 食替 in Chinese,
+    /// unless allowed by [RuleConfig::kuikae].
+    pub kuikae: Vec<CardType>,
+    /// The number of 北 each player has set aside as dora in 三麻, known as 拔北宝牌 in Chinese,
+    /// see [Cards::nuki_dora].
+    pub nuki: BTreeMap<FengType, u8>,
+    /// The player liable for the completion of each player, known as 包牌 or pao in Chinese,
+    /// i.e. who played the card called as the third triplet of 三元牌 or the fourth of 风牌,
+    /// see [scoring::pao_payment].
+    pub pao: BTreeMap<FengType, FengType>,
+    /// The suit each player has declared void in 血战到底, known as 定缺 in Chinese,
+    /// see [Cards::declare_void].
+    pub voids: BTreeMap<FengType, SuitType>,
+    /// The players who have completed in 血战到底, in the order of completing,
+    /// with the player who played the winning card, or `None` for 自摸, see [Cards::finish].
+    pub finished: Vec<(FengType, Option<FengType>)>,
     /// Functions used to indicate the situations of a player,
     /// including the name of the situation,
     /// and whether the situation is met.
+    ///
+    /// Not serialized, so they should be registered again after deserializing,
+    /// e.g. by [crate::riichi::register].
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub situation_checkers: HashMap<&'static str, SituationChecker>,
     /// Functions used to indicate if the current state satisfies a complete(known as 和牌 in Chinese) condition.
+    ///
+    /// Not serialized, see [Cards::situation_checkers].
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub completion_checkers: Vec<Completion>,
+    /// The options of the rules, e.g. 食断, see [Cards::with_config].
+    pub config: RuleConfig,
+    /// The random number generator building the walls, see [Cards::new_with_seed],
+    /// or `None` to use [rand::thread_rng].
+    ///
+    /// Not serialized, so the walls are random after deserializing.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub rng: Option<StdRng>,
+    /// The rules of the game, riichi mahjong by default, see [Cards::rules] and [Cards::with_ruleset].
+    ///
+    /// Not serialized, so they should be set again after deserializing,
+    /// together with their checkers, see [Ruleset::register].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub ruleset: SharedRuleset,
+}
+
+/// Serializes a hand as an array of card types and their counts,
+/// since card types are not strings and can not be keys of a JSON object.
+#[cfg(feature = "serde")]
+pub(crate) fn serialize_hand<S: serde::Serializer>(
+    hand: &Hand,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(hand)
+}
+
+/// Deserializes a hand from an array of card types and their counts, see [serialize_hand].
+#[cfg(feature = "serde")]
+pub(crate) fn deserialize_hand<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Hand, D::Error> {
+    let pairs: Vec<(CardType, u8)> = serde::Deserialize::deserialize(deserializer)?;
+    Ok(pairs.into_iter().collect())
+}
+
+/// Returns the cards that can not be played right after calling the case with the discard.
+///
+/// These are the discard itself, and for 吃 from either end,
+/// the card on the other side of the case completing the same shape(筋食替).
+fn kuikae(case: CaseType, discard: CardType) -> Vec<CardType> {
+    match case {
+        CaseType::Ke(card) => vec![card],
+        CaseType::Shun(start) => {
+            let mut res = vec![discard];
+            let rank = start.rank().unwrap_or(RankType::One);
+            if discard == start && rank <= RankType::Six {
+                res.push(start.next().next().next());
+            } else if discard == start.next().next() && rank >= RankType::Two {
+                res.extend(CardType::from_index(start.index() - 1));
+            }
+            res
+        }
+        CaseType::Gang(_) | CaseType::AnGang(_) => Vec::new(),
+    }
 }
 
-/// Initialize the mountain without shuffle.
-fn init() -> Vec<CardType> {
-    [
+/// Checks if the open holds all the triplets of 三元牌, or of 风牌,
+/// after calling the card played, i.e. the player who played it is liable(包牌), see [Cards::pao].
+fn is_pao_case(open: &Open, discard: CardType) -> bool {
+    let CardType::Zi(zi) = discard else {
+        return false;
+    };
+    let triplets = open
+        .iter()
+        .filter(|case| match case {
+            CaseType::Ke(c) | CaseType::Gang(c) | CaseType::AnGang(c) => matches!(
+                (c, zi),
+                (CardType::Zi(ZiType::Jian(_)), ZiType::Jian(_))
+                    | (CardType::Zi(ZiType::Feng(_)), ZiType::Feng(_))
+            ),
+            CaseType::Shun(_) => false,
+        })
+        .count();
+    match zi {
+        ZiType::Jian(_) => triplets == 3,
+        ZiType::Feng(_) => triplets == 4,
+    }
+}
+
+/// The 北 card, set aside as a dora in 三麻, see [Cards::nuki_dora].
+const NORTH: CardType = CardType::Zi(ZiType::Feng(FengType::Bei));
+
+/// Initialize the mountain without shuffle,
+/// with the copies of each card type given by the rules, see [Ruleset::copies].
+fn init(rules: &dyn Ruleset) -> Vec<CardType> {
+    let all = [
         CardType::Wan(RankType::One),
         CardType::Wan(RankType::Two),
         CardType::Wan(RankType::Three),
@@ -113,20 +501,24 @@ fn init() -> Vec<CardType> {
         CardType::Zi(ZiType::Feng(FengType::Nan)),
         CardType::Zi(ZiType::Feng(FengType::Xi)),
         CardType::Zi(ZiType::Feng(FengType::Bei)),
-    ]
-    .repeat(4)
+    ];
+    (0..4)
+        .flat_map(|round| {
+            all.into_iter()
+                .filter(move |&card| round < rules.copies(card))
+        })
+        .collect()
 }
 
 /// Shuffle the mountain using [rand].
-fn shuffle(cards: &mut [CardType]) {
-    let mut rng = rand::thread_rng();
-    cards.shuffle(&mut rng);
+fn shuffle<R: Rng + ?Sized>(cards: &mut [CardType], rng: &mut R) {
+    cards.shuffle(rng);
 }
 
-/// Deal out 13 cards to each player.
-fn deal(cards: &mut Vec<CardType>) -> Hand {
+/// Deal out the number of cards to a player.
+fn deal(cards: &mut Vec<CardType>, size: usize) -> Hand {
     cards
-        .split_off(cards.len() - 13)
+        .split_off(cards.len() - size)
         .into_iter()
         .fold(BTreeMap::new(), |mut map, card| {
             *map.entry(card).or_default() += 1;
@@ -134,10 +526,15 @@ fn deal(cards: &mut Vec<CardType>) -> Hand {
         })
 }
 
+/// Set apart the 14 cards of the dead wall(王牌) from the mountain.
+fn build_dead_wall(cards: &mut Vec<CardType>) -> Vec<CardType> {
+    cards.drain(..14).collect()
+}
+
 /// Remove a card from hand.
 ///
 /// Returns if the hand contained the card.
-fn remove_from_hand(hand: &mut Hand, card: CardType) -> bool {
+pub(crate) fn remove_from_hand(hand: &mut Hand, card: CardType) -> bool {
     match hand.get_mut(&card) {
         Some(1) => {
             hand.remove(&card);
@@ -151,6 +548,141 @@ fn remove_from_hand(hand: &mut Hand, card: CardType) -> bool {
 }
 
 impl Cards {
+    /// Returns the rules of the game, see [Cards::ruleset].
+    pub fn rules(&self) -> &dyn Ruleset {
+        self.ruleset.0.as_ref()
+    }
+
+    /// Returns the players of the game in the order of playing, see [Ruleset::players],
+    /// i.e. all but 北 in 三麻.
+    pub fn players(&self) -> &'static [FengType] {
+        self.rules().players()
+    }
+
+    /// Returns the player playing after the player, skipping those not playing, e.g. 北 in 三麻.
+    pub fn next_player(&self, side: FengType) -> FengType {
+        let players = self.players();
+        let mut res = side.next();
+        while !players.contains(&res) && res != side {
+            res = res.next();
+        }
+        res
+    }
+
+    /// Returns the wind of a player's seat, known as 自风 in Chinese.
+    ///
+    /// The dealer sits at 东, and the others follow in the order of playing, see [Cards::next_player].
+    pub fn seat_wind(&self, side: FengType) -> FengType {
+        let mut res = FengType::Dong;
+        let mut cur = self.dealer;
+        for _ in 1..self.players().len() {
+            if cur == side {
+                break;
+            }
+            cur = self.next_player(cur);
+            res = res.next();
+        }
+        res
+    }
+
+    /// Returns the winds counted as 役牌 for a player,
+    /// i.e. the wind of the round and the wind of the seat, in this order,
+    /// see [Cards::round_wind] and [Cards::seat_wind].
+    pub fn yakuhai_winds(&self, side: FengType) -> [FengType; 2] {
+        [self.round_wind, self.seat_wind(side)]
+    }
+
+    /// Returns the number of cards a player holds while waiting for a card, see [Ruleset::hand_size],
+    /// i.e. 13, or 16 in 台湾麻将, see [TaiwaneseRules].
+    ///
+    /// A player holds one more card when playing a card, see [Cards::card_count].
+    pub fn hand_size(&self) -> usize {
+        self.rules().hand_size()
+    }
+
+    /// Returns the next player still playing after the player,
+    /// skipping the players finished in 血战到底, see [Cards::finished].
+    pub fn next_playing(&self, side: FengType) -> FengType {
+        let mut res = self.next_player(side);
+        for _ in 1..self.players().len() {
+            if !self.has_finished(res) {
+                break;
+            }
+            res = self.next_player(res);
+        }
+        res
+    }
+
+    /// Checks if the player has completed in 血战到底, see [Cards::finished].
+    pub fn has_finished(&self, side: FengType) -> bool {
+        self.finished.iter().any(|&(s, _)| s == side)
+    }
+
+    /// Checks if the hand of 血战到底 is over, i.e. all the players but one have completed.
+    pub fn is_sichuan_over(&self) -> bool {
+        self.finished.len() + 1 >= self.players().len()
+    }
+
+    /// Records the players completing in 血战到底 with the card of the player given,
+    /// or by 自摸 if `None`, see [Cards::finished],
+    /// and passes the turn to the next player still playing after the last of them.
+    ///
+    /// The hands of the players are kept as they are, and they take no more part in the hand.
+    pub fn finish(&mut self, winners: &[FengType], from: Option<FengType>) {
+        self.finished
+            .extend(winners.iter().map(|&side| (side, from)));
+        if let Some(&last) = winners.last() {
+            self.active_player = self.next_playing(last);
+        }
+        self.drawn = None;
+        self.rinshan = false;
+        self.jia_gang = None;
+        self.kuikae.clear();
+    }
+
+    /// Checks if the player has yet to declare a void suit in 血战到底, see [Cards::declare_void].
+    pub fn must_declare_void(&self, side: FengType) -> bool {
+        self.rules().continues_after_completion() && !self.voids.contains_key(&side)
+    }
+
+    /// Declares the void suit of the active player in 血战到底, known as 定缺 in Chinese,
+    /// i.e. a number suit the player gives up:
+    /// the cards of the suit must be played before any other card,
+    /// and the player can not complete or call a 碰 or 杠 with them.
+    ///
+    /// Players declare in the order of playing from the dealer before the dealer draws,
+    /// so the next player becomes active, and the dealer again after the last declaration.
+    ///
+    /// Returns `false` and changes nothing if the player has declared, or the suit is the honors.
+    pub fn declare_void(&mut self, suit: SuitType) -> bool {
+        let side = self.active_player;
+        if !self.must_declare_void(side) || suit == SuitType::Zi {
+            return false;
+        }
+        self.voids.insert(side, suit);
+        self.active_player = self.next_player(side);
+        true
+    }
+
+    /// Checks if the player still holds a card of the declared void suit, see [Cards::declare_void].
+    pub fn holds_void(&self, side: FengType) -> bool {
+        self.voids
+            .get(&side)
+            .is_some_and(|&void| self.hand(side).keys().any(|c| c.suit() == void))
+    }
+
+    /// Checks if the player may complete with the card by the rules of 血战到底,
+    /// i.e. has not completed, has declared a void suit, and holds no card of it with the card.
+    ///
+    /// Always true in other rules.
+    fn may_complete(&self, side: FengType, card: CardType) -> bool {
+        !self.rules().continues_after_completion()
+            || !self.has_finished(side)
+                && !self.must_declare_void(side)
+                && !self.holds_void(side)
+                && self.voids.get(&side) != Some(&card.suit())
+    }
+
     /// Returns the hand of the current player by mut ref.
     pub fn current_hand_mut(&mut self) -> &mut Hand {
         self.hand_mut(self.active_player)
@@ -158,12 +690,27 @@ impl Cards {
 
     /// Returns the hand of a specified player by mut ref.
     pub fn hand_mut(&mut self, side: FengType) -> &mut Hand {
-        match side {
-            FengType::Dong => &mut self.dong_hand,
-            FengType::Nan => &mut self.nan_hand,
-            FengType::Xi => &mut self.xi_hand,
-            FengType::Bei => &mut self.bei_hand,
+        &mut self[side].hand
+    }
+
+    /// Returns the points of a specified player.
+    pub fn score(&self, side: FengType) -> i32 {
+        self[side].score
+    }
+
+    /// Returns the points of a specified player by mut ref.
+    pub fn score_mut(&mut self, side: FengType) -> &mut i32 {
+        &mut self[side].score
+    }
+
+    /// Transfers the points of the payment from the payers to the winner,
+    /// and gives the deposit to the winner.
+    pub fn apply_payment(&mut self, payment: &Payment) {
+        for &(side, points) in &payment.payers {
+            *self.score_mut(side) -= points as i32;
+            *self.score_mut(payment.winner) += points as i32;
         }
+        *self.score_mut(payment.winner) += payment.deposit as i32;
     }
 
     /// Returns the hand of the current player by ref.
@@ -173,42 +720,27 @@ impl Cards {
 
     /// Returns the hand of a specified player by ref.
     pub fn hand(&self, side: FengType) -> &Hand {
-        match side {
-            FengType::Dong => &self.dong_hand,
-            FengType::Nan => &self.nan_hand,
-            FengType::Xi => &self.xi_hand,
-            FengType::Bei => &self.bei_hand,
-        }
+        &self[side].hand
     }
 
     /// Returns the river of the current player by mut ref.
-    pub fn current_river_mut(&mut self) -> &mut Vec<RiverType> {
+    pub fn current_river_mut(&mut self) -> &mut River {
         self.river_mut(self.active_player)
     }
 
     /// Returns the river of a specified player by mut ref.
-    pub fn river_mut(&mut self, side: FengType) -> &mut Vec<RiverType> {
-        match side {
-            FengType::Dong => &mut self.dong_river,
-            FengType::Nan => &mut self.nan_river,
-            FengType::Xi => &mut self.xi_river,
-            FengType::Bei => &mut self.bei_river,
-        }
+    pub fn river_mut(&mut self, side: FengType) -> &mut River {
+        &mut self[side].river
     }
 
     /// Returns the river of the current player by ref.
-    pub fn current_river(&self) -> &Vec<RiverType> {
+    pub fn current_river(&self) -> &River {
         self.river(self.active_player)
     }
 
     /// Returns the river of a specified player by ref.
-    pub fn river(&self, side: FengType) -> &Vec<RiverType> {
-        match side {
-            FengType::Dong => &self.dong_river,
-            FengType::Nan => &self.nan_river,
-            FengType::Xi => &self.xi_river,
-            FengType::Bei => &self.bei_river,
-        }
+    pub fn river(&self, side: FengType) -> &River {
+        &self[side].river
     }
 
     /// Returns the open of the current player by mut ref.
@@ -218,12 +750,7 @@ impl Cards {
 
     /// Returns the open of a specified player by mut ref.
     pub fn open_mut(&mut self, side: FengType) -> &mut Vec<CaseType> {
-        match side {
-            FengType::Dong => &mut self.dong_open,
-            FengType::Nan => &mut self.nan_open,
-            FengType::Xi => &mut self.xi_open,
-            FengType::Bei => &mut self.bei_open,
-        }
+        &mut self[side].open
     }
 
     /// Returns the open of the current player by ref.
@@ -233,79 +760,1208 @@ impl Cards {
 
     /// Returns the open of a specified player by ref.
     pub fn open(&self, side: FengType) -> &Vec<CaseType> {
-        match side {
-            FengType::Dong => &self.dong_open,
-            FengType::Nan => &self.nan_open,
-            FengType::Xi => &self.xi_open,
-            FengType::Bei => &self.bei_open,
+        &self[side].open
+    }
+
+    /// Returns the number of cards the player holds, counting each case(面子) in open as 3.
+    ///
+    /// It is 13 when waiting for a card, and 14 when the player should play a card,
+    /// or 16 and 17 in 台湾麻将, see [Cards::hand_size].
+    pub fn card_count(&self, side: FengType) -> usize {
+        let num: usize = self.hand(side).values().map(|&n| n as usize).sum();
+        num + 3 * self.open(side).len()
+    }
+
+    /// Returns the wall of the hand laid out as [Cards::from_wall] takes it,
+    /// i.e. the dead wall(王牌), the mountain, and the hands of 北, 西, 南 and 东 in order.
+    ///
+    /// It is the wall the hand was dealt from only before any card is drawn.
+    pub fn wall(&self) -> Vec<CardType> {
+        let mut res = self.dead_wall.clone();
+        res.extend(&self.card_mountain);
+        for side in FengType::ALL.into_iter().rev() {
+            for (&card, &n) in self.hand(side) {
+                res.extend(std::iter::repeat_n(card, n as usize));
+            }
+        }
+        res
+    }
+
+    /// Returns whether each card of [Cards::wall] is a red five(赤宝牌).
+    pub fn wall_reds(&self) -> Vec<bool> {
+        let mut res = self.aka.dead_wall.clone();
+        res.extend(&self.aka.mountain);
+        res.resize(self.dead_wall.len() + self.card_mountain.len(), false);
+        for side in FengType::ALL.into_iter().rev() {
+            let reds = self.aka.in_hand.get(&side);
+            for (&card, &n) in self.hand(side) {
+                let red = reds.map_or(0, |v| v.iter().filter(|&&c| c == card).count());
+                res.extend((0..n as usize).map(|i| i < red));
+            }
+        }
+        res
+    }
+
+    /// Returns the state of the hand shown to the player, see [PlayerView],
+    /// where the hands of other players and the wall only show their numbers of cards.
+    pub fn view(&self, for_side: FengType) -> PlayerView {
+        let seats = self
+            .players()
+            .iter()
+            .map(|&side| SeatView {
+                side,
+                hand_count: self.hand(side).values().map(|&n| n as usize).sum(),
+                river: self.river(side).clone(),
+                open: self.open(side).clone(),
+                reds_in_open: self.aka.in_open.get(&side).copied().unwrap_or(0),
+                flowers: self.flowers.of(side).to_vec(),
+                score: self.score(side),
+                riichi: self.is_riichi(side),
+                waits: self.open_riichi_waits(side).unwrap_or_default(),
+                safe_tiles: self.safe_tiles(side).into_iter().collect(),
+                called: self[side].called.clone(),
+                nuki: self.nuki.get(&side).copied().unwrap_or(0),
+                void: self.voids.get(&side).copied(),
+            })
+            .collect();
+        PlayerView {
+            side: for_side,
+            hand: self.hand(for_side).clone(),
+            reds: self.aka.in_hand.get(&for_side).cloned().unwrap_or_default(),
+            drawn: self.drawn.filter(|_| self.active_player == for_side),
+            seats,
+            rules: self.rules().id(),
+            finished: self.finished.clone(),
+            active_player: self.active_player,
+            round_wind: self.round_wind,
+            dealer: self.dealer,
+            mountain: self.card_mountain.len(),
+            dora_indicators: self.dora_indicators(),
+            last_discard: self.last_discard,
+            jia_gang: self.jia_gang,
+            seen_tiles: self.seen_tiles,
+        }
+    }
+
+    /// Creates the cards of a hand consistent with the state shown to a player, see [Cards::view],
+    /// where the cards not shown, i.e. the hands of other players and the wall,
+    /// are dealt at random from the cards not seen by the player.
+    ///
+    /// Only the state of cards is rebuilt, and none of the situations or completions is registered.
+    /// A card waiting for claims is taken as passed by all players,
+    /// and the red fives(赤宝牌) not in the player's hand are left out.
+    pub fn sample_from_view<R: Rng + ?Sized>(view: &PlayerView, rng: &mut R) -> Self {
+        let ruleset = view.rules.ruleset();
+        let mut res = Self {
+            ruleset: SharedRuleset(ruleset.clone()),
+            voids: view
+                .seats
+                .iter()
+                .filter_map(|seat| Some((seat.side, seat.void?)))
+                .collect(),
+            finished: view.finished.clone(),
+            ..Default::default()
+        };
+        let mut unseen = Vec::new();
+        for (card, n) in view.seen_tiles.iter() {
+            let held = view.hand.get(&card).copied().unwrap_or(0);
+            unseen.extend(std::iter::repeat_n(
+                card,
+                ruleset.copies(card).saturating_sub(n + held) as usize,
+            ));
+        }
+        shuffle(&mut unseen, rng);
+
+        for seat in &view.seats {
+            let side = seat.side;
+            if side == view.side {
+                *res.hand_mut(side) = view.hand.clone();
+            } else {
+                let n = seat.hand_count.min(unseen.len());
+                let hand = res.hand_mut(side);
+                for card in unseen.split_off(unseen.len() - n) {
+                    *hand.entry(card).or_default() += 1;
+                }
+            }
+            *res.river_mut(side) = seat.river.clone();
+            if let Some(last) = seat.river.last() {
+                res.turn = res.turn.max(last.turn + 1);
+            }
+            *res.open_mut(side) = seat.open.clone();
+            res[side].called = seat.called.clone();
+            *res.score_mut(side) = seat.score;
+            if seat.reds_in_open > 0 {
+                res.aka.add_to_open(side, seat.reds_in_open);
+            }
+            if seat.nuki > 0 {
+                res.nuki.insert(side, seat.nuki);
+            }
+            if !seat.waits.is_empty() {
+                res.open_riichi.insert(side);
+            }
+            // After 立直 every card passed since the last play stays safe.
+            for &card in &seat.safe_tiles {
+                if seat.river.iter().any(|r| r.card() == card) {
+                    continue;
+                }
+                if seat.riichi {
+                    res[side].safe.insert(card);
+                } else {
+                    res[side].passed.insert(card);
+                }
+            }
+        }
+        res.aka.in_hand.insert(view.side, view.reds.clone());
+
+        let gangs = view
+            .seats
+            .iter()
+            .flat_map(|seat| &seat.open)
+            .filter(|c| matches!(c, CaseType::Gang(_) | CaseType::AnGang(_)))
+            .count();
+        res.replacements_drawn = gangs.min(4) as u8;
+        res.dora_revealed = view.dora_indicators.len() as u8;
+        let hidden = 4 - res.replacements_drawn as usize;
+        let mut dead_wall: Vec<_> = unseen.split_off(unseen.len().saturating_sub(hidden));
+        dead_wall.extend(&view.dora_indicators);
+        let rest = 14usize.saturating_sub(dead_wall.len());
+        dead_wall.extend(unseen.split_off(unseen.len().saturating_sub(rest)));
+        res.dead_wall = dead_wall;
+        unseen.truncate(view.mountain);
+        res.card_mountain = unseen;
+        res.aka.mountain = vec![false; res.card_mountain.len()];
+        res.aka.dead_wall = vec![false; res.dead_wall.len()];
+
+        res.seen_tiles = view.seen_tiles;
+        res.round_wind = view.round_wind;
+        res.dealer = view.dealer;
+        res.active_player = view.active_player;
+        res.last_discard = view.last_discard;
+        let active = view.active_player;
+        if let Some((from, _)) = view.last_discard.filter(|&(from, _)| from == active) {
+            res.active_player = res.next_player(from);
         }
+        res.drawn = view.drawn.filter(|_| view.side == active);
+        let holding = res.card_count(res.active_player);
+        res.rinshan = holding == res.hand_size()
+            && (view.jia_gang.is_some() || view.last_discard.is_none() && gangs > 0);
+        res
+    }
+
+    /// Marks a card as shown to all players.
+    fn reveal(&mut self, card: CardType) {
+        self.seen_tiles[card] += 1;
+    }
+
+    /// Returns how many copies of the card type are shown to all players, see [Cards::seen_tiles],
+    /// i.e. in rivers, in open, set aside as 拔北宝牌 and revealed as dora indicators(宝牌指示牌),
+    /// counting the card called from a river only once.
+    ///
+    /// The card type is exhausted to the players when it reaches the copies in the wall,
+    /// see [Ruleset::copies].
+    pub fn visible_count(&self, card: CardType) -> u8 {
+        self.seen_tiles[card]
+    }
+
+    /// Returns how many cards of the suit have not been shown to all players,
+    /// i.e. still in the mountain or in any hand.
+    pub fn remaining_tiles_of_suit(&self, suit: SuitType) -> u8 {
+        let total = init(self.rules())
+            .into_iter()
+            .filter(|c| c.suit() == suit)
+            .count() as u8;
+        let seen: u8 = self
+            .seen_tiles
+            .iter()
+            .filter(|(c, _)| c.suit() == suit)
+            .map(|(_, n)| n)
+            .sum();
+        total - seen
+    }
+
+    /// Returns the card states as a JSON value, e.g. for clients to inspect or merge.
+    ///
+    /// Checkers are not included.
+    #[cfg(feature = "serde")]
+    pub fn as_json_value(&self) -> serde_json::Result<serde_json::Value> {
+        serde_json::to_value(self)
+    }
+
+    /// Restores the card states from a JSON value, the reverse of [Cards::as_json_value].
+    ///
+    /// Checkers are not included, see [Cards::situation_checkers].
+    #[cfg(feature = "serde")]
+    pub fn from_json_value(value: serde_json::Value) -> serde_json::Result<Self> {
+        serde_json::from_value(value)
     }
 
     /// Creates a new [Cards], which contains well initialized states.
     ///
     /// The cards in mountain have been shuffled,
     /// players have been dealt,
+    /// the first dora indicator has been revealed,
     /// and the current active player is 东.
+    /// Every player starts with the [Ruleset::starting_score] of riichi mahjong,
+    /// so they have the points to declare 立直, see [Cards::validate_riichi].
     ///
     /// Note that the banker **HAVE NOT** draw a card to play.
     pub fn new() -> Self {
-        let mut cards = init();
-        shuffle(&mut cards);
-
-        Self {
-            dong_hand: deal(&mut cards),
-            nan_hand: deal(&mut cards),
-            xi_hand: deal(&mut cards),
-            bei_hand: deal(&mut cards),
-            card_mountain: cards,
+        let mut res = Self::default();
+        res.reset_for_next_hand(FengType::Dong);
+        res.set_starting_scores();
+        res
+    }
+
+    /// Creates a new [Cards] like [Cards::new],
+    /// but the players are dealt from the wall given without shuffling,
+    /// e.g. for scripted scenarios, regression tests and replays.
+    ///
+    /// The wall is laid out as it is built:
+    /// 东 is dealt the last 13 cards, then 南, 西 and 北 the 13 cards before in turn,
+    /// the first 14 cards are the dead wall(王牌), see [Cards::dead_wall],
+    /// and the rest are the mountain, drawn from the end.
+    /// Later hands are shuffled as usual.
+    ///
+    /// Returns `None` if the wall does not contain exactly 4 cards of each card type.
+    ///
+    /// For 三麻, create the cards by [Cards::with_sanma_rules] and deal the wall by [Cards::reset_with_wall] instead,
+    /// where 北 is not dealt and the wall contains no 2 to 8 of 万.
+    pub fn from_wall(wall: Vec<CardType>) -> Option<Self> {
+        let reds = vec![false; wall.len()];
+        Self::from_wall_with_reds(wall, reds)
+    }
+
+    /// Creates a new [Cards] like [Cards::from_wall],
+    /// where the red fives(赤宝牌) are marked by the flags given, in the same order as the wall.
+    ///
+    /// Returns `None` if the wall does not contain exactly 4 cards of each card type,
+    /// or a flag is set on a card other than a five of 万, 条 or 筒.
+    pub fn from_wall_with_reds(wall: Vec<CardType>, reds: Vec<bool>) -> Option<Self> {
+        let mut res = Self::default();
+        res.set_starting_scores();
+        res.reset_with_wall(FengType::Dong, wall, reds)
+            .then_some(res)
+    }
+
+    /// Starts the next hand like [Cards::reset_for_next_hand],
+    /// but the players are dealt from the wall given without shuffling,
+    /// see [Cards::from_wall_with_reds].
+    /// No flower cards(花牌) are in the wall given.
+    ///
+    /// Returns `false` and changes nothing if the wall or the flags are not valid.
+    pub fn reset_with_wall(
+        &mut self,
+        dealer: FengType,
+        wall: Vec<CardType>,
+        reds: Vec<bool>,
+    ) -> bool {
+        if reds.len() != wall.len()
+            || wall
+                .iter()
+                .zip(&reds)
+                .any(|(card, &red)| red && !aka::fives().contains(card))
+        {
+            return false;
+        }
+        let mut expected = TileCounts::new();
+        let mut given = TileCounts::new();
+        init(self.rules())
+            .into_iter()
+            .for_each(|card| expected[card] += 1);
+        wall.iter().for_each(|&card| given[card] += 1);
+        if given != expected {
+            return false;
+        }
+        self.aka.mark_with(reds);
+        self.deal_wall(wall);
+        self.flowers.schedule.clear();
+        self.flowers.collected.clear();
+        self.start_hand(dealer);
+        true
+    }
+
+    /// Starts the next hand like [Cards::reset_with_wall],
+    /// but the players are dealt from the cards on the table where the dice tell,
+    /// see [dice::wall_from_table], e.g. for replays of a physical table.
+    ///
+    /// Returns `false` and changes nothing if the dice, the cards or the flags are not valid.
+    pub fn reset_with_table(
+        &mut self,
+        dealer: FengType,
+        dice: Dice,
+        table: Vec<CardType>,
+        reds: Vec<bool>,
+    ) -> bool {
+        let players = self.players();
+        let size = self.hand_size();
+        let (Some(wall), Some(reds)) = (
+            dice::wall_from_table(&table, dice, dealer, players, size),
+            dice::wall_from_table(&reds, dice, dealer, players, size),
+        ) else {
+            return false;
+        };
+        let res = self.reset_with_wall(dealer, wall, reds);
+        if res {
+            self.dice = dice;
+        }
+        res
+    }
+
+    /// Returns the cards on the table at the start of the hand, see [dice::table_from_wall],
+    /// given the dealer, or `None` if the dice have not been rolled.
+    pub fn table(&self, dealer: FengType) -> Option<Vec<CardType>> {
+        dice::table_from_wall(
+            &self.wall(),
+            self.dice,
+            dealer,
+            self.players(),
+            self.hand_size(),
+        )
+    }
+
+    /// Creates a new [Cards] like [Cards::new],
+    /// but the walls of this and later hands are built from the seed,
+    /// so that the same seed always gives the same walls,
+    /// e.g. for tests, replays and duplicate tournaments.
+    pub fn new_with_seed(seed: u64) -> Self {
+        Self::new_with_rng(StdRng::seed_from_u64(seed))
+    }
+
+    /// Creates a new [Cards] like [Cards::new],
+    /// but the walls of this and later hands are built by the random number generator given.
+    ///
+    /// The generator is only used to seed the one kept in [Cards::rng].
+    pub fn new_with_rng<R: Rng>(mut rng: R) -> Self {
+        let mut res = Self {
+            rng: Some(StdRng::from_rng(&mut rng).unwrap_or_else(|_| StdRng::from_entropy())),
             ..Default::default()
+        };
+        res.reset_for_next_hand(FengType::Dong);
+        res.set_starting_scores();
+        res
+    }
+
+    /// Sets the points of every player to the [Ruleset::starting_score] of the rules.
+    fn set_starting_scores(&mut self) {
+        let score = self.rules().starting_score();
+        for &side in self.players() {
+            *self.score_mut(side) = score;
+        }
+    }
+
+    /// Creates the cards of the rules given, see [Ruleset],
+    /// with their situations and completions, see [Ruleset::register].
+    ///
+    /// Every player starts with [Ruleset::starting_score] points.
+    pub fn with_ruleset(ruleset: Arc<dyn Ruleset>) -> Self {
+        Self::with_config(ruleset, RuleConfig::default())
+    }
+
+    /// Creates the cards of the rules given like [Cards::with_ruleset],
+    /// with the options of the rules given, see [RuleConfig].
+    ///
+    /// The options are set before the completions are registered,
+    /// which may depend on them, e.g. [RuleConfig::kuitan].
+    pub fn with_config(ruleset: Arc<dyn Ruleset>, config: RuleConfig) -> Self {
+        Self::with_config_and_rng(ruleset, config, None)
+    }
+
+    /// Creates the cards of the rules given like [Cards::with_config],
+    /// where the walls are built by the random number generator given, see [Cards::rng].
+    fn with_config_and_rng(
+        ruleset: Arc<dyn Ruleset>,
+        config: RuleConfig,
+        rng: Option<StdRng>,
+    ) -> Self {
+        let mut cards = Self {
+            config,
+            rng,
+            ..Default::default()
+        };
+        cards.ruleset = SharedRuleset(ruleset.clone());
+        cards.reset_for_next_hand(FengType::Dong);
+        ruleset.register(&mut cards);
+        cards.set_starting_scores();
+        cards
+    }
+
+    /// Creates the cards with the built-in situations and completions of riichi mahjong,
+    /// see [RiichiRules] and [crate::riichi::register].
+    ///
+    /// Every player starts with 25000 points.
+    pub fn with_riichi_rules() -> Self {
+        Self::with_ruleset(Arc::new(RiichiRules))
+    }
+
+    /// Creates the cards of riichi mahjong like [Cards::with_riichi_rules],
+    /// but the walls of this and later hands are built from the seed,
+    /// the same as those of [Cards::new_with_seed].
+    pub fn with_riichi_rules_seeded(seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let rng = StdRng::from_rng(&mut rng).unwrap_or_else(|_| StdRng::from_entropy());
+        Self::with_config_and_rng(Arc::new(RiichiRules), RuleConfig::default(), Some(rng))
+    }
+
+    /// Creates the cards of 三麻 with the built-in situations and completions of riichi mahjong,
+    /// see [SanmaRules].
+    ///
+    /// Every player starts with 35000 points.
+    pub fn with_sanma_rules() -> Self {
+        Self::with_ruleset(Arc::new(SanmaRules))
+    }
+
+    /// Creates the cards of 台湾麻将 with its built-in situations and completions,
+    /// see [TaiwaneseRules], where no player can declare 立直.
+    ///
+    /// The flower cards(花牌) are in the wall, and every player starts with 25000 points.
+    pub fn with_taiwanese_rules() -> Self {
+        Self::with_ruleset(Arc::new(TaiwaneseRules))
+    }
+
+    /// Creates the cards of 血战到底 with its built-in situations and completions,
+    /// see [SichuanRules], where no player can declare 立直.
+    ///
+    /// Every player starts with 25000 points.
+    pub fn with_sichuan_rules() -> Self {
+        Self::with_ruleset(Arc::new(SichuanRules))
+    }
+
+    /// Creates the cards with the built-in situations and completions of Hong Kong Old Style mahjong,
+    /// see [HkosRules], where no player can declare 立直.
+    ///
+    /// Points are paid by [crate::scoring::hkos_payment], and every player starts with none.
+    pub fn with_hkos_rules() -> Self {
+        Self::with_ruleset(Arc::new(HkosRules))
+    }
+
+    /// Creates the cards with the built-in situations and completions of Zung Jung,
+    /// see [ZungJungRules], where no player can declare 立直.
+    ///
+    /// Points are paid by [crate::scoring::zung_jung_payment], and every player starts with none.
+    pub fn with_zung_jung_rules() -> Self {
+        Self::with_ruleset(Arc::new(ZungJungRules))
+    }
+
+    /// Creates the cards with the built-in situations and completions of Chinese official mahjong,
+    /// see [McrRules], where no player can declare 立直.
+    ///
+    /// Points are paid by [crate::scoring::mcr_payment], and every player starts with none.
+    pub fn with_mcr_rules() -> Self {
+        Self::with_ruleset(Arc::new(McrRules))
+    }
+
+    /// Tries to draw a card from mountain.
+    /// Returns `None` if there are no more cards in mountain,
+    /// or else the card been drawn.
+    /// The hand is drawn when the mountain runs out, see [Cards::is_exhausted].
+    ///
+    /// A card drawn after a 杠 should be drawn by [Cards::draw_replacement] instead.
+    /// Flower cards(花牌) drawn are set aside before the card, see [Flowers].
+    ///
+    /// After this call,
+    /// the hand of the active player will have been given the drawn card.
+    pub fn draw(&mut self) -> Option<CardType> {
+        self.pass_last_discard();
+        self.flowers
+            .draw(self.active_player, self.card_mountain.len());
+        let res = self.card_mountain.pop()?;
+        *self.current_hand_mut().entry(res).or_default() += 1;
+        self.aka.draw(self.active_player, res);
+        self.drawn = Some(res);
+        self.rinshan = false;
+        Some(res)
+    }
+
+    /// Tries to draw a replacement card(岭上牌) from the dead wall after a 杠.
+    /// Returns `None` if the active player can not, see [Cards::can_draw_replacement],
+    /// or else the card been drawn.
+    ///
+    /// The last card of the mountain joins the dead wall,
+    /// so the dead wall keeps 14 cards and the mountain has one card less to draw.
+    /// After a 北 is set aside in 三麻, see [Cards::nuki_dora],
+    /// the replacement card is that last card of the mountain itself,
+    /// so the dead wall and its dora indicators stay in place.
+    ///
+    /// After this call,
+    /// the hand of the active player will have been given the drawn card,
+    /// and [Cards::is_rinshan] holds.
+    pub fn draw_replacement(&mut self) -> Option<CardType> {
+        if !self.can_draw_replacement() {
+            return None;
+        }
+        self.pass_last_discard();
+        if self.is_nuki_replacement() {
+            let res = self.card_mountain.remove(0);
+            self.aka.draw_first(self.active_player, res);
+            self.flowers.shift();
+            *self.current_hand_mut().entry(res).or_default() += 1;
+            self.drawn = Some(res);
+            return Some(res);
+        }
+        self.jia_gang = None;
+        let res = self.dead_wall.remove(0);
+        let last = self.card_mountain.remove(0);
+        self.dead_wall.push(last);
+        self.aka.draw_replacement(self.active_player, res);
+        self.flowers.shift();
+        self.replacements_drawn += 1;
+        *self.current_hand_mut().entry(res).or_default() += 1;
+        self.drawn = Some(res);
+        Some(res)
+    }
+
+    /// Checks if the active player can draw a replacement card(岭上牌), see [Cards::draw_replacement],
+    /// i.e. has just done a 杠 or set aside a 北 and not drawn yet,
+    /// and the four replacement cards of 杠 have not all been drawn.
+    pub fn can_draw_replacement(&self) -> bool {
+        self.rinshan
+            && self.drawn.is_none()
+            && (self.replacements_drawn < 4 || self.is_nuki_replacement())
+            && self.card_count(self.active_player) == self.hand_size()
+            && !self.card_mountain.is_empty()
+    }
+
+    /// Checks if the replacement card to draw is for a 北 set aside rather than a 杠,
+    /// i.e. every 杠 has had its replacement card.
+    fn is_nuki_replacement(&self) -> bool {
+        let gangs = FengType::ALL
+            .iter()
+            .flat_map(|&side| self.open(side))
+            .filter(|c| matches!(c, CaseType::Gang(_) | CaseType::AnGang(_)))
+            .count();
+        self.rules().has_nuki_dora() && gangs <= self.replacements_drawn as usize
+    }
+
+    /// Sets aside a 北 from the hand of the active player as a dora in 三麻,
+    /// known as 拔北 in Chinese, see [Cards::nuki].
+    /// The player then draws a replacement card, see [Cards::draw_replacement].
+    ///
+    /// Returns `false` and changes nothing if the player can not, see [Cards::can_nuki_dora].
+    pub fn nuki_dora(&mut self) -> bool {
+        if !self.can_nuki_dora() {
+            return false;
+        }
+        let side = self.active_player;
+        remove_from_hand(self.current_hand_mut(), NORTH);
+        *self.nuki.entry(side).or_default() += 1;
+        self.reveal(NORTH);
+        self.drawn = None;
+        self.rinshan = true;
+        true
+    }
+
+    /// Checks if the active player can set aside a 北 as a dora, see [Cards::nuki_dora],
+    /// i.e. the game is 三麻, the player has drawn a card and holds a 北,
+    /// and the mountain has a card left to replace it.
+    ///
+    /// After declaring 立直, only the 北 just drawn can be set aside.
+    pub fn can_nuki_dora(&self) -> bool {
+        let side = self.active_player;
+        self.rules().has_nuki_dora()
+            && self.drawn.is_some()
+            && self.card_count(side) == self.hand_size() + 1
+            && self.hand(side).contains_key(&NORTH)
+            && !self.card_mountain.is_empty()
+            && (!self.is_riichi(side) || self.drawn == Some(NORTH))
+    }
+
+    /// Reveals the next dora indicator(宝牌指示牌) in the dead wall, known as 翻宝牌 in Chinese.
+    /// Returns `None` if all the five indicators have been revealed,
+    /// or else the indicator revealed.
+    ///
+    /// The first indicator is revealed when the hand starts,
+    /// and another one after each 杠, at the time given by the rules,
+    /// see [RuleConfig::ankan_dora] and [RuleConfig::minkan_dora].
+    pub fn reveal_dora(&mut self) -> Option<CardType> {
+        if self.dora_revealed >= 5 {
+            return None;
+        }
+        let index = 4 + self.dora_revealed as usize - self.replacements_drawn as usize;
+        let res = *self.dead_wall.get(index)?;
+        self.dora_revealed += 1;
+        self.reveal(res);
+        Some(res)
+    }
+
+    /// Returns the dora indicators(宝牌指示牌) revealed, in the order of being revealed.
+    ///
+    /// The dora of an indicator is the next card of it, see [CardType::dora].
+    pub fn dora_indicators(&self) -> Vec<CardType> {
+        let start = 4 - self.replacements_drawn as usize;
+        self.dead_wall
+            .iter()
+            .skip(start)
+            .take(self.dora_revealed as usize)
+            .copied()
+            .collect()
+    }
+
+    /// Returns the number of dora(宝牌) in the hand and the open of the player
+    /// completing with the winning card, see [crate::riichi::dora_count].
+    ///
+    /// Returns `None` if the winning card of 自摸 is not in hand.
+    pub fn dora_count(&self, side: FengType, winning: CardType, is_tsumo: bool) -> Option<u16> {
+        let hand = self.winning_hand(side, winning, is_tsumo)?;
+        Some(crate::riichi::dora_count(
+            &hand,
+            self.open(side),
+            &self.counting_indicators(self.dora_indicators()),
+        ))
+    }
+
+    /// Returns the indicators as counted for dora(宝牌).
+    ///
+    /// In 三麻, the dora of 1 of 万 is 9 of 万, as 2 to 8 of 万 are not in the wall,
+    /// so it counts as 8 of 万 would.
+    fn counting_indicators(&self, mut indicators: Vec<CardType>) -> Vec<CardType> {
+        if self.rules().copies(CardType::Wan(RankType::Two)) == 0 {
+            for i in &mut indicators {
+                if *i == CardType::Wan(RankType::One) {
+                    *i = CardType::Wan(RankType::Eight);
+                }
+            }
+        }
+        indicators
+    }
+
+    /// Returns the ura dora indicators(里宝牌指示牌) under the dora indicators revealed,
+    /// which are only revealed to a player completing after declaring 立直.
+    ///
+    /// Returns an empty array if the player has not declared 立直.
+    pub fn ura_dora_indicators(&self, side: FengType) -> Vec<CardType> {
+        if !self.is_riichi(side) {
+            return Vec::new();
+        }
+        let start = 9 - self.replacements_drawn as usize;
+        self.dead_wall
+            .iter()
+            .skip(start)
+            .take(self.dora_revealed as usize)
+            .copied()
+            .collect()
+    }
+
+    /// Returns the number of dora(宝牌), ura dora(里宝牌), red fives(赤宝牌)
+    /// and 北 set aside in 三麻(拔北宝牌) of the player completing with the winning card.
+    ///
+    /// Returns `None` if the winning card of 自摸 is not in hand.
+    pub fn dora_breakdown(
+        &self,
+        side: FengType,
+        winning: CardType,
+        is_tsumo: bool,
+    ) -> Option<DoraBreakdown> {
+        let hand = self.winning_hand(side, winning, is_tsumo)?;
+        let open = self.open(side);
+        let dora = self.counting_indicators(self.dora_indicators());
+        let ura_dora = self.counting_indicators(self.ura_dora_indicators(side));
+        let nuki = self.nuki.get(&side).copied().unwrap_or(0);
+        let set_aside = Hand::from([(NORTH, nuki)]);
+        Some(DoraBreakdown {
+            dora: crate::riichi::dora_count(&hand, open, &dora),
+            ura_dora: crate::riichi::dora_count(&hand, open, &ura_dora),
+            aka: self.aka.count(side) + u16::from(!is_tsumo && self.aka.last_played),
+            nuki: nuki as u16
+                + crate::riichi::dora_count(&set_aside, &Open::new(), &dora)
+                + crate::riichi::dora_count(&set_aside, &Open::new(), &ura_dora),
+        })
+    }
+
+    /// Tries to draw a card from mountain into the hand of a specified player,
+    /// who does not need to be the active player.
+    ///
+    /// This is meant for setting up scenarios or unusual rules,
+    /// and leaves the turn flags such as [Cards::drawn] and [Cards::last_discard] untouched.
+    /// Use [Cards::draw] during a game instead.
+    ///
+    /// Returns `None` if the player is not waiting for a card,
+    /// i.e. the hand and the open do not count 13 cards with each case(面子) as 3, see [Cards::hand_size],
+    /// or if there are no more cards in mountain,
+    /// or else the card been drawn.
+    pub fn draw_for(&mut self, side: FengType) -> Option<CardType> {
+        if self.card_count(side) != self.hand_size() {
+            return None;
+        }
+        let res = self.card_mountain.pop()?;
+        self.flowers.draw(side, self.card_mountain.len() + 1);
+        *self.hand_mut(side).entry(res).or_default() += 1;
+        self.aka.draw(side, res);
+        Some(res)
+    }
+
+    /// Clears the flags only lasting for a turn or a hand,
+    /// i.e. [Cards::drawn], [Cards::rinshan], [Cards::ippatsu], [Cards::interrupted],
+    /// [Cards::double_riichi], [Cards::open_riichi], [Cards::passed_ron], [Cards::riichi_furiten],
+    /// [Cards::last_discard], [Cards::jia_gang] and [Cards::kuikae].
+    pub fn reset_turn_flags(&mut self) {
+        self.jia_gang = None;
+        self.drawn = None;
+        self.rinshan = false;
+        self.kuikae.clear();
+        self.ippatsu.clear();
+        self.interrupted = false;
+        self.double_riichi.clear();
+        self.open_riichi.clear();
+        self.passed_ron.clear();
+        self.riichi_furiten.clear();
+        self.last_discard = None;
+    }
+
+    /// Returns the players who could complete(荣和) with the card last played,
+    /// see [Cards::last_discard].
+    fn missed_ron(&self) -> Vec<FengType> {
+        let Some((discarder, card)) = self.last_discard else {
+            return Vec::new();
+        };
+        self.players()
+            .iter()
+            .copied()
+            .filter(|&side| side != discarder && self.may_complete(side, card))
+            .filter(|&side| {
+                self.winning_hand(side, card, false)
+                    .is_some_and(|hand| self.is_complete_hand(side, &hand))
+            })
+            .collect()
+    }
+
+    /// Marks the players who could have completed(荣和) with the card last played as passed on it,
+    /// since the game goes on without them completing.
+    ///
+    /// Called when the next card is drawn.
+    fn pass_last_discard(&mut self) {
+        for side in self.missed_ron() {
+            self.pass_ron(side);
+        }
+        self.last_discard = None;
+    }
+
+    /// Marks the player as passed on a 荣和, see [Cards::passed_ron] and [Cards::riichi_furiten].
+    pub fn pass_ron(&mut self, side: FengType) {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(side = %side, "passed on 荣和 and in 振听");
+        self.passed_ron.insert(side);
+        if self.is_riichi(side) {
+            self.riichi_furiten.insert(side);
+        }
+    }
+
+    /// Starts the next hand with the same checkers and rules.
+    ///
+    /// The mountain is rebuilt and shuffled,
+    /// players have been dealt with empty rivers and opens,
+    /// the first dora indicator has been revealed,
+    /// the turn flags have been cleared,
+    /// and the current active player is the dealer.
+    ///
+    /// Note that the dealer **HAVE NOT** draw a card to play.
+    pub fn reset_for_next_hand(&mut self, dealer: FengType) {
+        let mut rng = self.rng.take();
+        match &mut rng {
+            Some(rng) => self.build_wall(rng),
+            None => self.build_wall(&mut rand::thread_rng()),
+        }
+        self.rng = rng;
+        self.start_hand(dealer);
+    }
+
+    /// Builds a shuffled wall and deals it, see [Cards::deal_wall],
+    /// marking the red fives and the flower cards at random.
+    ///
+    /// The flower cards are always enabled in 台湾麻将.
+    fn build_wall<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        let mut cards = init(self.rules());
+        shuffle(&mut cards, rng);
+        self.aka.mark(&cards, self.config.aka, rng);
+        self.deal_wall(cards);
+        self.flowers.enabled |= self.rules().has_flowers();
+        self.flowers.mark(
+            self.players(),
+            self.hand_size(),
+            self.card_mountain.len(),
+            rng,
+        );
+        self.dice = Dice::roll(rng);
+    }
+
+    /// Deals the players from the wall with empty rivers and opens,
+    /// and sets apart the dead wall(王牌).
+    /// 北 is not dealt in 三麻.
+    ///
+    /// The red fives should have been marked, see [Aka::mark].
+    fn deal_wall(&mut self, mut cards: Vec<CardType>) {
+        for player in &mut self.players {
+            *player = Player {
+                score: player.score,
+                ..Default::default()
+            };
+        }
+        for &side in self.players() {
+            self.aka.deal(side, &cards, self.hand_size());
+            *self.hand_mut(side) = deal(&mut cards, self.hand_size());
+        }
+        self.aka.build_dead_wall();
+        self.dead_wall = build_dead_wall(&mut cards);
+        self.card_mountain = cards;
+    }
+
+    /// Starts the hand dealt, revealing the first dora indicator and clearing the turn flags.
+    fn start_hand(&mut self, dealer: FengType) {
+        self.replacements_drawn = 0;
+        self.dora_revealed = 0;
+        self.pending_dora = 0;
+        self.turn = 0;
+        self.seen_tiles = TileCounts::new();
+        self.nuki.clear();
+        self.pao.clear();
+        self.voids.clear();
+        self.finished.clear();
+        self.reveal_dora();
+        self.dealer = dealer;
+        self.active_player = dealer;
+        self.reset_turn_flags();
+    }
+
+    /// Play a card. If the player want to be in the drawing hand(听牌) state,
+    /// the card should be in `RiverType::Drawing`, otherwise `RiverType::Normal`.
+    /// The card is automatically added to the player's river.
+    ///
+    /// A `RiverType::Drawing` card is played by [Cards::declare_riichi].
+    ///
+    /// The active player can only play after drawing a card or doing a call action,
+    /// i.e. when the hand and the open count 14 cards with each case(面子) as 3,
+    /// and can not play a card in [Cards::kuikae].
+    ///
+    /// Returns an error telling why if the card can not be played.
+    pub fn play(&mut self, discard: RiverType) -> Result<(), PlayError> {
+        match discard {
+            RiverType::Normal(_) => self.discard(discard),
+            RiverType::Drawing(_) => self.declare_riichi(discard),
+        }
+    }
+
+    /// Declares 立直 with the card played as the drawing hand indicator.
+    ///
+    /// The declaration and the discard happen together:
+    /// the card should be in `RiverType::Drawing`,
+    /// the active player should not have declared before,
+    /// should have done no call action,
+    /// should have the points of the deposit(立直棒, see [RIICHI_DEPOSIT]),
+    /// and should be in the drawing hand(听牌) state after the card being played,
+    /// while at least 4 cards are left in mountain.
+    ///
+    /// After declaring, the hand is locked:
+    /// the player can only play the card just drawn,
+    /// and can do no call action but an 暗杠 keeping the waits, see [Cards::check_an_gang].
+    ///
+    /// Returns an error telling why if the declaration failed.
+    /// Nothing is changed if it failed.
+    pub fn declare_riichi(&mut self, discard: RiverType) -> Result<(), PlayError> {
+        let RiverType::Drawing(card) = discard else {
+            return Err(PlayError::NotDrawingIndicator);
+        };
+        self.validate_riichi(card)?;
+        let side = self.active_player;
+        let first_play = !self.interrupted && self.river(side).is_empty();
+        self.discard(discard)?;
+        self.ippatsu.insert(side);
+        if first_play {
+            self.double_riichi.insert(side);
+        }
+        Ok(())
+    }
+
+    /// Declares 立直 openly(开立直) by playing the card, if allowed by [RuleConfig::open_riichi],
+    /// showing the waits of the player to the other players, see [Cards::open_riichi_waits].
+    ///
+    /// Returns an error telling why if the declaration failed, see [Cards::declare_riichi].
+    /// Nothing is changed if it failed.
+    pub fn declare_open_riichi(&mut self, card: CardType) -> Result<(), PlayError> {
+        if !self.config.open_riichi {
+            return Err(PlayError::OpenRiichiNotAllowed);
+        }
+        let side = self.active_player;
+        self.declare_riichi(RiverType::Drawing(card))?;
+        self.open_riichi.insert(side);
+        Ok(())
+    }
+
+    /// Returns the waits of the player shown by 开立直, see [Cards::declare_open_riichi],
+    /// or `None` if the player has not declared 立直 openly.
+    ///
+    /// The card just drawn is not counted, so the waits stay the same during the turn of the player.
+    pub fn open_riichi_waits(&self, side: FengType) -> Option<Vec<CardType>> {
+        if !self.open_riichi.contains(&side) {
+            return None;
+        }
+        let mut hand = self.hand(side).clone();
+        if let Some(card) = self.drawn.filter(|_| side == self.active_player) {
+            remove_from_hand(&mut hand, card);
+        }
+        Some(analysis::waits(&hand, self.open(side)))
+    }
+
+    /// Checks if the active player can declare 立直 by playing the card,
+    /// see [Cards::declare_riichi].
+    ///
+    /// Returns an error telling why if not.
+    pub fn validate_riichi(&self, card: CardType) -> Result<(), PlayError> {
+        let side = self.active_player;
+        if !self.rules().allows_riichi() {
+            return Err(PlayError::RiichiNotAllowed);
+        }
+        if self.is_riichi(side) {
+            return Err(PlayError::AlreadyRiichi);
+        }
+        if !self.is_concealed(side) {
+            return Err(PlayError::NotConcealed);
+        }
+        if self.score(side) < RIICHI_DEPOSIT {
+            return Err(PlayError::NotEnoughPoints);
+        }
+        if self.card_mountain.len() < 4 {
+            return Err(PlayError::NotEnoughCards);
+        }
+        self.validate_discard(card)?;
+        let mut hand = self.current_hand().clone();
+        remove_from_hand(&mut hand, card);
+        if !analysis::is_tenpai(&hand, self.current_open()) {
+            return Err(PlayError::NotTenpai);
+        }
+        Ok(())
+    }
+
+    /// Checks if the active player can declare 立直 by playing the card,
+    /// see [Cards::validate_riichi].
+    pub fn can_declare_riichi(&self, card: CardType) -> bool {
+        self.validate_riichi(card).is_ok()
+    }
+
+    /// Checks if the active player can play the card, see [Cards::play].
+    ///
+    /// Returns an error telling why if not.
+    pub fn validate_discard(&self, card: CardType) -> Result<(), PlayError> {
+        let side = self.active_player;
+        if self.card_count(side) != self.hand_size() + 1 {
+            return Err(PlayError::NotYourTurn);
+        }
+        if !self.current_hand().contains_key(&card) {
+            return Err(PlayError::CardNotInHand);
+        }
+        if self.kuikae.contains(&card) {
+            return Err(PlayError::Kuikae);
+        }
+        if self.is_riichi(side) && self.drawn != Some(card) {
+            return Err(PlayError::RiichiLocked);
+        }
+        if self.holds_void(side) && self.voids.get(&side) != Some(&card.suit()) {
+            return Err(PlayError::VoidSuitFirst);
+        }
+        Ok(())
+    }
+
+    /// Checks if the active player can play the card, see [Cards::validate_discard].
+    pub fn can_discard(&self, card: CardType) -> bool {
+        self.validate_discard(card).is_ok()
+    }
+
+    /// Checks if the player has declared 立直,
+    /// i.e. there is a `RiverType::Drawing` card in the river.
+    pub fn is_riichi(&self, side: FengType) -> bool {
+        self.river(side).iter().any(RiverCard::is_drawing)
+    }
+
+    /// Checks if the hand is drawn as the mountain runs out, see [DrawReason::HuangPai],
+    /// i.e. no cards are left in mountain and no player is to play a card,
+    /// the players finished in 血战到底 excluded.
+    ///
+    /// A player may still complete(荣和) with the last card played(河底),
+    /// which should be checked before.
+    pub fn is_exhausted(&self) -> bool {
+        self.card_mountain.is_empty()
+            && self
+                .players()
+                .iter()
+                .filter(|&&side| !self.has_finished(side))
+                .all(|&side| self.card_count(side) == self.hand_size())
+    }
+
+    /// Checks if the player meets 流局满贯 at the end of a drawn hand(荒牌流局),
+    /// i.e. all the cards in the player's river are terminals and honors(幺九牌),
+    /// and none of them is taken by call actions of other players, see [RiverCard::claimed].
+    pub fn is_nagashi(&self, side: FengType) -> bool {
+        let river = self.river(side);
+        !river.is_empty()
+            && river.iter().all(|r| r.card().is_yao_jiu())
+            && !river.iter().any(|r| r.claimed)
+    }
+
+    /// Returns the players meeting 流局满贯, in the order of players, see [Cards::is_nagashi].
+    pub fn nagashi_players(&self) -> Vec<FengType> {
+        self.players()
+            .iter()
+            .copied()
+            .filter(|&side| self.is_nagashi(side))
+            .collect()
+    }
+
+    /// Returns the players in drawing hand(听牌) state, in the order of players,
+    /// as judged at the end of a drawn hand(流局).
+    pub fn tenpai_players(&self) -> Vec<FengType> {
+        self.players()
+            .iter()
+            .copied()
+            .filter(|&side| analysis::is_tenpai(self.hand(side), self.open(side)))
+            .collect()
+    }
+
+    /// Checks if the active player can end the hand with 九种九牌, see [DrawReason::JiuZhongJiuPai],
+    /// i.e. holds nine or more different terminals and honors(幺九牌) before the first play,
+    /// and no call action has happened in the hand.
+    pub fn can_jiu_zhong_jiu_pai(&self) -> bool {
+        let side = self.active_player;
+        !self.interrupted
+            && self.river(side).is_empty()
+            && self.card_count(side) == self.hand_size() + 1
+            && self.hand(side).keys().filter(|c| c.is_yao_jiu()).count() >= 9
+    }
+
+    /// Checks if the hand ends in an abortive draw(途中流局)
+    /// after a card is played and no player completes with it,
+    /// i.e. [DrawReason::SiFengLianDa], [DrawReason::SiJiaLiZhi] or [DrawReason::SiGangSanLe].
+    ///
+    /// [DrawReason::JiuZhongJiuPai] is chosen by the player, see [Cards::can_jiu_zhong_jiu_pai],
+    /// and [DrawReason::SanJiaHeLe] depends on the players completing, see [DrawReason::of_rons].
+    /// There is no abortive draw in rules without, e.g. 血战到底, see [Ruleset::has_abortive_draws].
+    pub fn check_abortive_draw(&self) -> Option<DrawReason> {
+        if !self.rules().has_abortive_draws() {
+            return None;
+        }
+        let first = self.river(FengType::Dong).first().map(RiverCard::card);
+        if !self.interrupted
+            && matches!(first, Some(CardType::Zi(ZiType::Feng(_))))
+            && self.players().iter().all(|&side| {
+                let river = self.river(side);
+                river.len() == 1 && Some(river[0].card()) == first
+            })
+        {
+            return Some(DrawReason::SiFengLianDa);
+        }
+        if self.players().iter().all(|&side| self.is_riichi(side)) {
+            return Some(DrawReason::SiJiaLiZhi);
+        }
+        let gangs: Vec<_> = self
+            .players()
+            .iter()
+            .map(|&side| {
+                self.open(side)
+                    .iter()
+                    .filter(|c| matches!(c, CaseType::Gang(_) | CaseType::AnGang(_)))
+                    .count()
+            })
+            .collect();
+        if gangs.iter().sum::<usize>() >= 4 && gangs.iter().filter(|&&n| n > 0).count() > 1 {
+            return Some(DrawReason::SiGangSanLe);
         }
+        None
     }
 
-    /// Tries to draw a card from mountain.
-    /// Returns `None` if there are no more cards in mountain,
-    /// or else the card been drawn.
+    /// Moves a card from the hand of the active player to the river.
     ///
-    /// After this call,
-    /// the hand of the active player will have been given the drawn card.
-    pub fn draw(&mut self) -> Option<CardType> {
-        let res = self.card_mountain.pop()?;
-        *self.current_hand_mut().entry(res).or_default() += 1;
-        Some(res)
+    /// Returns an error telling why if the card can not be played, see [Cards::play].
+    fn discard(&mut self, discard: RiverType) -> Result<(), PlayError> {
+        self.validate_discard(discard.card())?;
+        let side = self.active_player;
+        let card = discard.card();
+        let hand = self.current_hand_mut();
+        let before = hand.get(&card).copied().unwrap_or(0);
+        remove_from_hand(hand, card);
+        self.aka.last_played = self.aka.leave_hand(side, card, before, before - 1) > 0;
+        self.reveal(card);
+        let tsumogiri = self.drawn == Some(card);
+        let turn = self.turn;
+        self.turn += 1;
+        self.current_river_mut().push(RiverCard {
+            tsumogiri,
+            turn,
+            ..discard.into()
+        });
+        self.mark_safe(side, card);
+        for _ in 0..std::mem::take(&mut self.pending_dora) {
+            self.reveal_dora();
+        }
+        self.drawn = None;
+        self.rinshan = false;
+        self.kuikae.clear();
+        self.ippatsu.remove(&self.active_player);
+        self.passed_ron.remove(&self.active_player);
+        self.last_discard = Some((side, card));
+        Ok(())
     }
 
-    /// Play a card. If the player want to be in the drawing hand(听牌) state,
-    /// the card should be in `RiverType::Drawing`, otherwise `RiverType::Normal`.
-    /// The card is automatically added to the player's river.
+    /// Marks the card just played by the player as safe against the other players,
+    /// see [Player::safe] and [Player::passed].
     ///
-    /// Returns whether the card was in hand.
-    pub fn play(&mut self, discard: RiverType) -> bool {
-        let hand = self.current_hand_mut();
-        if !remove_from_hand(
-            hand,
-            match discard {
-                RiverType::Drawing(c) | RiverType::Normal(c) => c,
-            },
-        ) {
-            return false;
+    /// The cards passed by the player since the last play are no longer safe.
+    fn mark_safe(&mut self, side: FengType, card: CardType) {
+        self[side].passed.clear();
+        for &other in self.players().iter().filter(|&&other| other != side) {
+            if self.is_riichi(other) {
+                self[other].safe.insert(card);
+            } else {
+                self[other].passed.insert(card);
+            }
         }
-        self.current_river_mut().push(discard);
-        true
+    }
+
+    /// Returns the cards safe to play against the player, known as 现物 in Chinese,
+    /// i.e. those the player can not complete with by 荣和 for 振听:
+    /// the cards in the river, see [Player::safe] and [Player::passed] for the others.
+    pub fn safe_tiles(&self, side: FengType) -> BTreeSet<CardType> {
+        let player = &self[side];
+        player
+            .river
+            .iter()
+            .map(RiverCard::card)
+            .chain(player.safe.iter().copied())
+            .chain(player.passed.iter().copied())
+            .collect()
     }
 
     /// Checks if the active player can 暗杠.
     ///
+    /// After declaring 立直, only the card just drawn can do an 暗杠,
+    /// and only if the waits do not change.
+    ///
     /// Returns the cards that can do an 暗杠.
     pub fn check_an_gang(&self) -> Vec<CardType> {
         let mut res = Vec::new();
         for (&card, &num) in self.current_hand() {
-            if num == 4 {
+            if num == 4 && self.can_an_gang_after_riichi(card) {
                 res.push(card);
             }
         }
         res
     }
 
+    /// Checks if the active player can 暗杠 the card without changing the waits,
+    /// which is always true before declaring 立直.
+    fn can_an_gang_after_riichi(&self, card: CardType) -> bool {
+        let side = self.active_player;
+        if !self.is_riichi(side) {
+            return true;
+        }
+        let Some(drawn) = self.drawn.filter(|&d| d == card) else {
+            return false;
+        };
+        let mut before = self.hand(side).clone();
+        remove_from_hand(&mut before, drawn);
+        let mut after = self.hand(side).clone();
+        after.remove(&card);
+        let mut open = self.open(side).clone();
+        open.push(CaseType::AnGang(card));
+        analysis::waits(&before, self.open(side)) == analysis::waits(&after, &open)
+    }
+
     /// Checks if the active player can 加杠.
     ///
     /// Returns the cards that can do an 加杠.
@@ -322,23 +1978,70 @@ impl Cards {
         res
     }
 
+    /// Checks if the player can rob the 加杠 just done(抢杠),
+    /// i.e. complete(荣和) with the card added to the 刻子, see [Cards::score_if_ron].
+    pub fn check_chankan(&self, side: FengType) -> bool {
+        self.jia_gang
+            .is_some_and(|(s, card)| s != side && self.fan_if_ron(side, card).is_some())
+    }
+
+    /// Robs the 加杠 just done by completing(荣和) with the card added, known as 抢杠 in Chinese.
+    ///
+    /// The 加杠 is cancelled, i.e. turned back into the 刻子 of the player who did it,
+    /// and the card becomes the winning card of the player robbing it.
+    /// [Cards::jia_gang] is kept, so more players can rob it and the completions see it,
+    /// see [Cards::game_situations].
+    ///
+    /// Returns the card robbed, or `None` if the player can not rob it, see [Cards::check_chankan].
+    pub fn rob_kan(&mut self, side: FengType) -> Option<CardType> {
+        if !self.check_chankan(side) {
+            return None;
+        }
+        let (robbed, card) = self.jia_gang?;
+        if let Some(case) = self
+            .open_mut(robbed)
+            .iter_mut()
+            .find(|c| **c == CaseType::Gang(card))
+        {
+            *case = CaseType::Ke(card);
+            if self.aka.last_played {
+                self.aka.remove_from_open(robbed, 1);
+            }
+        }
+        self.rinshan = false;
+        Some(card)
+    }
+
     /// Do a call action, also known as 鸣牌 in Chinese.
     ///
     /// The caller should provide the case(面子) of the call, the player who will do the call, and the card being played, as shown in the function arguments.
     ///
     /// After the call action, the active player, the hand and open of the caller player will be changed if needed.
     ///
-    /// Returns whether the call succeeded.
+    /// Returns an error telling why if the call failed, see [Cards::validate_call].
+    ///
+    /// A player who has declared 立直 can only do an 暗杠 keeping the waits,
+    /// see [Cards::declare_riichi].
     ///
-    /// Will not draw a card.
+    /// Will not draw a card, but a 杠 makes the next draw a replacement(岭上牌).
+    /// An 加杠 can be robbed by other players before that, see [Cards::check_chankan].
     pub fn call(
         &mut self,
         case: CaseType,
         side: FengType,
         discard: CardType,
         mut hitchhiker: Vec<CardType>,
-    ) -> bool {
-        match case {
+    ) -> Result<(), CallError> {
+        self.validate_call(case, side, discard, &hitchhiker)?;
+        let from_river = side != self.active_player;
+        let discarder = self.active_player;
+        let fives_before = aka::fives().map(|c| self.hand(side).get(&c).copied().unwrap_or(0));
+        let missed = if from_river {
+            self.missed_ron()
+        } else {
+            Vec::new()
+        };
+        let called = match case {
             CaseType::Shun(start)
                 if hitchhiker
                     .iter()
@@ -353,10 +2056,16 @@ impl Cards {
                             && hitchhiker[0] == start
                     } =>
             {
+                if let Some(pos) = hitchhiker.iter().position(|&c| c == discard) {
+                    hitchhiker.remove(pos);
+                }
                 let hand = self.hand_mut(side);
-                for c in hitchhiker {
+                for &c in &hitchhiker {
                     remove_from_hand(hand, c);
                 }
+                for c in hitchhiker {
+                    self.reveal(c);
+                }
 
                 self.open_mut(side).push(case);
                 self.active_player = side;
@@ -373,6 +2082,8 @@ impl Cards {
 
                 remove_from_hand(hand, card);
                 remove_from_hand(hand, card);
+                self.reveal(card);
+                self.reveal(card);
 
                 self.open_mut(side).push(case);
                 self.active_player = side;
@@ -387,6 +2098,9 @@ impl Cards {
             {
                 let hand = self.hand_mut(side);
                 hand.remove(&card);
+                for _ in 0..3 {
+                    self.reveal(card);
+                }
                 self.open_mut(side).push(case);
                 self.active_player = side;
 
@@ -401,11 +2115,12 @@ impl Cards {
             {
                 if let Some(case) = self
                     .open_mut(side)
-                    .into_iter()
+                    .iter_mut()
                     .find(|&&mut o| o == CaseType::Ke(card))
                 {
                     *case = CaseType::Gang(card);
                     self.current_hand_mut().remove(&card);
+                    self.reveal(card);
                     true
                 } else {
                     false
@@ -420,22 +2135,176 @@ impl Cards {
             {
                 let hand = self.current_hand_mut();
                 hand.remove(&card);
+                for _ in 0..4 {
+                    self.reveal(card);
+                }
                 self.current_open_mut().push(case);
+                self[side].called.push(None);
 
                 true
             }
             _ => false,
+        };
+        if called {
+            for side in missed {
+                self.pass_ron(side);
+            }
+            self.last_discard = None;
+            let mut reds_moved = 0;
+            for (five, before) in aka::fives().into_iter().zip(fives_before) {
+                let after = self.hand(side).get(&five).copied().unwrap_or(0);
+                let reds = self.aka.leave_hand(side, five, before, after);
+                self.aka.add_to_open(side, reds);
+                reds_moved += reds;
+            }
+            if from_river {
+                if let Some(r) = self.river_mut(discarder).last_mut() {
+                    r.claimed = true;
+                }
+                let source = CalledCard {
+                    from: discarder,
+                    card: discard,
+                    red: self.aka.last_played,
+                };
+                self[side].called.push(Some(source));
+                if self.aka.last_played {
+                    self.aka.add_to_open(side, 1);
+                }
+                if is_pao_case(self.open(side), discard) {
+                    self.pao.insert(side, discarder);
+                }
+            }
+            self.drawn = None;
+            self.rinshan = matches!(case, CaseType::Gang(_) | CaseType::AnGang(_));
+            if matches!(case, CaseType::Gang(_)) && !from_river {
+                self.jia_gang = Some((side, discard));
+                self.last_discard = Some((side, discard));
+                self.aka.last_played = reds_moved > 0;
+            }
+            let timing = match case {
+                CaseType::AnGang(_) => Some(self.config.ankan_dora),
+                CaseType::Gang(_) => Some(self.config.minkan_dora),
+                _ => None,
+            };
+            if let Some(timing) = timing {
+                // The dora of a 杠 still pending is revealed by the next 杠.
+                for _ in 0..std::mem::take(&mut self.pending_dora) {
+                    self.reveal_dora();
+                }
+                match timing {
+                    KanDoraTiming::Immediate => {
+                        self.reveal_dora();
+                    }
+                    KanDoraTiming::AfterDiscard => self.pending_dora += 1,
+                }
+            }
+            self.ippatsu.clear();
+            self.interrupted = true;
+            if !self.config.kuikae {
+                self.kuikae = kuikae(case, discard);
+            }
+        }
+        if called {
+            Ok(())
+        } else {
+            Err(CallError::InvalidMeldShape)
+        }
+    }
+
+    /// Checks if the player can do the call action, see [Cards::call].
+    ///
+    /// 吃, 碰 and 大明杠 are done on the card the active player has just played, see [Cards::last_discard],
+    /// with the other cards of the case(面子) from hand, i.e. `hitchhiker`,
+    /// and 吃 only by the next player.
+    /// 加杠 and 暗杠 are done by the active player,
+    /// with all the four cards given as `hitchhiker`.
+    ///
+    /// Returns an error telling why if not.
+    pub fn validate_call(
+        &self,
+        case: CaseType,
+        side: FengType,
+        discard: CardType,
+        hitchhiker: &[CardType],
+    ) -> Result<(), CallError> {
+        let from_river = match case {
+            CaseType::Shun(_) | CaseType::Ke(_) => true,
+            CaseType::Gang(_) => hitchhiker.len() == 3,
+            CaseType::AnGang(_) => false,
+        };
+        let locked = match case {
+            CaseType::AnGang(card) => {
+                side == self.active_player && !self.can_an_gang_after_riichi(card)
+            }
+            _ => self.is_riichi(side),
+        };
+        if locked {
+            return Err(CallError::RiichiLocked);
+        }
+        if !self.rules().allows_chi() && matches!(case, CaseType::Shun(_)) {
+            return Err(CallError::ChiNotAllowed);
+        }
+        if self.has_finished(side) || self.must_declare_void(side) {
+            return Err(CallError::NotYourTurn);
+        }
+        if self.voids.get(&side) == Some(&discard.suit()) {
+            return Err(CallError::VoidSuit);
+        }
+        let in_turn = if from_river {
+            side != self.active_player
+                && (!matches!(case, CaseType::Shun(_))
+                    || side == self.next_player(self.active_player))
+        } else {
+            side == self.active_player
+        };
+        if !in_turn {
+            return Err(CallError::NotYourTurn);
+        }
+        if from_river
+            && (self.last_discard != Some((self.active_player, discard)) || self.jia_gang.is_some())
+        {
+            return Err(CallError::NotLastDiscard);
+        }
+        let mut cards = hitchhiker.to_vec();
+        if from_river {
+            cards.push(discard);
         }
+        cards.sort_unstable();
+        let mut expected = case.cards();
+        expected.sort_unstable();
+        let wraps = matches!(case, CaseType::Shun(start) if !matches!(start.rank(), Some(n) if n <= RankType::Seven));
+        if wraps || cards != expected || !expected.contains(&discard) {
+            return Err(CallError::InvalidMeldShape);
+        }
+        let from_hand = match case {
+            CaseType::Gang(card) if !from_river => vec![card],
+            _ => hitchhiker.to_vec(),
+        };
+        let mut hand = self.hand(side).clone();
+        if !from_hand
+            .into_iter()
+            .all(|c| remove_from_hand(&mut hand, c))
+        {
+            return Err(CallError::CardNotInHand);
+        }
+        if matches!(case, CaseType::Gang(card) if !from_river && !self.open(side).contains(&CaseType::Ke(card)))
+        {
+            return Err(CallError::NoTriplet);
+        }
+        Ok(())
     }
 
     /// Checks if any other player than the active player can do a call action.
+    /// The players who can complete(荣和) with the card are given by [Cards::check_dian_pao].
+    /// No player can call 吃 in 三麻 or 血战到底, see [Ruleset::allows_chi],
+    /// and in 血战到底 no player can call the declared void suit or after completing.
     ///
     /// Returns an array of the case type of the call
     /// and the player who can do it.
     pub fn check_call(&self, card: CardType) -> Vec<(FengType, CaseType)> {
         let mut res = Vec::new();
 
-        let next_side = self.active_player.next();
+        let next_side = self.next_player(self.active_player);
         let next_hand = self.hand(next_side);
         let lastlast = match card {
             CardType::Wan(num) | CardType::Tiao(num) | CardType::Tong(num)
@@ -469,112 +2338,677 @@ impl Cards {
             }
             _ => None,
         };
-        if last.is_some() && lastlast.is_some() {
-            res.push((next_side, CaseType::Shun(lastlast.unwrap())));
-        }
-        if last.is_some() && next.is_some() {
-            res.push((next_side, CaseType::Shun(last.unwrap())));
-        }
-        if next.is_some() && nextnext.is_some() {
-            res.push((next_side, CaseType::Shun(card)));
+        if self.rules().allows_chi() {
+            if let (Some(_), Some(lastlast)) = (last, lastlast) {
+                res.push((next_side, CaseType::Shun(lastlast)));
+            }
+            if let (Some(last), Some(_)) = (last, next) {
+                res.push((next_side, CaseType::Shun(last)));
+            }
+            if next.is_some() && nextnext.is_some() {
+                res.push((next_side, CaseType::Shun(card)));
+            }
         }
 
-        let hands = [
-            (next_side, next_hand),
-            (next_side.next(), self.hand(next_side.next())),
-            (next_side.next().next(), self.hand(next_side.next().next())),
-        ];
-
-        for (side, hand) in hands {
-            let num = hand.get(&card).copied().unwrap_or_default();
+        let mut side = next_side;
+        while side != self.active_player {
+            let num = self.hand(side).get(&card).copied().unwrap_or_default();
             if num >= 3 {
                 res.push((side, CaseType::Gang(card)));
             }
             if num >= 2 {
                 res.push((side, CaseType::Ke(card)));
             }
+            side = self.next_player(side);
         }
 
+        res.retain(|&(side, _)| {
+            !self.is_riichi(side)
+                && !self.has_finished(side)
+                && !self.must_declare_void(side)
+                && self.voids.get(&side) != Some(&card.suit())
+        });
+        res
+    }
+
+    /// Returns the situations of the player depending on the course of the game,
+    /// which situation checkers can not see:
+    /// `一发` if the player is in [Cards::ippatsu],
+    /// `两立直` if the player is in [Cards::double_riichi],
+    /// `开立直` if the player is in [Cards::open_riichi],
+    /// and for 自摸 on the first draw of the player with no call action before,
+    /// `天和` if no card has been played yet, i.e. for the dealer, otherwise `地和`.
+    pub fn game_situations(&self, side: FengType) -> Vec<&'static str> {
+        let mut res = Vec::new();
+        if self.ippatsu.contains(&side) {
+            res.push("一发");
+        }
+        if self.double_riichi.contains(&side) {
+            res.push("两立直");
+        }
+        if self.open_riichi.contains(&side) {
+            res.push("开立直");
+        }
+        if side == self.active_player
+            && !self.interrupted
+            && !self.rinshan
+            && self.river(side).is_empty()
+            && self.card_count(side) == self.hand_size() + 1
+        {
+            if self.players().iter().all(|&s| self.river(s).is_empty()) {
+                res.push("天和");
+            } else {
+                res.push("地和");
+            }
+        }
         res
     }
 
+    /// Returns the context of the last card checked for completion of the player, e.g. where it comes from.
+    ///
+    /// The last card of the active player is drawn, from the dead wall if [Cards::rinshan],
+    /// while the last card of another player is robbed from an 加杠 if any, see [Cards::jia_gang],
+    /// or else played by the active player.
+    ///
+    /// The winds counted as 役牌 are those of the player, see [Cards::yakuhai_winds],
+    /// and the cards shown are counted from the rivers and the opens, see [WinContext::shown].
+    pub fn win_context(&self, side: FengType) -> WinContext {
+        let source = if self.jia_gang.is_some_and(|(s, _)| s != side) {
+            CardSource::Robbed
+        } else if side != self.active_player {
+            CardSource::River
+        } else if self.rinshan {
+            CardSource::Replacement
+        } else {
+            CardSource::Mountain
+        };
+        let mut shown = TileCounts::new();
+        for &s in self.players() {
+            for river_card in self.river(s).iter().filter(|c| !c.claimed) {
+                shown[river_card.card()] += 1;
+            }
+            for case in self.open(s) {
+                if !matches!(case, CaseType::AnGang(_)) {
+                    for card in case.cards() {
+                        shown[card] += 1;
+                    }
+                }
+            }
+        }
+        let last = match source {
+            CardSource::Robbed => self.jia_gang.map(|(_, card)| card),
+            CardSource::River => self.last_discard.map(|(_, card)| card),
+            _ => None,
+        };
+        if let Some(card) = last {
+            shown[card] = shown[card].saturating_sub(1);
+        }
+        WinContext {
+            source,
+            is_last: self.card_mountain.is_empty(),
+            winds: self.yakuhai_winds(side).to_vec(),
+            shown,
+            interpretation: None,
+        }
+    }
+
     /// Checks if side wins.
     ///
-    /// Returns the completions.
+    /// The situation checkers are given the hand as it is,
+    /// so it contains the last card for 自摸 but not for 荣和,
+    /// see [Cards::winning_hand].
+    /// The situation checkers are also given [Cards::win_context],
+    /// and the situations of [Cards::game_situations] are added to those of the checkers.
+    ///
+    /// Returns the completions of the interpretation of the hand scoring the most,
+    /// see [Cards::interpret].
     pub fn win(&self, side: FengType, last_card: CardType) -> impl Iterator<Item = &Completion> {
+        self.interpret_with(side, self.hand(side), last_card, self.win_context(side))
+            .into_iter()
+            .flat_map(|reading| reading.completions)
+    }
+
+    /// Returns the interpretation of the hand of the player completing with the last card
+    /// scoring the most, given the winds counted as 役牌, see [Cards::yakuhai_winds].
+    ///
+    /// Each interpretation of the hand, see [analysis::interpretations],
+    /// except those of the knitted forms in rules without, see [Ruleset::has_knitted_forms],
+    /// is checked on its own like [Cards::win], see [WinContext::interpretation],
+    /// so situations met by different interpretations never add up.
+    /// The interpretation paying the most points by the rules is taken, see [Ruleset::payment],
+    /// then the one of the most fan and then of the most fu,
+    /// and the first one in the order of [analysis::interpretations] if they still tie.
+    ///
+    /// Returns `None` if no interpretation gives any completion.
+    pub fn interpret(
+        &self,
+        side: FengType,
+        last_card: CardType,
+        winds: &[FengType],
+    ) -> Option<Reading<'_>> {
+        let context = WinContext {
+            winds: winds.to_vec(),
+            ..self.win_context(side)
+        };
+        self.interpret_with(side, self.hand(side), last_card, context)
+    }
+
+    /// Returns the interpretation scoring the most like [Cards::interpret],
+    /// given the hand instead of the hand of the player, and the context.
+    fn interpret_with(
+        &self,
+        side: FengType,
+        hand: &Hand,
+        last_card: CardType,
+        context: WinContext,
+    ) -> Option<Reading<'_>> {
+        let open = self.open(side);
+        let rules = self.rules();
+        let (complete, _) = analysis::complete_hand(hand, open, last_card);
+        let mut interpretations: Vec<_> = analysis::interpretations(&complete, open, last_card)
+            .into_iter()
+            .filter(|i| {
+                rules.has_knitted_forms()
+                    || !matches!(i.form, FormType::QuanBuKao(_) | FormType::ZuHeLong(_))
+            })
+            .map(Some)
+            .collect();
+        if interpretations.is_empty() {
+            interpretations.push(None);
+        }
+        let mut res: Option<(Reading, (u32, u16, u8))> = None;
+        for interpretation in interpretations {
+            let fu = interpretation.as_ref().map_or(0, |i| {
+                crate::riichi::fu(i, open, last_card, context.is_tsumo(), &context.winds)
+            });
+            let context = WinContext {
+                interpretation: interpretation.clone(),
+                ..context.clone()
+            };
+            let completions = self.completions_with(side, hand, last_card, &context);
+            if completions.is_empty() {
+                continue;
+            }
+            let fan = completions.iter().map(|c| c.fan).sum();
+            let points = rules
+                .payment(&self.config, fan, fu, side, side, None)
+                .total();
+            let key = (points, fan, fu);
+            if res.as_ref().is_none_or(|(_, best)| key > *best) {
+                let reading = Reading {
+                    interpretation,
+                    completions,
+                    fan,
+                    fu,
+                };
+                res = Some((reading, key));
+            }
+        }
+        res.map(|(reading, _)| reading)
+    }
+
+    /// Returns the completions met by the hand with the last card in the context.
+    fn completions_with(
+        &self,
+        side: FengType,
+        hand: &Hand,
+        last_card: CardType,
+        context: &WinContext,
+    ) -> Vec<&Completion> {
         let situations: HashSet<_> = self
             .situation_checkers
             .iter()
-            .filter(|(_, f)| {
-                f(
-                    self.hand(side),
-                    self.river(side),
-                    self.open(side),
-                    last_card,
-                )
-            })
+            .filter(|(_, f)| f(hand, self.river(side), self.open(side), last_card, context))
             .map(|t| *t.0)
+            .chain(self.game_situations(side))
+            .collect();
+        self.completion_checkers
+            .iter()
+            .filter(|item| {
+                item.required.iter().all(|r| situations.contains(r))
+                    && !item.forbidden.iter().any(|f| situations.contains(f))
+            })
+            .collect()
+    }
+
+    /// Checks if the player has not done any call action, known as 门前清 in Chinese.
+    ///
+    /// 暗杠 does not break it.
+    pub fn is_concealed(&self, side: FengType) -> bool {
+        !self.open(side).iter().any(CaseType::is_open)
+    }
+
+    /// Checks if the hand of the player together with the winning card
+    /// consists of simples(中张牌) only, known as 断幺九 in Chinese.
+    ///
+    /// This is a pure shape check of hand and open, regardless of any rule.
+    /// See [Cards::qualifies_tanyao] for whether the hand really scores it.
+    pub fn is_tanyao(&self, side: FengType, winning: CardType) -> bool {
+        winning.is_simple()
+            && self.hand(side).keys().all(CardType::is_simple)
+            && self
+                .open(side)
+                .iter()
+                .flat_map(CaseType::cards)
+                .all(|c| c.is_simple())
+    }
+
+    /// Checks if the player scores 断幺九 with the winning card.
+    ///
+    /// Besides the shape checked by [Cards::is_tanyao],
+    /// an open hand only qualifies when [RuleConfig::kuitan] is enabled.
+    pub fn qualifies_tanyao(&self, side: FengType, winning: CardType) -> bool {
+        self.is_tanyao(side, winning) && (self.config.kuitan || self.is_concealed(side))
+    }
+
+    /// Returns the cards completing the hand of the player given their open,
+    /// see [analysis::waits].
+    ///
+    /// The hand should not contain the card just drawn.
+    pub fn waits(&self, side: FengType) -> Vec<CardType> {
+        analysis::waits(self.hand(side), self.open(side))
+    }
+
+    /// Checks if the active player can make themselves drawing hand(听牌).
+    ///
+    /// Returns an array of cards that after which being played
+    /// can lead to drawing hand(听牌) state.
+    ///
+    /// The returned array has been sorted and deduplicated.
+    pub fn check_drawing_hand(&self) -> Vec<CardType> {
+        let mut hand = self.current_hand().clone();
+        let mut discards = Vec::new();
+        for card in self.current_hand().keys().copied() {
+            remove_from_hand(&mut hand, card);
+            if analysis::is_tenpai(&hand, self.current_open()) {
+                discards.push(card);
+            }
+            *hand.entry(card).or_default() += 1;
+        }
+        discards
+    }
+
+    /// Returns the number of each card type not visible to the player,
+    /// i.e. still in the mountain, the dead wall or the hands of the other players.
+    ///
+    /// These are the copies of each card type in the wall, see [Ruleset::copies],
+    /// less those in the hand of the player and those shown to all players, see [Cards::seen_tiles],
+    /// i.e. in rivers, in open and the dora indicators(宝牌指示牌) revealed.
+    pub fn unseen(&self, side: FengType) -> TileCounts {
+        let mut all = TileCounts::new();
+        for card in init(self.rules()) {
+            all[card] += 1;
+        }
+        all - self.seen_tiles - TileCounts::from(self.hand(side))
+    }
+
+    /// Returns the acceptance(进张) of each card the active player can play,
+    /// i.e. the cards bringing the hand closer to drawing hand(听牌) after the play,
+    /// and how many copies of them are not visible to the active player.
+    ///
+    /// The returned array is in the order of the card being played.
+    pub fn acceptance(&self) -> Vec<analysis::Acceptance> {
+        let unseen = self.unseen(self.active_player);
+        let open = self.current_open();
+        let mut hand = self.current_hand().clone();
+        let mut res = Vec::new();
+        for discard in self.current_hand().keys().copied() {
+            remove_from_hand(&mut hand, discard);
+            res.push(analysis::Acceptance {
+                discard,
+                shanten: analysis::shanten(&hand, open),
+                tiles: analysis::useful_tiles(&hand, open)
+                    .into_iter()
+                    .map(|card| (card, unseen[card]))
+                    .collect(),
+            });
+            *hand.entry(discard).or_default() += 1;
+        }
+        res
+    }
+
+    /// Returns the shanten number(向听数) of the player, see [analysis::shanten].
+    pub fn shanten(&self, side: FengType) -> i8 {
+        analysis::shanten(self.hand(side), self.open(side))
+    }
+
+    /// Returns the shanten numbers(向听数) of the player for each form of complete hands,
+    /// see [analysis::shanten_breakdown].
+    pub fn shanten_breakdown(&self, side: FengType) -> analysis::ShantenBreakdown {
+        analysis::shanten_breakdown(self.hand(side), self.open(side))
+    }
+
+    /// Returns the cards that bring the player closer to drawing hand(听牌) when drawn,
+    /// whatever the shanten number(向听数) is, see [analysis::useful_tiles].
+    pub fn tiles_to_tenpai(&self, side: FengType) -> Vec<CardType> {
+        analysis::useful_tiles(self.hand(side), self.open(side))
+    }
+
+    /// Checks if the winning card is already in the hand of the player.
+    ///
+    /// It should be the case for 自摸, where the card has been drawn into the hand,
+    /// and should not for 荣和 unless the hand holds another copy of it.
+    pub fn winning_tile_is_in_hand(&self, side: FengType, winning: CardType) -> bool {
+        self.hand(side).contains_key(&winning)
+    }
+
+    /// Returns the complete hand of the player with the winning card,
+    /// which is the input of the functions in [analysis].
+    ///
+    /// For 自摸(`is_tsumo`), the winning card has been drawn into the hand,
+    /// so the hand is returned as it is and must contain the winning card.
+    /// For 荣和, the winning card is added to the hand.
+    ///
+    /// Returns `None` if the winning card of 自摸 is not in hand.
+    pub fn winning_hand(&self, side: FengType, winning: CardType, is_tsumo: bool) -> Option<Hand> {
+        let mut hand = self.hand(side).clone();
+        if is_tsumo {
+            if !self.winning_tile_is_in_hand(side, winning) {
+                return None;
+            }
+        } else {
+            *hand.entry(winning).or_default() += 1;
+        }
+        Some(hand)
+    }
+
+    /// Checks if the complete hand of the player is complete(和牌型), see [Cards::winning_hand],
+    /// in the knitted forms as well in rules having them, see [Ruleset::has_knitted_forms].
+    fn is_complete_hand(&self, side: FengType, hand: &Hand) -> bool {
+        let open = self.open(side);
+        analysis::is_complete(hand, open)
+            || self.rules().has_knitted_forms() && !analysis::knitted_forms(hand, open).is_empty()
+    }
+
+    /// Returns the fu(符) of the player completing with the winning card,
+    /// i.e. of the interpretation of the hand chosen by [Cards::interpret], see [crate::riichi::fu].
+    ///
+    /// Returns `None` if the hand gives no completion with the winning card.
+    pub fn fu(&self, side: FengType, winning: CardType, winds: &[FengType]) -> Option<u8> {
+        self.interpret(side, winning, winds)
+            .map(|reading| reading.fu)
+    }
+
+    /// Checks if the player can complete by drawing(自摸), i.e. is the active player and has drawn a card.
+    ///
+    /// A replacement card(岭上牌) is drawn as well, see [Cards::is_rinshan].
+    pub fn is_tsumo(&self, side: FengType) -> bool {
+        side == self.active_player && self.drawn.is_some()
+    }
+
+    /// Checks if the player can complete by drawing with a concealed hand, known as 门前清自摸和 in Chinese.
+    ///
+    /// This holds for a replacement card(岭上牌) as well,
+    /// so 岭上开花 on a concealed hand stacks with it.
+    pub fn is_menzen_tsumo(&self, side: FengType) -> bool {
+        self.is_tsumo(side) && self.is_concealed(side)
+    }
+
+    /// Checks if the player can complete by drawing a replacement card(岭上牌) after a 杠,
+    /// known as 岭上开花 in Chinese.
+    ///
+    /// It is always a 自摸, i.e. [Cards::is_tsumo] holds as well.
+    pub fn is_rinshan(&self, side: FengType) -> bool {
+        self.is_tsumo(side) && self.rinshan
+    }
+
+    /// Returns all the ways the hand of the player is complete with the winning card,
+    /// see [analysis::complete_forms] and [Cards::winning_hand].
+    ///
+    /// Returns an empty array if the hand is not complete.
+    pub fn complete_forms(
+        &self,
+        side: FengType,
+        winning: CardType,
+        is_tsumo: bool,
+    ) -> Vec<analysis::FormType> {
+        self.winning_hand(side, winning, is_tsumo)
+            .map(|hand| analysis::complete_forms(&hand, self.open(side)))
+            .unwrap_or_default()
+    }
+
+    /// Checks if the active player's hand is complete(自摸和牌).
+    ///
+    /// The caller should provide the card the active player just has drawn.
+    ///
+    /// Returns true if the hand is complete and [Cards::total_fan] gives a fan, otherwise false.
+    /// In 血战到底, a hand holding the declared void suit can not complete.
+    pub fn check_tsumo(&self, card: CardType) -> bool {
+        let side = self.active_player;
+        self.may_complete(side, card)
+            && self
+                .winning_hand(side, card, true)
+                .is_some_and(|hand| self.is_complete_hand(side, &hand))
+            && self.total_fan(side, card).is_some()
+    }
+
+    /// Returns the score of the player completing with the card,
+    /// i.e. drawn by the active player for 自摸, or else played by the active player,
+    /// counting the winds of the player as 役牌, see [Cards::yakuhai_winds].
+    ///
+    /// The points are paid by the rules, see [Ruleset::payment],
+    /// and the dora(宝牌) add to the fan unless the rules have none, see [Ruleset::has_dora],
+    /// or the hand is a yakuman(役满), see [YAKUMAN_FAN], while 累计役满 counts as one yakuman at most.
+    /// The players finished in 血战到底 pay nothing, see [Cards::finished].
+    /// A player liable for the completion pays for it, see [Cards::pao],
+    /// and a player not in 立直 playing the winning card to 开立直 pays a yakuman at least,
+    /// see [Cards::declare_open_riichi].
+    ///
+    /// The completions, the fan and the fu are those of the interpretation of the hand
+    /// chosen by [Cards::interpret] given the winds.
+    ///
+    /// Returns `None` if the player can not complete with the card, see [Cards::check_tsumo] and [Cards::score_if_ron].
+    pub fn score_win(&self, side: FengType, winning: CardType) -> Option<AgariResult> {
+        // A replacement card(岭上牌) is drawn as well, counting the fu of 自摸.
+        let is_tsumo = self.win_context(side).is_tsumo();
+        let can_win = if is_tsumo {
+            self.check_tsumo(winning)
+        } else {
+            self.check_ron(side, winning)
+        };
+        if !can_win {
+            return None;
+        }
+        let reading = self.interpret(side, winning, &self.yakuhai_winds(side))?;
+        let (fan, fu) = (reading.fan, reading.fu);
+        let rules = self.rules();
+        let completions: Vec<_> = reading
+            .completions
+            .iter()
+            .map(|c| (c.name.to_string(), c.fan))
             .collect();
-        self.completion_checkers.iter().filter(move |item| {
-            item.required.iter().all(|r| situations.contains(r))
-                && !item.forbidden.iter().any(|f| situations.contains(f))
+        let yakuman = completions.iter().any(|&(_, fan)| fan >= YAKUMAN_FAN);
+        // The dora do not count for 役满, and 累计役满 counts as one 役满 at most.
+        let (dora, mut total) = if rules.has_dora() && !yakuman {
+            let dora = self.dora_breakdown(side, winning, is_tsumo)?;
+            let total = (fan + dora.total()).min(YAKUMAN_FAN);
+            (dora, total)
+        } else {
+            (DoraBreakdown::default(), fan)
+        };
+        let from = (!is_tsumo).then_some(self.active_player);
+        if from.is_some_and(|from| self.open_riichi.contains(&side) && !self.is_riichi(from)) {
+            total = total.max(YAKUMAN_FAN);
+        }
+        let pao = self.pao.get(&side).copied().filter(|_| {
+            completions
+                .iter()
+                .any(|(name, _)| scoring::PAO_COMPLETIONS.contains(&name.as_str()))
+        });
+        let payment = match pao {
+            Some(liable) => scoring::pao_payment(
+                &rules.payment(&self.config, total, fu, side, self.dealer, Some(liable)),
+                from,
+            ),
+            None => rules.payment(&self.config, total, fu, side, self.dealer, from),
+        };
+        // The players finished in 血战到底 are out of the hand and pay nothing for 自摸.
+        let payment = Payment {
+            payers: payment
+                .payers
+                .into_iter()
+                .filter(|&(payer, _)| !self.has_finished(payer))
+                .collect(),
+            ..payment
+        };
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            winner = %side,
+            card = %winning,
+            completions = ?completions,
+            fan = total,
+            fu,
+            pao = ?pao,
+            points = payment.total(),
+            "completion scored"
+        );
+        Some(AgariResult {
+            winner: side,
+            from,
+            completions,
+            fan,
+            dora,
+            fu,
+            payment,
         })
     }
 
-    // /// Checks if the active player can make themselves drawing hand(听牌).
-    // ///
-    // /// Returns an array of cards that after which being played
-    // /// can lead to drawing hand(听牌) state.
-    // ///
-    // /// The returned array has been sorted and deduplicated.
-    // pub fn check_drawing_hand(&self) -> Vec<CardType> {
-    //     let mut discards = self
-    //         .drawing_hand_checkers
-    //         .iter()
-    //         .map(|f| f(self.current_hand(), self.current_open()))
-    //         .flatten()
-    //         .collect::<Vec<_>>();
-    //     discards.sort_unstable();
-    //     discards.dedup();
-    //     discards
-    // }
-
-    // /// Checks if the active player's hand is complete(自摸和牌).
-    // ///
-    // /// The caller should provide the card the active player just has drawn.
-    // ///
-    // /// Returns true if any completion checkers returns true, otherwise false.
-    // pub fn check_zi_mo(&self, card: CardType) -> bool {
-    //     self.completion_checkers.iter().any(|f| {
-    //         f(
-    //             self.current_hand(),
-    //             self.current_open(),
-    //             RiverType::Drawing(card),
-    //         )
-    //     })
-    // }
-
-    // /// Checks if any other player will complete(荣和)
-    // /// given the card the active player has just played.
-    // ///
-    // /// The caller should provide the card the active player just has played.
-    // ///
-    // /// Returns the players who would complete given the card being played.
-    // pub fn check_dian_pao(&self, card: CardType) -> Vec<FengType> {
-    //     let mut cur_side = self.active_player;
-    //     let mut res = Vec::new();
-    //     for _ in 0..3 {
-    //         cur_side = cur_side.next();
-    //         if self.completion_checkers.iter().any(|f| {
-    //             f(
-    //                 self.hand(cur_side),
-    //                 self.open(cur_side),
-    //                 RiverType::Normal(card),
-    //             )
-    //         }) {
-    //             res.push(cur_side);
-    //         }
-    //     }
-    //     res
-    // }
+    /// Checks if the player can complete(荣和) with the card the active player is playing.
+    ///
+    /// Returns true if [Cards::score_if_ron] gives a score, otherwise false.
+    pub fn check_ron(&self, side: FengType, card: CardType) -> bool {
+        self.fan_if_ron(side, card).is_some()
+    }
+
+    /// Checks if the player can complete with the card,
+    /// either by drawing(自摸, `is_tsumo`) or with the card the active player is playing(荣和).
+    ///
+    /// The hand must be complete and meet any completion,
+    /// and the player must not be in 振听 state for 荣和.
+    pub fn can_win(&self, side: FengType, card: CardType, is_tsumo: bool) -> bool {
+        if is_tsumo {
+            side == self.active_player && self.check_tsumo(card)
+        } else {
+            self.check_ron(side, card)
+        }
+    }
+
+    /// Checks if the player is in 振听 state,
+    /// i.e. any card completing the hand is in the player's own river(舍张振听),
+    /// or the player is in [Cards::passed_ron] or [Cards::riichi_furiten].
+    ///
+    /// A player passes on a 荣和 when the game goes on without completing with a card completing the hand,
+    /// see [Cards::pass_ron].
+    pub fn is_furiten(&self, side: FengType) -> bool {
+        if self.passed_ron.contains(&side) || self.riichi_furiten.contains(&side) {
+            return true;
+        }
+        let waits = analysis::waits(self.hand(side), self.open(side));
+        self.river(side).iter().any(|r| waits.contains(&r.card()))
+    }
+
+    /// Returns the points the player would get if completing(荣和)
+    /// with the card the active player is playing, as paid by the rules, see [Cards::score_win].
+    ///
+    /// Returns `None` if the player can not complete with the card,
+    /// i.e. the player is the active player,
+    /// the hand is not complete with the card,
+    /// the player is in 振听 state,
+    /// the player can not complete in 血战到底, e.g. having completed or holding the void suit,
+    /// or no completion is met.
+    pub fn score_if_ron(&self, winner: FengType, discard: CardType) -> Option<u32> {
+        if winner == self.active_player {
+            return None;
+        }
+        self.score_win(winner, discard)
+            .map(|result| result.points())
+    }
+
+    /// Returns the total fan(番数) the player would get if completing(荣和)
+    /// with the card the active player is playing, or `None` if not, see [Cards::score_if_ron].
+    fn fan_if_ron(&self, winner: FengType, discard: CardType) -> Option<u16> {
+        if winner == self.active_player
+            || self.is_furiten(winner)
+            || !self.may_complete(winner, discard)
+        {
+            return None;
+        }
+        let hand = self.winning_hand(winner, discard, false)?;
+        if !self.is_complete_hand(winner, &hand) {
+            return None;
+        }
+        self.total_fan(winner, discard)
+    }
+
+    /// Returns the total fan(番数) of the completions the player meets with the last card.
+    ///
+    /// Returns `None` if no completion is met,
+    /// the total fan is less than [Ruleset::min_fan],
+    /// or the hand completes by 后付 where not allowed, see [RuleConfig::atozuke].
+    pub fn total_fan(&self, side: FengType, last_card: CardType) -> Option<u16> {
+        if !self.config.atozuke && self.is_kata_agari(side, last_card) {
+            return None;
+        }
+        let reading =
+            self.interpret_with(side, self.hand(side), last_card, self.win_context(side))?;
+        (reading.fan >= self.rules().min_fan()).then_some(reading.fan)
+    }
+
+    /// Checks if some of the cards completing the hand of the player give no completion,
+    /// known as 片和了 in Chinese, given the last card, i.e. the card drawn for 自摸.
+    ///
+    /// The other cards completing the hand are checked as if they were the last card,
+    /// in the same situations of the game, see [Cards::win_context].
+    pub fn is_kata_agari(&self, side: FengType, last_card: CardType) -> bool {
+        let mut hand = self.hand(side).clone();
+        let is_tsumo = side == self.active_player && self.card_count(side) == self.hand_size() + 1;
+        if is_tsumo {
+            remove_from_hand(&mut hand, last_card);
+        }
+        analysis::waits(&hand, self.open(side))
+            .into_iter()
+            .filter(|&card| card != last_card)
+            .any(|card| {
+                let mut hand = hand.clone();
+                if is_tsumo {
+                    *hand.entry(card).or_default() += 1;
+                }
+                self.interpret_with(side, &hand, card, self.win_context(side))
+                    .is_none()
+            })
+    }
+
+    /// Checks if any other player will complete(荣和)
+    /// given the card the active player has just played, known as 点炮 in Chinese.
+    ///
+    /// The caller should provide the card the active player just has played.
+    ///
+    /// Returns the players who would complete given the card being played,
+    /// in the order of players after the active player, see [Cards::check_ron].
+    pub fn check_dian_pao(&self, card: CardType) -> Vec<FengType> {
+        let mut cur_side = self.next_player(self.active_player);
+        let mut res = Vec::new();
+        while cur_side != self.active_player {
+            if self.check_ron(cur_side, card) {
+                res.push(cur_side);
+            }
+            cur_side = self.next_player(cur_side);
+        }
+        res
+    }
+}
+
+impl Index<FengType> for Cards {
+    type Output = Player;
+
+    /// Returns the cards and points of the player at the side, see [Cards::players].
+    fn index(&self, side: FengType) -> &Player {
+        &self.players[side as usize]
+    }
+}
+
+impl IndexMut<FengType> for Cards {
+    fn index_mut(&mut self, side: FengType) -> &mut Player {
+        &mut self.players[side as usize]
+    }
 }
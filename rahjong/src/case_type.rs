@@ -1,11 +1,17 @@
 //! Case types and their associated methods if any.
-//! 
+//!
 //! Cases are known as 面子 in Chinese, which represents the cards being well formed, shown to other players but not in the river.
+//!
+//! A case is displayed as its cards with the suit written once, e.g. `123m`,
+//! while an 暗杠 is put in brackets, e.g. `[中中中中]`.
 
-use crate::card_type::CardType;
+use std::fmt;
+
+use crate::card_type::{CardType, Next, SuitType};
 
 /// The `CaseType` type, or known as 面子 in Chinese. See [the module level documentation](self) for more.
-#[derive(PartialEq, Eq, Clone, Copy)]
+#[derive(PartialEq, Eq, Clone, Copy, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CaseType {
     /// The 刻子 type. Contains a [CardType] indicating the card of 刻子.
     Ke(CardType),
@@ -18,3 +24,50 @@ pub enum CaseType {
     /// The 杠子 type specially for 暗杠. Contains a [CardType] indicating the card of 杠子.
     AnGang(CardType),
 }
+
+impl CaseType {
+    /// Returns all the cards forming the case.
+    pub fn cards(&self) -> Vec<CardType> {
+        match *self {
+            Self::Shun(start) => vec![start, start.next(), start.next().next()],
+            Self::Ke(card) => vec![card; 3],
+            Self::Gang(card) | Self::AnGang(card) => vec![card; 4],
+        }
+    }
+
+    /// Checks if the case is formed by a call action, i.e. shown to other players(明).
+    ///
+    /// Only [CaseType::AnGang] is not.
+    pub fn is_open(&self) -> bool {
+        !matches!(self, Self::AnGang(_))
+    }
+}
+
+impl fmt::Display for CaseType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cards = self.cards();
+        let concealed = matches!(self, Self::AnGang(_));
+        if concealed {
+            f.write_str("[")?;
+        }
+        for card in &cards {
+            match card.rank() {
+                Some(n) => write!(f, "{n}")?,
+                None => write!(f, "{card}")?,
+            }
+        }
+        if cards[0].suit() != SuitType::Zi {
+            write!(f, "{}", cards[0].suit())?;
+        }
+        if concealed {
+            f.write_str("]")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for CaseType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
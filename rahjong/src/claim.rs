@@ -0,0 +1,164 @@
+//! Claims on a played card and their arbitration.
+//!
+//! After a card is played, the other players respond to it at the same time,
+//! and only the claim of the highest priority is applied:
+//! 荣和 comes first, then 碰 and 杠, then 吃.
+//! The [ClaimArbitrator] collects the claims and applies the winning one to the [Cards].
+
+use crate::{
+    card_type::{CardType, FengType, Next},
+    cards::Cards,
+    case_type::CaseType,
+};
+
+/// The `Claim` type. Represents a player's response to the card played.
+#[derive(PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Claim {
+    /// Completing with the card, known as 荣和 in Chinese.
+    Ron,
+    /// A call action with the card, see [Cards::call].
+    ///
+    /// The case(面子) contains the card, and the other cards of it come from hand.
+    Call(CaseType),
+}
+
+impl Claim {
+    /// Returns the priority of the claim, the higher the first.
+    pub fn priority(&self) -> u8 {
+        match self {
+            Self::Ron => 2,
+            Self::Call(CaseType::Shun(_)) => 0,
+            Self::Call(_) => 1,
+        }
+    }
+}
+
+/// The `ClaimOutcome` type. Represents the claim applied by [ClaimArbitrator::apply].
+#[derive(PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ClaimOutcome {
+    /// The players completing with the card, in the order of players after the one who played it.
+    ///
+    /// The cards are not changed, and the completions should be settled by the caller.
+    Ron(Vec<FengType>),
+    /// The player doing the call action and its case.
+    Called(FengType, CaseType),
+    /// No player claims the card.
+    Passed,
+}
+
+/// The struct collecting the claims on the card played.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClaimArbitrator {
+    /// The player who played the card.
+    pub discarder: FengType,
+    /// The card played.
+    pub card: CardType,
+    /// The claims collected and the players who made them.
+    pub claims: Vec<(FengType, Claim)>,
+    /// Whether more than one player can complete with the card(一炮多响),
+    /// otherwise only the first one in the order of players does(截和).
+    pub multiple_ron: bool,
+}
+
+impl ClaimArbitrator {
+    /// Creates a new [ClaimArbitrator] for the card last played, see [Cards::last_discard].
+    ///
+    /// Returns `None` if no card is waiting for claims.
+    pub fn new(cards: &Cards) -> Option<Self> {
+        let (discarder, card) = cards.last_discard?;
+        Some(Self {
+            discarder,
+            card,
+            claims: Vec::new(),
+            multiple_ron: false,
+        })
+    }
+
+    /// Submits the claim of a player.
+    ///
+    /// The claim must be legal, i.e. the player can complete with the card for 荣和,
+    /// see [Cards::check_ron], or the call action is listed by [Cards::check_call].
+    /// Each player can submit one claim only.
+    ///
+    /// Returns whether the claim is accepted.
+    pub fn submit(&mut self, cards: &Cards, side: FengType, claim: Claim) -> bool {
+        if side == self.discarder || self.claims.iter().any(|&(s, _)| s == side) {
+            return false;
+        }
+        let legal = match claim {
+            Claim::Ron => cards.check_ron(side, self.card),
+            Claim::Call(case) => cards.check_call(self.card).contains(&(side, case)),
+        };
+        if legal {
+            self.claims.push((side, claim));
+        } else {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(side = %side, card = %self.card, "illegal claim rejected");
+        }
+        legal
+    }
+
+    /// Returns the distance of the player from the one who played the card, in the order of players.
+    fn distance(&self, side: FengType) -> u8 {
+        let mut res = 0;
+        let mut cur = self.discarder;
+        while cur != side {
+            cur = cur.next();
+            res += 1;
+        }
+        res
+    }
+
+    /// Returns the winning claims, i.e. those of the highest priority, see [Claim::priority].
+    ///
+    /// Ties are broken by the order of players after the one who played the card,
+    /// and only 荣和 may have more than one winning claim if [ClaimArbitrator::multiple_ron].
+    pub fn resolve(&self) -> Vec<(FengType, Claim)> {
+        let Some(top) = self.claims.iter().map(|(_, c)| c.priority()).max() else {
+            return Vec::new();
+        };
+        let mut res: Vec<_> = self
+            .claims
+            .iter()
+            .filter(|(_, c)| c.priority() == top)
+            .copied()
+            .collect();
+        res.sort_by_key(|&(side, _)| self.distance(side));
+        if !(self.multiple_ron && res[0].1 == Claim::Ron) {
+            res.truncate(1);
+        }
+        res
+    }
+
+    /// Applies the winning claims to the cards, see [ClaimArbitrator::resolve].
+    ///
+    /// A call action is done by [Cards::call],
+    /// while 荣和 leaves the cards as they are for the caller to settle.
+    /// Three players completing together may abort the hand,
+    /// see [crate::draw_reason::DrawReason::of_rons].
+    ///
+    /// Returns the claim applied.
+    pub fn apply(&self, cards: &mut Cards) -> ClaimOutcome {
+        let winners = self.resolve();
+        match winners.first() {
+            None => ClaimOutcome::Passed,
+            Some((_, Claim::Ron)) => {
+                ClaimOutcome::Ron(winners.into_iter().map(|(side, _)| side).collect())
+            }
+            Some(&(side, Claim::Call(case))) => {
+                let mut hitchhiker = case.cards();
+                if let Some(pos) = hitchhiker.iter().position(|&c| c == self.card) {
+                    hitchhiker.remove(pos);
+                }
+                if cards.call(case, side, self.card, hitchhiker).is_ok() {
+                    ClaimOutcome::Called(side, case)
+                } else {
+                    ClaimOutcome::Passed
+                }
+            }
+        }
+    }
+}
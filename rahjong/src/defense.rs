@@ -0,0 +1,130 @@
+//! Defense analysis(防守), estimating how dangerous each card is to play against an opponent.
+//!
+//! Only the cards shown to the player are used, i.e. the rivers, the opens,
+//! the dora indicators(宝牌指示牌) and the player's own hand, so it is fair for bots and hints.
+//! The core of this module is [danger], telling for each card type whether it is
+//! 现物(genbutsu), 筋(suji), 壁(kabe, no chance) or one chance against the opponent,
+//! and a danger score combining them.
+
+use std::collections::BTreeMap;
+
+use crate::{
+    card_type::{CardType, FengType},
+    cards::Cards,
+    tile_counts::TileCounts,
+};
+
+/// The struct telling how dangerous a card is to play against an opponent.
+#[derive(PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TileDanger {
+    /// The card.
+    pub card: CardType,
+    /// Whether the card is safe against the opponent, known as 现物 in Chinese,
+    /// which the opponent can not complete with(振听), see [Cards::safe_tiles].
+    pub genbutsu: bool,
+    /// Whether every 两面 wait on the card is ruled out by the opponent's river, known as 筋 in Chinese,
+    /// e.g. 4 in the river for 1 and 7.
+    pub suji: bool,
+    /// Whether every 两面 wait on the card is ruled out by the neighbouring cards all seen,
+    /// known as 壁 in Chinese, or no chance.
+    pub kabe: bool,
+    /// Whether every 两面 wait on the card is ruled out but for one card left,
+    /// i.e. three of the neighbouring cards seen.
+    pub one_chance: bool,
+    /// The danger score from 0 to 100, the higher the more dangerous,
+    /// supposing the opponent is in drawing hand(听牌).
+    pub score: u8,
+}
+
+/// Returns how dangerous each card type is to play against the opponent,
+/// seen by the player, in the order of card types.
+pub fn danger(cards: &Cards, viewer: FengType, opponent: FengType) -> Vec<TileDanger> {
+    let mut visible = cards.seen_tiles;
+    for (&card, &n) in cards.hand(viewer) {
+        visible[card] += n;
+    }
+    let mut river = TileCounts::new();
+    for r in cards.river(opponent) {
+        river[r.card()] += 1;
+    }
+    let safe = cards.safe_tiles(opponent);
+    (0..CardType::COUNT)
+        .filter_map(CardType::from_index)
+        .map(|card| tile_danger(card, safe.contains(&card), &visible, &river))
+        .collect()
+}
+
+/// Returns how dangerous each card type is to play against each opponent of the player,
+/// i.e. the other players of the rules, see [danger] and [Cards::players].
+pub fn dangers(cards: &Cards, viewer: FengType) -> BTreeMap<FengType, Vec<TileDanger>> {
+    cards
+        .players()
+        .iter()
+        .copied()
+        .filter(|&side| side != viewer)
+        .map(|side| (side, danger(cards, viewer, side)))
+        .collect()
+}
+
+/// Returns how dangerous the card is, given whether it is safe against the opponent,
+/// the cards seen by the player and the cards in the opponent's river.
+fn tile_danger(
+    card: CardType,
+    genbutsu: bool,
+    visible: &TileCounts,
+    river: &TileCounts,
+) -> TileDanger {
+    let index = card.index();
+    let mut res = TileDanger {
+        card,
+        genbutsu,
+        suji: false,
+        kabe: false,
+        one_chance: false,
+        score: 0,
+    };
+    if genbutsu {
+        return res;
+    }
+    let Some(rank) = card.rank().map(|r| r as usize) else {
+        // Only 单骑 and 双碰 wait on an honor, less likely when more of it is seen.
+        res.score = match visible[card] {
+            3.. => 3,
+            2 => 12,
+            _ => 25,
+        };
+        return res;
+    };
+    let base = index - rank;
+    let of = |r: usize| CardType::from_index(base + r);
+    // The 两面 waits on the card: the shape above waits on ranks up to 7th,
+    // and the shape below on ranks from 3rd, each closed by its neighbour.
+    let mut sides = Vec::new();
+    if rank <= 5 {
+        sides.push((of(rank + 3), of(rank + 1)));
+    }
+    if rank >= 3 {
+        sides.push((of(rank - 3), of(rank - 1)));
+    }
+    let count = |c: Option<CardType>, counts: &TileCounts| c.map_or(0, |c| counts[c]);
+    res.suji = sides.iter().all(|&(suji, _)| count(suji, river) > 0);
+    res.kabe = sides.iter().all(|&(_, wall)| count(wall, visible) >= 4);
+    res.one_chance = !res.kabe && sides.iter().all(|&(_, wall)| count(wall, visible) >= 3);
+    let mut score: u32 = match rank {
+        0 | 8 => 40,
+        1 | 7 => 50,
+        2 | 6 => 60,
+        _ => 70,
+    };
+    if res.suji {
+        score = score * 2 / 5;
+    }
+    if res.kabe {
+        score = score.min(20);
+    } else if res.one_chance {
+        score = score * 7 / 10;
+    }
+    res.score = score as u8;
+    res
+}
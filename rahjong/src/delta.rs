@@ -0,0 +1,473 @@
+//! The changes between two states of a hand(局) seen by a player, and their associated functions.
+//!
+//! The core of this module is [diff], which tells how a [PlayerView] has changed as a list of [StateDelta]s,
+//! e.g. a card drawn, a card played or a case(面子) called,
+//! so a server can send the changes instead of the whole view after every action,
+//! and a client can animate them before bringing its view up to date by [apply].
+//!
+//! The changes not worth animating, e.g. the points of a player, are given as the new values of the fields,
+//! and the whole view is given if the views can not be compared, e.g. of different players or games.
+
+use crate::{
+    card_type::{CardType, FengType, HuaType, SuitType},
+    cards::{remove_from_hand, CalledCard, Hand, Open, River},
+    case_type::CaseType,
+    river_type::RiverCard,
+    tile_counts::TileCounts,
+    view::{PlayerView, SeatView},
+};
+
+/// The `StateDelta` type. Represents a change of the state of a hand(局) seen by a player, see [diff].
+#[derive(PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StateDelta {
+    /// The player draws a card, only shown to the player drawing it, see [PlayerView::drawn].
+    Drawn {
+        /// The player drawing the card.
+        side: FengType,
+        /// The card drawn, or `None` if drawn by another player.
+        card: Option<CardType>,
+    },
+    /// The player plays the card into the river.
+    Discarded {
+        /// The player playing the card.
+        side: FengType,
+        /// The card played.
+        card: RiverCard,
+    },
+    /// The card at the index in the river of the player is claimed, see [RiverCard::claimed].
+    Claimed {
+        /// The player whose card is claimed.
+        side: FengType,
+        /// The index of the card in the river.
+        index: usize,
+    },
+    /// The player calls the case(面子) into open, or does an 暗杠.
+    Melded {
+        /// The player calling the case.
+        side: FengType,
+        /// The case called.
+        case: CaseType,
+        /// The card called from a river, or `None` for an 暗杠, see [SeatView::called].
+        called: Option<CalledCard>,
+    },
+    /// The player adds a card to the 刻子 at the index in open, making it an 加杠.
+    Upgraded {
+        /// The player doing the 加杠.
+        side: FengType,
+        /// The index of the case in open.
+        index: usize,
+        /// The case made.
+        case: CaseType,
+    },
+    /// The cards in the player's own hand, see [PlayerView::hand].
+    Hand {
+        /// The cards in hand.
+        hand: Hand,
+        /// The red fives(赤宝牌) in hand.
+        reds: Vec<CardType>,
+        /// The card just drawn, if any.
+        drawn: Option<CardType>,
+    },
+    /// The number of cards in the player's hand, see [SeatView::hand_count].
+    HandCount {
+        /// The player.
+        side: FengType,
+        /// The number of cards.
+        count: usize,
+    },
+    /// The player's river, changed other than by playing or claiming, see [SeatView::river].
+    River {
+        /// The player.
+        side: FengType,
+        /// The cards in the river.
+        river: River,
+    },
+    /// The player's open, changed other than by calling, see [SeatView::open].
+    Open {
+        /// The player.
+        side: FengType,
+        /// The cases in open.
+        open: Open,
+        /// The card called from a river into each case, see [SeatView::called].
+        called: Vec<Option<CalledCard>>,
+    },
+    /// The number of red fives(赤宝牌) in the player's open, see [SeatView::reds_in_open].
+    RedsInOpen {
+        /// The player.
+        side: FengType,
+        /// The number of red fives.
+        count: u8,
+    },
+    /// The flower cards(花牌) set aside by the player, see [SeatView::flowers].
+    Flowers {
+        /// The player.
+        side: FengType,
+        /// The flower cards.
+        flowers: Vec<HuaType>,
+    },
+    /// The points of the player, see [SeatView::score].
+    Score {
+        /// The player.
+        side: FengType,
+        /// The points.
+        score: i32,
+    },
+    /// Whether the player has declared 立直, with the waits shown by 开立直, see [SeatView::waits].
+    Riichi {
+        /// The player.
+        side: FengType,
+        /// Whether the player has declared 立直.
+        riichi: bool,
+        /// The waits shown.
+        waits: Vec<CardType>,
+    },
+    /// The cards safe to play against the player, see [SeatView::safe_tiles].
+    SafeTiles {
+        /// The player.
+        side: FengType,
+        /// The cards safe to play.
+        tiles: Vec<CardType>,
+    },
+    /// The number of 北 the player has set aside as dora in 三麻, see [SeatView::nuki].
+    Nuki {
+        /// The player.
+        side: FengType,
+        /// The number of 北.
+        count: u8,
+    },
+    /// The suit the player has declared void in 血战到底, see [SeatView::void].
+    Void {
+        /// The player.
+        side: FengType,
+        /// The suit declared void.
+        suit: Option<SuitType>,
+    },
+    /// The players who have completed in 血战到底, see [PlayerView::finished].
+    Finished(Vec<(FengType, Option<FengType>)>),
+    /// The player who should play a card, see [PlayerView::active_player].
+    ActivePlayer(FengType),
+    /// The number of cards left in mountain, see [PlayerView::mountain].
+    Mountain(usize),
+    /// The dora indicators(宝牌指示牌) revealed, see [PlayerView::dora_indicators].
+    DoraIndicators(Vec<CardType>),
+    /// The card last played, see [PlayerView::last_discard].
+    LastDiscard(Option<(FengType, CardType)>),
+    /// The 加杠 just done, see [PlayerView::jia_gang].
+    JiaGang(Option<(FengType, CardType)>),
+    /// The number of each card type shown to all players, see [PlayerView::seen_tiles].
+    SeenTiles(TileCounts),
+    /// The whole view, given when the views can not be compared.
+    Full(Box<PlayerView>),
+}
+
+impl StateDelta {
+    /// Applies the change to the view, see [apply].
+    ///
+    /// A change of a player not in the view is ignored.
+    pub fn apply_to(&self, view: &mut PlayerView) {
+        let own = view.side;
+        match self {
+            Self::Drawn { side, card } => {
+                if let Some(s) = seat_mut(view, *side) {
+                    s.hand_count += 1;
+                }
+                if let Some(card) = card.filter(|_| *side == own) {
+                    *view.hand.entry(card).or_default() += 1;
+                    view.drawn = Some(card);
+                }
+            }
+            Self::Discarded { side, card } => {
+                if let Some(s) = seat_mut(view, *side) {
+                    s.river.push(*card);
+                    s.hand_count = s.hand_count.saturating_sub(1);
+                }
+                if *side == own {
+                    remove_from_hand(&mut view.hand, card.card());
+                    view.drawn = None;
+                }
+            }
+            Self::Claimed { side, index } => {
+                if let Some(r) = seat_mut(view, *side).and_then(|s| s.river.get_mut(*index)) {
+                    r.claimed = true;
+                }
+            }
+            Self::Melded { side, case, called } => {
+                let mut removed = case.cards();
+                if let Some(pos) = called.and_then(|c| removed.iter().position(|&r| r == c.card)) {
+                    removed.remove(pos);
+                }
+                if let Some(s) = seat_mut(view, *side) {
+                    s.open.push(*case);
+                    s.called.push(*called);
+                    s.hand_count = s.hand_count.saturating_sub(removed.len());
+                }
+                if *side == own {
+                    for card in removed {
+                        remove_from_hand(&mut view.hand, card);
+                    }
+                    view.drawn = None;
+                }
+            }
+            Self::Upgraded { side, index, case } => {
+                if let Some(s) = seat_mut(view, *side) {
+                    if let Some(c) = s.open.get_mut(*index) {
+                        *c = *case;
+                    }
+                    s.hand_count = s.hand_count.saturating_sub(1);
+                }
+                if *side == own {
+                    if let Some(&card) = case.cards().first() {
+                        remove_from_hand(&mut view.hand, card);
+                    }
+                    view.drawn = None;
+                }
+            }
+            Self::Hand { hand, reds, drawn } => {
+                view.hand = hand.clone();
+                view.reds = reds.clone();
+                view.drawn = *drawn;
+            }
+            Self::HandCount { side, count } => {
+                if let Some(s) = seat_mut(view, *side) {
+                    s.hand_count = *count;
+                }
+            }
+            Self::River { side, river } => {
+                if let Some(s) = seat_mut(view, *side) {
+                    s.river = river.clone();
+                }
+            }
+            Self::Open { side, open, called } => {
+                if let Some(s) = seat_mut(view, *side) {
+                    s.open = open.clone();
+                    s.called = called.clone();
+                }
+            }
+            Self::RedsInOpen { side, count } => {
+                if let Some(s) = seat_mut(view, *side) {
+                    s.reds_in_open = *count;
+                }
+            }
+            Self::Flowers { side, flowers } => {
+                if let Some(s) = seat_mut(view, *side) {
+                    s.flowers = flowers.clone();
+                }
+            }
+            Self::Score { side, score } => {
+                if let Some(s) = seat_mut(view, *side) {
+                    s.score = *score;
+                }
+            }
+            Self::Riichi {
+                side,
+                riichi,
+                waits,
+            } => {
+                if let Some(s) = seat_mut(view, *side) {
+                    s.riichi = *riichi;
+                    s.waits = waits.clone();
+                }
+            }
+            Self::SafeTiles { side, tiles } => {
+                if let Some(s) = seat_mut(view, *side) {
+                    s.safe_tiles = tiles.clone();
+                }
+            }
+            Self::Nuki { side, count } => {
+                if let Some(s) = seat_mut(view, *side) {
+                    s.nuki = *count;
+                }
+            }
+            Self::Void { side, suit } => {
+                if let Some(s) = seat_mut(view, *side) {
+                    s.void = *suit;
+                }
+            }
+            Self::Finished(finished) => view.finished = finished.clone(),
+            Self::ActivePlayer(side) => view.active_player = *side,
+            Self::Mountain(n) => view.mountain = *n,
+            Self::DoraIndicators(indicators) => view.dora_indicators = indicators.clone(),
+            Self::LastDiscard(last) => view.last_discard = *last,
+            Self::JiaGang(jia_gang) => view.jia_gang = *jia_gang,
+            Self::SeenTiles(seen) => view.seen_tiles = *seen,
+            Self::Full(new) => *view = (**new).clone(),
+        }
+    }
+}
+
+/// Returns the state of the player at the side in the view, if any.
+fn seat_mut(view: &mut PlayerView, side: FengType) -> Option<&mut SeatView> {
+    view.seats.iter_mut().find(|s| s.side == side)
+}
+
+/// Applies the changes to the view in order, see [diff].
+pub fn apply(view: &mut PlayerView, deltas: &[StateDelta]) {
+    for delta in deltas {
+        delta.apply_to(view);
+    }
+}
+
+/// Returns the changes from the previous view to the new one of the same player,
+/// which bring the previous view to the new one by [apply].
+///
+/// The changes animated, i.e. [StateDelta::Discarded], [StateDelta::Claimed], [StateDelta::Melded],
+/// [StateDelta::Upgraded] and [StateDelta::Drawn], come first in this order,
+/// followed by the new values of the fields changed otherwise.
+/// Returns [StateDelta::Full] alone if the views are of different players, games or hands.
+pub fn diff(prev: &PlayerView, new: &PlayerView) -> Vec<StateDelta> {
+    let comparable = prev.side == new.side
+        && prev.rules == new.rules
+        && (prev.round_wind, prev.dealer) == (new.round_wind, new.dealer)
+        && prev.seats.len() == new.seats.len()
+        && prev
+            .seats
+            .iter()
+            .zip(&new.seats)
+            .all(|(p, n)| p.side == n.side);
+    if !comparable {
+        return vec![StateDelta::Full(Box::new(new.clone()))];
+    }
+    let mut discards = Vec::new();
+    let mut claims = Vec::new();
+    let mut calls = Vec::new();
+    let mut draws = Vec::new();
+    for (p, n) in prev.seats.iter().zip(&new.seats) {
+        let side = n.side;
+        let played = n.river.len().saturating_sub(p.river.len());
+        let kept = p.river.len() <= n.river.len()
+            && p.river.iter().zip(&n.river).all(|(a, b)| {
+                RiverCard {
+                    claimed: b.claimed,
+                    ..*a
+                } == *b
+                    && (!a.claimed || b.claimed)
+            });
+        if kept {
+            for (index, (a, b)) in p.river.iter().zip(&n.river).enumerate() {
+                if !a.claimed && b.claimed {
+                    claims.push(StateDelta::Claimed { side, index });
+                }
+            }
+            for &card in &n.river[p.river.len()..] {
+                discards.push(StateDelta::Discarded { side, card });
+            }
+        }
+        let called = n.open.len().saturating_sub(p.open.len());
+        if n.open.starts_with(&p.open) && n.called.starts_with(&p.called) {
+            for (i, &case) in n.open.iter().enumerate().skip(p.open.len()) {
+                let called = n.called.get(i).copied().flatten();
+                calls.push(StateDelta::Melded { side, case, called });
+            }
+        } else if n.open.len() == p.open.len() {
+            for (index, (a, b)) in p.open.iter().zip(&n.open).enumerate() {
+                if matches!((a, b), (CaseType::Ke(x), CaseType::Gang(y)) if x == y) {
+                    calls.push(StateDelta::Upgraded {
+                        side,
+                        index,
+                        case: *b,
+                    });
+                }
+            }
+        }
+        if n.hand_count == p.hand_count + 1 && played == 0 && called == 0 {
+            let card = if side == new.side { new.drawn } else { None };
+            draws.push(StateDelta::Drawn { side, card });
+        }
+    }
+    let mut res: Vec<_> = [discards, claims, calls, draws].concat();
+    let mut view = prev.clone();
+    apply(&mut view, &res);
+
+    if (&view.hand, &view.reds, view.drawn) != (&new.hand, &new.reds, new.drawn) {
+        res.push(StateDelta::Hand {
+            hand: new.hand.clone(),
+            reds: new.reds.clone(),
+            drawn: new.drawn,
+        });
+    }
+    for (p, n) in view.seats.iter().zip(&new.seats) {
+        let side = n.side;
+        if p.hand_count != n.hand_count {
+            res.push(StateDelta::HandCount {
+                side,
+                count: n.hand_count,
+            });
+        }
+        if p.river != n.river {
+            res.push(StateDelta::River {
+                side,
+                river: n.river.clone(),
+            });
+        }
+        if (&p.open, &p.called) != (&n.open, &n.called) {
+            res.push(StateDelta::Open {
+                side,
+                open: n.open.clone(),
+                called: n.called.clone(),
+            });
+        }
+        if p.reds_in_open != n.reds_in_open {
+            res.push(StateDelta::RedsInOpen {
+                side,
+                count: n.reds_in_open,
+            });
+        }
+        if p.flowers != n.flowers {
+            res.push(StateDelta::Flowers {
+                side,
+                flowers: n.flowers.clone(),
+            });
+        }
+        if p.score != n.score {
+            res.push(StateDelta::Score {
+                side,
+                score: n.score,
+            });
+        }
+        if (p.riichi, &p.waits) != (n.riichi, &n.waits) {
+            res.push(StateDelta::Riichi {
+                side,
+                riichi: n.riichi,
+                waits: n.waits.clone(),
+            });
+        }
+        if p.safe_tiles != n.safe_tiles {
+            res.push(StateDelta::SafeTiles {
+                side,
+                tiles: n.safe_tiles.clone(),
+            });
+        }
+        if p.nuki != n.nuki {
+            res.push(StateDelta::Nuki {
+                side,
+                count: n.nuki,
+            });
+        }
+        if p.void != n.void {
+            res.push(StateDelta::Void { side, suit: n.void });
+        }
+    }
+    if view.finished != new.finished {
+        res.push(StateDelta::Finished(new.finished.clone()));
+    }
+    if view.active_player != new.active_player {
+        res.push(StateDelta::ActivePlayer(new.active_player));
+    }
+    if view.mountain != new.mountain {
+        res.push(StateDelta::Mountain(new.mountain));
+    }
+    if view.dora_indicators != new.dora_indicators {
+        res.push(StateDelta::DoraIndicators(new.dora_indicators.clone()));
+    }
+    if view.last_discard != new.last_discard {
+        res.push(StateDelta::LastDiscard(new.last_discard));
+    }
+    if view.jia_gang != new.jia_gang {
+        res.push(StateDelta::JiaGang(new.jia_gang));
+    }
+    if view.seen_tiles != new.seen_tiles {
+        res.push(StateDelta::SeenTiles(new.seen_tiles));
+    }
+    res
+}
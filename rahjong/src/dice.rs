@@ -0,0 +1,137 @@
+//! The dice roll(掷骰) and where the wall is broken(开门), and their associated functions.
+//!
+//! [crate::cards::Cards] keeps the wall as a line, see [crate::cards::Cards::wall],
+//! while on a table the cards are stacked two high in a wall in front of each player.
+//! The dice rolled by the dealer choose the wall broken and the stack it is broken at,
+//! which tells the cards each player is dealt, the dead wall(王牌) and its dora indicators(宝牌指示牌).
+//!
+//! The table is laid out as a line of stacks going clockwise, i.e. the order cards are taken in,
+//! from the right end of 东's wall as seen by 东, then the walls of the players before 东 in turn,
+//! with the top card before the bottom card of each stack, see [wall_from_table] and [table_from_wall].
+//!
+//! Starting from the dealer as one, the total of the dice is counted around the players in turn
+//! to choose the wall broken, and the cards are taken from the stack after that many stacks
+//! from its right end, four cards to each player in turn, then one card each,
+//! see [crate::cards::Cards::hand_size].
+//! The last seven stacks are the dead wall, with the replacement cards(岭上牌) on the far end
+//! and the dora indicators on the top of the third stack from it onward.
+
+use rand::Rng;
+
+use crate::card_type::FengType;
+
+/// The `Dice` type. Represents the two dice rolled by the dealer at the start of a hand.
+#[derive(PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Dice(pub [u8; 2]);
+
+impl Dice {
+    /// Rolls the dice by the random number generator given.
+    pub fn roll<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Self([rng.gen_range(1..=6), rng.gen_range(1..=6)])
+    }
+
+    /// Returns the total of the dice, from 2 to 12.
+    pub fn total(&self) -> u8 {
+        self.0[0] + self.0[1]
+    }
+
+    /// Checks if both dice show a face from 1 to 6, which is not the case before rolled.
+    pub fn is_valid(&self) -> bool {
+        self.0.iter().all(|n| (1..=6).contains(n))
+    }
+
+    /// Returns the player whose wall is broken given the dealer and the players in the order of playing,
+    /// counting the total of the dice from the dealer as one, see [Dice::total].
+    ///
+    /// Returns `None` if the dice are not valid, or the dealer is not among the players.
+    pub fn wall_broken(&self, dealer: FengType, players: &[FengType]) -> Option<FengType> {
+        let start = players.iter().position(|&side| side == dealer)?;
+        self.is_valid()
+            .then(|| players[(start + self.total() as usize - 1) % players.len()])
+    }
+}
+
+/// Returns the position on the table of each card of the wall as a line,
+/// see the [module](self) docs.
+///
+/// Returns `None` if the walls of the players can not be stacked evenly,
+/// or the cards are too few to deal the players and set apart the dead wall.
+fn table_positions(
+    len: usize,
+    dice: Dice,
+    dealer: FengType,
+    players: &[FengType],
+    hand_size: usize,
+) -> Option<Vec<usize>> {
+    let n = players.len();
+    let dealt = n * hand_size;
+    if n == 0 || !len.is_multiple_of(2 * n) || len < dealt + 14 {
+        return None;
+    }
+    let broken = dice.wall_broken(dealer, players)?;
+    let dealer_index = players.iter().position(|&side| side == dealer)?;
+    // The walls go clockwise from 东, i.e. against the order of playing.
+    let wall_index = (n - players.iter().position(|&side| side == broken)?) % n;
+    let first = (wall_index * len / n + 2 * dice.total() as usize) % len;
+    let taken = |i: usize| (first + i) % len;
+    // The order of taking of the cards dealt to each player.
+    let mut hands = vec![Vec::with_capacity(hand_size); n];
+    let mut i = 0;
+    let sizes = std::iter::repeat_n(4, hand_size / 4).chain(std::iter::repeat_n(1, hand_size % 4));
+    for size in sizes {
+        for k in 0..n {
+            hands[(dealer_index + k) % n].extend((i..i + size).map(taken));
+            i += size;
+        }
+    }
+    let dead = len - 14;
+    let mut res: Vec<usize> = [12, 13, 10, 11, 8, 6, 4, 2, 0, 9, 7, 5, 3, 1]
+        .into_iter()
+        .map(|k| taken(dead + k))
+        .collect();
+    res.extend((dealt..dead).rev().map(taken));
+    for hand in hands.into_iter().rev() {
+        res.extend(hand);
+    }
+    Some(res)
+}
+
+/// Returns the wall as a line, see [crate::cards::Cards::from_wall],
+/// given the cards on the table, see the [module](self) docs,
+/// the dice, the dealer, the players in the order of playing and the number of cards dealt to each of them.
+///
+/// Works on anything laid out as the cards, e.g. whether each of them is a red five(赤宝牌).
+///
+/// Returns `None` if the dice are not valid, the dealer is not among the players,
+/// the walls of the players can not be stacked evenly,
+/// or the cards are too few to deal the players and set apart the dead wall.
+pub fn wall_from_table<T: Clone>(
+    table: &[T],
+    dice: Dice,
+    dealer: FengType,
+    players: &[FengType],
+    hand_size: usize,
+) -> Option<Vec<T>> {
+    let positions = table_positions(table.len(), dice, dealer, players, hand_size)?;
+    Some(positions.into_iter().map(|i| table[i].clone()).collect())
+}
+
+/// Returns the cards on the table given the wall as a line, see [wall_from_table].
+///
+/// The cards in hand have no order in [crate::cards::Cards::wall],
+/// so they are laid out as listed among the positions they are dealt from.
+pub fn table_from_wall<T: Clone>(
+    wall: &[T],
+    dice: Dice,
+    dealer: FengType,
+    players: &[FengType],
+    hand_size: usize,
+) -> Option<Vec<T>> {
+    let positions = table_positions(wall.len(), dice, dealer, players, hand_size)?;
+    let mut res = wall.to_vec();
+    for (card, i) in wall.iter().zip(positions) {
+        res[i] = card.clone();
+    }
+    Some(res)
+}
@@ -0,0 +1,58 @@
+//! Draw reasons and their associated methods if any.
+//!
+//! A hand may end in a draw(流局) with no player completing,
+//! and the [DrawReason] tells why.
+//! The hand is drawn when the mountain runs out(荒牌流局),
+//! see [crate::cards::Cards::is_exhausted].
+//! The abortive draws(途中流局) end the hand at once
+//! and are detected by [crate::cards::Cards::check_abortive_draw]
+//! and [crate::cards::Cards::can_jiu_zhong_jiu_pai].
+
+use std::fmt;
+
+use crate::card_type::FengType;
+
+/// The `DrawReason` type. See [the module level documentation](self) for more.
+#[derive(PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DrawReason {
+    /// No cards are left in mountain and the last card played is not completed with,
+    /// known as 荒牌流局 in Chinese.
+    HuangPai,
+    /// A player holds nine or more different terminals and honors(幺九牌) in the first go-around,
+    /// and chooses to end the hand, known as 九种九牌 in Chinese.
+    JiuZhongJiuPai,
+    /// All the players play the same wind with their first play
+    /// and no call action happens, known as 四风连打 in Chinese.
+    SiFengLianDa,
+    /// All the players have declared 立直, known as 四家立直 in Chinese.
+    SiJiaLiZhi,
+    /// Four 杠 have been done by more than one player, known as 四杠散了 in Chinese.
+    SiGangSanLe,
+    /// Three players complete(荣和) with the same card, known as 三家和了 in Chinese.
+    SanJiaHeLe,
+}
+
+impl DrawReason {
+    /// Returns [DrawReason::SanJiaHeLe] if three different players
+    /// complete(荣和) with the same card, otherwise `None`.
+    pub fn of_rons(winners: &[FengType]) -> Option<Self> {
+        let mut winners = winners.to_vec();
+        winners.sort_unstable();
+        winners.dedup();
+        (winners.len() >= 3).then_some(Self::SanJiaHeLe)
+    }
+}
+
+impl fmt::Display for DrawReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::HuangPai => "荒牌流局",
+            Self::JiuZhongJiuPai => "九种九牌",
+            Self::SiFengLianDa => "四风连打",
+            Self::SiJiaLiZhi => "四家立直",
+            Self::SiGangSanLe => "四杠散了",
+            Self::SanJiaHeLe => "三家和了",
+        })
+    }
+}
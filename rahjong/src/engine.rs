@@ -0,0 +1,904 @@
+//! The turn-driven engine of a hand(局), and its associated types.
+//!
+//! The core of this module is the [Engine] struct,
+//! which takes the [Action] of each player and applies it to the [Game]
+//! only if it is legal at the moment, so the caller does not mutate the [Cards] by hand.
+//!
+//! A hand goes on as follows:
+//! the active player draws a card, then plays one, declares 立直, completes(自摸), or does a 杠;
+//! after a card is played, the other players respond to it at the same time,
+//! see [crate::claim::ClaimArbitrator], and the next player draws if all of them pass.
+//! The players who do not respond in time are answered for by [Engine::time_out_claims],
+//! see [Engine::claim_timeout].
+//! The hand ends when a player completes or the hand is drawn, see [HandEnd],
+//! and is settled by the [Game] at once, see [Engine::wins], until [Engine::next_hand] starts the next one.
+//! What the hand is waiting for at the moment is kept as its [Phase],
+//! and no action out of phase is applied.
+//! In 血战到底, each player declares a void suit before the dealer draws,
+//! and the hand goes on after a player completes until three of them have, see [crate::ruleset::Ruleset::continues_after_completion].
+//!
+//! With the `tracing` feature, every action applied or rejected and the decisions made on it,
+//! e.g. the claims resolved and how the hand ends, are logged by `tracing`,
+//! so the hands disputed can be looked into from the logs of a server.
+
+use std::{collections::BTreeSet, fmt};
+
+use crate::{
+    card_type::{CardType, FengType, SuitType},
+    cards::Cards,
+    case_type::CaseType,
+    claim::{Claim, ClaimArbitrator, ClaimOutcome},
+    draw_reason::DrawReason,
+    event::{EventListener, GameEvent},
+    game::Game,
+    river_type::RiverType,
+    scoring::AgariResult,
+};
+
+/// The `Action` type. Represents what a player does.
+#[derive(PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Action {
+    /// Drawing a card, or a replacement card(岭上牌) after a 杠 or [Action::Nuki].
+    Draw,
+    /// Playing a card from hand.
+    Discard(CardType),
+    /// Declaring 立直 by playing the card, see [Cards::declare_riichi].
+    Riichi(CardType),
+    /// Declaring 立直 openly(开立直) by playing the card, see [Cards::declare_open_riichi].
+    OpenRiichi(CardType),
+    /// Calling 吃 on the card played, with the 顺子 starting from the card given.
+    Chi(CardType),
+    /// Calling 碰 on the card played.
+    Pon,
+    /// Calling 杠 with the card, i.e. 大明杠 on the card played, or 加杠 in the player's own turn.
+    Kan(CardType),
+    /// Doing an 暗杠 with the card in the player's own turn.
+    AnKan(CardType),
+    /// Setting aside a 北 as a dora in 三麻, see [Cards::nuki_dora].
+    Nuki,
+    /// Completing with the card drawn, known as 自摸 in Chinese.
+    Tsumo,
+    /// Completing with the card played or added to an 加杠, known as 荣和 in Chinese.
+    Ron,
+    /// Passing on the card played.
+    Pass,
+    /// Declaring the void suit before the first draw in 血战到底, see [Cards::declare_void].
+    DeclareVoid(SuitType),
+}
+
+/// The `HandEnd` type. Represents how a hand(局) ends.
+///
+/// In 血战到底, the hand ends with the last player completing or drawn as the mountain runs out,
+/// and all the players completing are in [Cards::finished].
+#[derive(PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HandEnd {
+    /// The player completes with the card drawn(自摸).
+    Tsumo(FengType),
+    /// The players complete with the card of another player(荣和).
+    Ron {
+        /// The players completing, in the order of players after the one who played the card.
+        winners: Vec<FengType>,
+        /// The player who played the card(放铳).
+        from: FengType,
+    },
+    /// The hand is drawn(流局).
+    Draw(DrawReason),
+}
+
+/// The `Phase` type. Represents what the hand(局) is waiting for, see [Engine::phase].
+#[derive(PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Phase {
+    /// The active player declaring the void suit before the first draw in 血战到底.
+    DeclaringVoid,
+    /// The active player drawing a card, or a replacement card(岭上牌).
+    #[default]
+    AwaitingDraw,
+    /// The active player playing a card, declaring 立直, completing(自摸) or doing a 杠 after drawing.
+    AwaitingDiscard,
+    /// The other players responding to the card just played or added to an 加杠.
+    AwaitingClaims,
+    /// The hand has ended, see [Engine::result].
+    HandFinished,
+}
+
+impl Phase {
+    /// Checks if the action can be done in the phase, regardless of the player and the cards.
+    pub fn allows(&self, action: Action) -> bool {
+        match self {
+            Phase::DeclaringVoid => matches!(action, Action::DeclareVoid(_)),
+            Phase::AwaitingDraw => action == Action::Draw,
+            Phase::AwaitingDiscard => matches!(
+                action,
+                Action::Discard(_)
+                    | Action::Riichi(_)
+                    | Action::OpenRiichi(_)
+                    | Action::Tsumo
+                    | Action::AnKan(_)
+                    | Action::Kan(_)
+                    | Action::Nuki
+            ),
+            Phase::AwaitingClaims => matches!(
+                action,
+                Action::Chi(_) | Action::Pon | Action::Kan(_) | Action::Ron | Action::Pass
+            ),
+            Phase::HandFinished => false,
+        }
+    }
+}
+
+/// The function deciding the response of a player who has not responded in time to the card waiting for claims,
+/// given the cards and the actions the player can do, see [Engine::time_out_claims].
+pub type ClaimTimeout = Box<dyn Fn(&Cards, FengType, &[Action]) -> Action + Send + Sync>;
+
+/// The struct driving a hand(局) by the actions of players.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Engine {
+    /// The game of the hand.
+    pub game: Game,
+    /// The claims on the card just played or added to an 加杠, if any.
+    pub claims: Option<ClaimArbitrator>,
+    /// The players who have responded to the card just played, including those who can not claim it.
+    pub responded: BTreeSet<FengType>,
+    /// How the hand ended, or `None` if it is still going on.
+    pub result: Option<HandEnd>,
+    /// The scores of the completions of the hand so far, in the order of completing,
+    /// each settled by [Game::settle_win] as it happens,
+    /// i.e. the winner's points rise by the payment and the counters(本场) and riichi sticks(供托) if any.
+    ///
+    /// A hand drawn as the mountain runs out(荒牌流局) is settled by [Game::settle_draw],
+    /// and an abortive draw(途中流局) pays nothing.
+    pub wins: Vec<AgariResult>,
+    /// What the hand is waiting for, kept up to date after every action applied.
+    pub phase: Phase,
+    /// The response of the players who have not responded in time, see [Engine::time_out_claims],
+    /// or `None` to pass for them.
+    ///
+    /// Not serialized, so it should be set again after deserializing.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub claim_timeout: Option<ClaimTimeout>,
+    /// The functions called with every event recorded, in the order of registering, see [Engine::on_event].
+    ///
+    /// Not serialized, see [Engine::claim_timeout].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub listeners: Vec<EventListener>,
+    /// The events of the hand so far, starting with [GameEvent::Start], see [crate::event].
+    pub events: Vec<GameEvent>,
+    /// The events taken back by [Engine::undo], grouped by action, the last one to be redone first.
+    ///
+    /// It is cleared when an action is applied, see [Engine::apply].
+    pub undone: Vec<Vec<GameEvent>>,
+}
+
+impl Engine {
+    /// Creates a new [Engine] from the game,
+    /// whose hand has just started, see [Cards::reset_for_next_hand].
+    pub fn new(game: Game) -> Self {
+        let mut res = Self {
+            events: vec![Self::start_event(&game)],
+            undone: Vec::new(),
+            game,
+            claims: None,
+            responded: BTreeSet::new(),
+            result: None,
+            wins: Vec::new(),
+            phase: Phase::default(),
+            claim_timeout: None,
+            listeners: Vec::new(),
+        };
+        res.update_phase();
+        res
+    }
+
+    /// Returns the event starting the current hand of the game, see [GameEvent::Start].
+    fn start_event(game: &Game) -> GameEvent {
+        let cards = &game.cards;
+        GameEvent::Start {
+            round_wind: cards.round_wind,
+            dealer: cards.dealer,
+            kyoku: game.kyoku,
+            honba: game.honba,
+            riichi_sticks: game.riichi_sticks,
+            scores: FengType::ALL.map(|side| cards.score(side)),
+            wall: cards.wall(),
+            reds: cards.wall_reds(),
+            dice: cards.dice,
+        }
+    }
+
+    /// Starts the next hand of the game once the hand has ended, see [Game::next_hand].
+    ///
+    /// The dealer repeats(连庄) by completing, by being in drawing hand(听牌) as the mountain runs out,
+    /// or after an abortive draw, see [Game::abort_hand].
+    /// The events and the state of the engine start over with the new hand, see [Engine::new],
+    /// and the listeners are passed its [GameEvent::Start].
+    ///
+    /// Returns false without starting a hand if the hand is still going on or the game is over.
+    pub fn next_hand(&mut self) -> bool {
+        let Some(end) = &self.result else {
+            return false;
+        };
+        let dealer = self.cards().dealer;
+        let started = match end {
+            HandEnd::Draw(DrawReason::HuangPai) => {
+                let repeats = self.cards().tenpai_players().contains(&dealer);
+                self.game.next_hand(repeats, true)
+            }
+            HandEnd::Draw(_) => self.game.abort_hand(),
+            _ => {
+                let repeats = self.wins.iter().any(|win| win.winner == dealer);
+                self.game.next_hand(repeats, false)
+            }
+        };
+        if !started {
+            return false;
+        }
+        let start = Self::start_event(&self.game);
+        for listener in &self.listeners {
+            listener(&start);
+        }
+        self.events = vec![start];
+        self.undone.clear();
+        self.claims = None;
+        self.responded.clear();
+        self.result = None;
+        self.wins.clear();
+        self.update_phase();
+        true
+    }
+
+    /// Scores the completions of the players with the card and settles them, see [Engine::wins].
+    fn settle_wins(&mut self, winners: &[FengType], card: CardType) {
+        for &side in winners {
+            if let Some(win) = self.game.score_win(side, card) {
+                self.game.settle_win(win.payment.clone());
+                self.wins.push(win);
+            }
+        }
+    }
+
+    /// Ends the hand as the mountain runs out(荒牌流局) and settles it, see [Game::settle_draw].
+    fn end_exhausted(&mut self) {
+        self.result = Some(HandEnd::Draw(DrawReason::HuangPai));
+        self.game.settle_draw();
+    }
+
+    /// Returns the cards of the current hand.
+    pub fn cards(&self) -> &Cards {
+        &self.game.cards
+    }
+
+    /// Applies the action of the player if it is legal at the moment.
+    ///
+    /// In the player's own turn, the active player can [Action::Draw] if holding 13 cards,
+    /// otherwise [Action::Discard], [Action::Riichi], [Action::Tsumo], [Action::AnKan], [Action::Kan]
+    /// or [Action::Nuki].
+    /// In 血战到底, the active player can only [Action::DeclareVoid] until all players have declared.
+    /// While a card played is waiting for claims,
+    /// the other players can [Action::Chi], [Action::Pon], [Action::Kan], [Action::Ron] or [Action::Pass],
+    /// and only [Action::Ron] or [Action::Pass] on a card added to an 加杠(抢杠).
+    ///
+    /// Any action not allowed in the current [Engine::phase] is rejected at once,
+    /// e.g. playing twice in a turn, or calling a card before it is played.
+    ///
+    /// Returns whether the action was applied. Nothing is changed if it is illegal.
+    /// The action applied and its results are recorded in [Engine::events].
+    pub fn apply(&mut self, side: FengType, action: Action) -> bool {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("apply", side = %side, action = %action).entered();
+        let cards = &self.game.cards;
+        let claimed = self.claims.as_ref().map(|c| (c.discarder, c.card));
+        let opens = FengType::ALL.map(|s| cards.open(s).len());
+        let dora_revealed = cards.dora_revealed as usize;
+        let rinshan = cards.rinshan;
+        let wins = self.wins.len();
+        if !self.phase.allows(action) || !self.apply_action(side, action) {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(phase = %self.phase, "action rejected");
+            return false;
+        }
+        self.update_phase();
+        #[cfg(feature = "tracing")]
+        tracing::debug!(phase = %self.phase, "action applied");
+        let cards = &self.game.cards;
+        let event = match (action, claimed) {
+            (Action::Pass, Some(_)) => GameEvent::Pass { side },
+            (Action::Ron, Some(_)) => GameEvent::Claim {
+                side,
+                claim: Claim::Ron,
+            },
+            (Action::Chi(start), Some(_)) => GameEvent::Claim {
+                side,
+                claim: Claim::Call(CaseType::Shun(start)),
+            },
+            (Action::Pon, Some((_, card))) => GameEvent::Claim {
+                side,
+                claim: Claim::Call(CaseType::Ke(card)),
+            },
+            (Action::Kan(card), Some(_)) => GameEvent::Claim {
+                side,
+                claim: Claim::Call(CaseType::Gang(card)),
+            },
+            (Action::Draw, _) => GameEvent::Draw {
+                side,
+                card: cards
+                    .drawn
+                    .expect("a draw applied always sets the card drawn"),
+                replacement: rinshan,
+            },
+            (Action::Discard(card), _) => GameEvent::Discard {
+                side,
+                card,
+                riichi: false,
+                open: false,
+            },
+            (Action::Riichi(card), _) => GameEvent::Discard {
+                side,
+                card,
+                riichi: true,
+                open: false,
+            },
+            (Action::OpenRiichi(card), _) => GameEvent::Discard {
+                side,
+                card,
+                riichi: true,
+                open: true,
+            },
+            (Action::AnKan(card), _) => GameEvent::AnGang { side, card },
+            (Action::Kan(card), _) => GameEvent::JiaGang { side, card },
+            (Action::Nuki, _) => GameEvent::Nuki { side },
+            (Action::DeclareVoid(suit), _) => GameEvent::DeclareVoid { side, suit },
+            _ => GameEvent::Tsumo { side },
+        };
+        let recorded = self.events.len();
+        self.events.push(event);
+        if let Some((from, _)) = claimed.filter(|_| self.claims.is_none()) {
+            for (s, n) in FengType::ALL.into_iter().zip(opens) {
+                let open = cards.open(s);
+                if open.len() > n {
+                    if let Some(&case) = open.last() {
+                        self.events.push(GameEvent::Call {
+                            side: s,
+                            case,
+                            from,
+                        });
+                    }
+                }
+            }
+            let winners: Vec<_> = self.wins[wins..].iter().map(|win| win.winner).collect();
+            if !winners.is_empty() {
+                self.events.push(GameEvent::Ron { winners, from });
+            }
+        }
+        for &indicator in cards.dora_indicators().iter().skip(dora_revealed) {
+            self.events.push(GameEvent::Dora { indicator });
+        }
+        if let Some(end) = &self.result {
+            #[cfg(feature = "tracing")]
+            tracing::info!(result = %end, "hand ended");
+            self.events.push(GameEvent::End(end.clone()));
+        }
+        for event in &self.events[recorded..] {
+            for listener in &self.listeners {
+                listener(event);
+            }
+        }
+        self.undone.clear();
+        true
+    }
+
+    /// Registers the function to be called with every event recorded from now on,
+    /// e.g. for a user interface, a logger or sound effects to react to the hand without polling it.
+    ///
+    /// The events are passed in the order of [Engine::events], right after the action causing them is applied.
+    /// The events of [Engine::replay], and so of [Engine::undo], are not passed,
+    /// as they are recorded again rather than happening.
+    pub fn on_event(&mut self, listener: EventListener) {
+        self.listeners.push(listener);
+    }
+
+    /// Rebuilds the hand from the events recorded, see [Engine::events],
+    /// i.e. restarts the hand by [GameEvent::Start] and applies the actions of the events in order,
+    /// see [GameEvent::action].
+    ///
+    /// The events may stop at any moment of the hand, which is the position rebuilt.
+    /// The options of the rules are kept, see [Cards::config].
+    ///
+    /// Returns the index of the first event which can not be replayed if any,
+    /// where the engine is left at the position before it.
+    pub fn replay(&mut self, events: &[GameEvent]) -> Result<(), usize> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("replay", events = events.len()).entered();
+        let listeners = std::mem::take(&mut self.listeners);
+        let res = self.replay_events(events);
+        self.listeners = listeners;
+        res
+    }
+
+    /// Rebuilds the hand from the events recorded, see [Engine::replay].
+    fn replay_events(&mut self, events: &[GameEvent]) -> Result<(), usize> {
+        let Some(GameEvent::Start {
+            round_wind,
+            dealer,
+            kyoku,
+            honba,
+            riichi_sticks,
+            scores,
+            wall,
+            reds,
+            dice,
+        }) = events.first()
+        else {
+            return Err(0);
+        };
+        let cards = &mut self.game.cards;
+        if !cards.reset_with_wall(*dealer, wall.clone(), reds.clone()) {
+            return Err(0);
+        }
+        cards.dice = *dice;
+        cards.round_wind = *round_wind;
+        for (side, &score) in FengType::ALL.into_iter().zip(scores) {
+            *cards.score_mut(side) = score;
+        }
+        self.game.kyoku = *kyoku;
+        self.game.honba = *honba;
+        self.game.riichi_sticks = *riichi_sticks;
+        self.claims = None;
+        self.responded.clear();
+        self.result = None;
+        self.wins.clear();
+        self.update_phase();
+        self.events = vec![events[0].clone()];
+        for (i, event) in events.iter().enumerate().skip(1) {
+            if let Some((side, action)) = event.action() {
+                if !self.apply(side, action) {
+                    return Err(i);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Takes back the last action applied, passes included, with its results,
+    /// by replaying the events before it, see [Engine::replay].
+    ///
+    /// Returns `false` if no action has been applied in the hand.
+    pub fn undo(&mut self) -> bool {
+        let Some(i) = self.events.iter().rposition(|e| e.action().is_some()) else {
+            return false;
+        };
+        let mut events = std::mem::take(&mut self.events);
+        let group = events.split_off(i);
+        let mut undone = std::mem::take(&mut self.undone);
+        let replayed = self.replay(&events).is_ok();
+        undone.push(group);
+        self.undone = undone;
+        replayed
+    }
+
+    /// Applies again the last action taken back by [Engine::undo].
+    ///
+    /// Returns `false` if no action is to be redone.
+    pub fn redo(&mut self) -> bool {
+        let Some((side, action)) = self.undone.last().and_then(|g| g.first()?.action()) else {
+            return false;
+        };
+        let mut undone = std::mem::take(&mut self.undone);
+        let applied = self.apply(side, action);
+        if applied {
+            undone.pop();
+        }
+        self.undone = undone;
+        applied
+    }
+
+    /// Updates the phase of the hand given the state of the engine, see [Engine::phase].
+    fn update_phase(&mut self) {
+        let cards = &self.game.cards;
+        let side = cards.active_player;
+        self.phase = if self.result.is_some() {
+            Phase::HandFinished
+        } else if self.claims.is_some() {
+            Phase::AwaitingClaims
+        } else if cards.must_declare_void(side) {
+            Phase::DeclaringVoid
+        } else if cards.card_count(side) > cards.hand_size() {
+            Phase::AwaitingDiscard
+        } else {
+            Phase::AwaitingDraw
+        };
+    }
+
+    /// Applies the action of the player if it is legal at the moment, see [Engine::apply].
+    fn apply_action(&mut self, side: FengType, action: Action) -> bool {
+        if self.claims.is_some() {
+            return self.respond(side, action);
+        }
+        if side != self.game.cards.active_player {
+            return false;
+        }
+        let cards = &mut self.game.cards;
+        let holding = cards.card_count(side);
+        let size = cards.hand_size();
+        if cards.must_declare_void(side) {
+            return matches!(action, Action::DeclareVoid(suit) if cards.declare_void(suit));
+        }
+        match action {
+            Action::Draw if holding == size => {
+                let drawn = if cards.rinshan {
+                    cards.draw_replacement()
+                } else {
+                    cards.draw()
+                };
+                drawn.is_some()
+            }
+            Action::Discard(card) if holding == size + 1 => {
+                self.game.play(RiverType::Normal(card)).is_ok() && {
+                    self.open_claims();
+                    true
+                }
+            }
+            Action::Riichi(card) if holding == size + 1 => {
+                self.game.play(RiverType::Drawing(card)).is_ok() && {
+                    self.open_claims();
+                    true
+                }
+            }
+            Action::OpenRiichi(card) if holding == size + 1 => {
+                self.game.declare_open_riichi(card).is_ok() && {
+                    self.open_claims();
+                    true
+                }
+            }
+            Action::Tsumo if holding == size + 1 => {
+                let Some(card) = cards.drawn.filter(|&card| cards.check_tsumo(card)) else {
+                    return false;
+                };
+                // The completion is scored before 血战到底 moves on to the next player.
+                self.settle_wins(&[side], card);
+                let cards = &mut self.game.cards;
+                if cards.rules().continues_after_completion() {
+                    cards.finish(&[side], None);
+                    if cards.is_sichuan_over() {
+                        self.result = Some(HandEnd::Tsumo(side));
+                    } else if cards.is_exhausted() {
+                        self.end_exhausted();
+                    }
+                } else {
+                    self.result = Some(HandEnd::Tsumo(side));
+                }
+                true
+            }
+            Action::Nuki => cards.nuki_dora(),
+            Action::AnKan(card) if holding == size + 1 => {
+                cards.check_an_gang().contains(&card)
+                    && cards
+                        .call(CaseType::AnGang(card), side, card, vec![card; 4])
+                        .is_ok()
+            }
+            Action::Kan(card) if holding == size + 1 => {
+                cards.check_jia_gang().contains(&card)
+                    && cards
+                        .call(CaseType::Gang(card), side, card, vec![card; 4])
+                        .is_ok()
+                    && {
+                        self.open_claims();
+                        true
+                    }
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the actions the player can do at the moment, see [Engine::apply].
+    ///
+    /// Every 吃 is listed with the 顺子 it makes,
+    /// and every card which can be played to declare 立直 is listed as [Action::Riichi].
+    /// [Action::Pass] is listed whenever the player can respond to a card.
+    pub fn legal_actions(&self, side: FengType) -> Vec<Action> {
+        let cards = &self.game.cards;
+        let mut res = Vec::new();
+        if self.phase == Phase::HandFinished {
+            return res;
+        }
+        if let Some(claims) = &self.claims {
+            if self.responded.contains(&side) {
+                return res;
+            }
+            let card = claims.card;
+            if cards.check_ron(side, card) {
+                res.push(Action::Ron);
+            }
+            if cards.jia_gang.is_none() {
+                for (s, case) in cards.check_call(card) {
+                    match case {
+                        _ if s != side => {}
+                        CaseType::Shun(start) => res.push(Action::Chi(start)),
+                        CaseType::Ke(_) => res.push(Action::Pon),
+                        CaseType::Gang(card) => res.push(Action::Kan(card)),
+                        CaseType::AnGang(_) => {}
+                    }
+                }
+            }
+            res.push(Action::Pass);
+            return res;
+        }
+        if side != cards.active_player {
+            return res;
+        }
+        if cards.must_declare_void(side) {
+            return [SuitType::Wan, SuitType::Tiao, SuitType::Tong]
+                .map(Action::DeclareVoid)
+                .to_vec();
+        }
+        let size = cards.hand_size();
+        match cards.card_count(side) {
+            n if n == size => {
+                let can_draw = if cards.rinshan {
+                    cards.can_draw_replacement()
+                } else {
+                    !cards.card_mountain.is_empty()
+                };
+                if can_draw {
+                    res.push(Action::Draw);
+                }
+            }
+            n if n == size + 1 => {
+                if cards.drawn.is_some_and(|card| cards.check_tsumo(card)) {
+                    res.push(Action::Tsumo);
+                }
+                for &card in cards.current_hand().keys() {
+                    if cards.can_discard(card) {
+                        res.push(Action::Discard(card));
+                    }
+                }
+                for &card in cards.current_hand().keys() {
+                    if cards.can_declare_riichi(card) {
+                        res.push(Action::Riichi(card));
+                        if cards.config.open_riichi {
+                            res.push(Action::OpenRiichi(card));
+                        }
+                    }
+                }
+                res.extend(cards.check_an_gang().into_iter().map(Action::AnKan));
+                res.extend(cards.check_jia_gang().into_iter().map(Action::Kan));
+                if cards.can_nuki_dora() {
+                    res.push(Action::Nuki);
+                }
+            }
+            _ => {}
+        }
+        res
+    }
+
+    /// Returns the players who have not responded yet to the card waiting for claims,
+    /// in the order of players after the one who played it.
+    ///
+    /// The players who can not claim the card are not listed, as they pass at once.
+    pub fn pending_responses(&self) -> Vec<FengType> {
+        let Some(claims) = &self.claims else {
+            return Vec::new();
+        };
+        let cards = self.cards();
+        let mut side = cards.next_player(claims.discarder);
+        let mut res = Vec::new();
+        while side != claims.discarder {
+            if !self.responded.contains(&side) {
+                res.push(side);
+            }
+            side = cards.next_player(side);
+        }
+        res
+    }
+
+    /// Closes the claims on the card waiting for them as the time to respond is up,
+    /// applying the response decided by [Engine::claim_timeout] for each player who has not responded,
+    /// or [Action::Pass] if none is set or the response is not legal.
+    ///
+    /// The winning claims are then applied as usual, see [Engine::apply].
+    ///
+    /// Returns `false` if no card is waiting for claims.
+    pub fn time_out_claims(&mut self) -> bool {
+        if self.phase != Phase::AwaitingClaims {
+            return false;
+        }
+        for side in self.pending_responses() {
+            let actions = self.legal_actions(side);
+            let action = match &self.claim_timeout {
+                Some(timeout) => timeout(self.cards(), side, &actions),
+                None => Action::Pass,
+            };
+            #[cfg(feature = "tracing")]
+            tracing::debug!(side = %side, action = %action, "claim timed out");
+            if !(actions.contains(&action) && self.apply(side, action)) {
+                self.apply(side, Action::Pass);
+            }
+        }
+        true
+    }
+
+    /// Opens the claims on the card just played or added to an 加杠,
+    /// where the players who can not claim it pass at once.
+    fn open_claims(&mut self) {
+        let cards = &self.game.cards;
+        let Some(mut claims) = ClaimArbitrator::new(cards) else {
+            return;
+        };
+        claims.multiple_ron =
+            cards.config.multiple_ron || cards.rules().continues_after_completion();
+        let card = claims.card;
+        let rons = cards.check_dian_pao(card);
+        let calls = if cards.jia_gang.is_some() {
+            Vec::new()
+        } else {
+            cards.check_call(card)
+        };
+        self.responded = cards
+            .players()
+            .iter()
+            .copied()
+            .filter(|&side| {
+                side == claims.discarder
+                    || !rons.contains(&side) && !calls.iter().any(|&(s, _)| s == side)
+            })
+            .collect();
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            discarder = %claims.discarder,
+            card = %claims.card,
+            pending = ?cards.players().iter().filter(|s| !self.responded.contains(s)).collect::<Vec<_>>(),
+            "claims opened"
+        );
+        self.claims = Some(claims);
+        self.close_claims_if_done();
+    }
+
+    /// Takes the response of a player to the card waiting for claims.
+    fn respond(&mut self, side: FengType, action: Action) -> bool {
+        if self.responded.contains(&side) {
+            return false;
+        }
+        let cards = &self.game.cards;
+        let Some(claims) = &mut self.claims else {
+            return false;
+        };
+        let card = claims.card;
+        let robbing = cards.jia_gang.is_some();
+        let claim = match action {
+            Action::Pass => None,
+            Action::Ron => Some(Claim::Ron),
+            Action::Chi(start) if !robbing => Some(Claim::Call(CaseType::Shun(start))),
+            Action::Pon if !robbing => Some(Claim::Call(CaseType::Ke(card))),
+            Action::Kan(c) if !robbing && c == card => Some(Claim::Call(CaseType::Gang(card))),
+            _ => return false,
+        };
+        if let Some(claim) = claim {
+            if !claims.submit(cards, side, claim) {
+                return false;
+            }
+        }
+        self.responded.insert(side);
+        self.close_claims_if_done();
+        true
+    }
+
+    /// Applies the winning claims once all the players have responded,
+    /// and moves on to the next player if none claims the card.
+    fn close_claims_if_done(&mut self) {
+        if self.responded.len() < self.cards().players().len() {
+            return;
+        }
+        let Some(claims) = self.claims.take() else {
+            return;
+        };
+        self.responded.clear();
+        let cards = &mut self.game.cards;
+        let robbing = cards.jia_gang.is_some();
+        let outcome = claims.apply(cards);
+        #[cfg(feature = "tracing")]
+        match &outcome {
+            ClaimOutcome::Ron(winners) => {
+                tracing::debug!(winners = ?winners, "claims resolved to 荣和")
+            }
+            ClaimOutcome::Called(side, case) => {
+                tracing::debug!(side = %side, case = %case, "claims resolved to a call")
+            }
+            ClaimOutcome::Passed => tracing::debug!("claims passed"),
+        }
+        match outcome {
+            ClaimOutcome::Ron(winners) => {
+                let continues = cards.rules().continues_after_completion();
+                let reason =
+                    DrawReason::of_rons(&winners).filter(|_| cards.rules().has_abortive_draws());
+                if let Some(reason) = reason.filter(|_| !continues) {
+                    self.result = Some(HandEnd::Draw(reason));
+                    return;
+                }
+                // The completions are scored before the 杠 is robbed, which still shows its four cards.
+                self.settle_wins(&winners, claims.card);
+                let cards = &mut self.game.cards;
+                if robbing {
+                    for &side in &winners {
+                        cards.rob_kan(side);
+                    }
+                }
+                if continues {
+                    cards.finish(&winners, Some(claims.discarder));
+                    if cards.is_sichuan_over() {
+                        self.result = Some(HandEnd::Ron {
+                            winners,
+                            from: claims.discarder,
+                        });
+                    } else if cards.is_exhausted() {
+                        self.end_exhausted();
+                    }
+                    return;
+                }
+                self.result = Some(HandEnd::Ron {
+                    winners,
+                    from: claims.discarder,
+                });
+            }
+            ClaimOutcome::Called(..) => {}
+            ClaimOutcome::Passed if robbing => {}
+            ClaimOutcome::Passed => {
+                if let Some(reason) = cards.check_abortive_draw() {
+                    self.result = Some(HandEnd::Draw(reason));
+                } else if cards.is_exhausted() {
+                    self.end_exhausted();
+                } else {
+                    cards.active_player = cards.next_playing(claims.discarder);
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Draw => f.write_str("摸牌"),
+            Self::Discard(card) => write!(f, "打{card}"),
+            Self::Riichi(card) => write!(f, "立直{card}"),
+            Self::OpenRiichi(card) => write!(f, "开立直{card}"),
+            Self::Chi(start) => write!(f, "吃{}", CaseType::Shun(*start)),
+            Self::Pon => f.write_str("碰"),
+            Self::Kan(card) => write!(f, "杠{card}"),
+            Self::AnKan(card) => write!(f, "暗杠{card}"),
+            Self::Nuki => f.write_str("拔北"),
+            Self::Tsumo => f.write_str("自摸"),
+            Self::Ron => f.write_str("荣和"),
+            Self::Pass => f.write_str("过"),
+            Self::DeclareVoid(suit) => write!(f, "定缺{suit}"),
+        }
+    }
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::DeclaringVoid => "declaring void",
+            Self::AwaitingDraw => "awaiting draw",
+            Self::AwaitingDiscard => "awaiting discard",
+            Self::AwaitingClaims => "awaiting claims",
+            Self::HandFinished => "hand finished",
+        })
+    }
+}
+
+impl fmt::Display for HandEnd {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tsumo(side) => write!(f, "{side}自摸"),
+            Self::Ron { winners, from } => {
+                for side in winners {
+                    write!(f, "{side}")?;
+                }
+                write!(f, "荣和{from}")
+            }
+            Self::Draw(reason) => write!(f, "{reason}"),
+        }
+    }
+}
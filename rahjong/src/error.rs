@@ -0,0 +1,169 @@
+//! Errors of the actions on cards, telling why an action failed.
+//!
+//! [PlayError] is returned by [crate::cards::Cards::play],
+//! and [CallError] by [crate::cards::Cards::call].
+//! [ParseCardError] is returned when parsing cards from the standard notation,
+//! see [crate::card_type::parse_cards],
+//! and [LogError] when importing a game log.
+
+use std::fmt;
+
+/// The `PlayError` type. Represents why a card can not be played.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PlayError {
+    /// The active player should draw a card or do a call action before playing,
+    /// i.e. the hand and the open do not count 14 cards.
+    NotYourTurn,
+    /// The card is not in the hand of the active player.
+    CardNotInHand,
+    /// The card can not be played right after a 吃 or 碰, known as 食替 in Chinese.
+    Kuikae,
+    /// The player has declared 立直 and can only play the card just drawn.
+    RiichiLocked,
+    /// The player has already declared 立直.
+    AlreadyRiichi,
+    /// The player has done a call action, so can not declare 立直.
+    NotConcealed,
+    /// The player does not have the points of the deposit(立直棒).
+    NotEnoughPoints,
+    /// Fewer than 4 cards are left in mountain to declare 立直.
+    NotEnoughCards,
+    /// The hand would not be in the drawing hand(听牌) state after playing the card.
+    NotTenpai,
+    /// The card is not a `RiverType::Drawing` card to declare 立直 with.
+    NotDrawingIndicator,
+    /// The rules do not have 立直, see [crate::ruleset::Ruleset::allows_riichi].
+    RiichiNotAllowed,
+    /// The rules do not have 开立直, see [crate::rule_config::RuleConfig::open_riichi].
+    OpenRiichiNotAllowed,
+    /// The player holds cards of the void suit declared in 血战到底, which must be played first,
+    /// see [crate::cards::Cards::declare_void].
+    VoidSuitFirst,
+}
+
+impl fmt::Display for PlayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            Self::NotYourTurn => "the active player can not play a card now",
+            Self::CardNotInHand => "the card is not in hand",
+            Self::Kuikae => "the card can not be played right after the call action",
+            Self::RiichiLocked => "only the card just drawn can be played after riichi",
+            Self::AlreadyRiichi => "riichi has already been declared",
+            Self::NotConcealed => "riichi can not be declared after a call action",
+            Self::NotEnoughPoints => "not enough points to declare riichi",
+            Self::NotEnoughCards => "not enough cards left in mountain to declare riichi",
+            Self::NotTenpai => "the hand would not be tenpai",
+            Self::NotDrawingIndicator => "the card is not played as a drawing hand indicator",
+            Self::RiichiNotAllowed => "riichi is not allowed by the rules",
+            Self::OpenRiichiNotAllowed => "open riichi is not allowed by the rules",
+            Self::VoidSuitFirst => "the cards of the void suit must be played first",
+        };
+        f.write_str(msg)
+    }
+}
+
+impl std::error::Error for PlayError {}
+
+/// The `CallError` type. Represents why a call action can not be done.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CallError {
+    /// The player can not do the call action now,
+    /// e.g. a 吃 by other than the next player, or an 暗杠 by other than the active player.
+    NotYourTurn,
+    /// The cards from hand are not all in the hand of the player.
+    CardNotInHand,
+    /// The cards do not form the case(面子) together with the card called.
+    InvalidMeldShape,
+    /// There is no 刻子 in open to add the card to for 加杠.
+    NoTriplet,
+    /// The player has declared 立直 and can only do an 暗杠 keeping the waits.
+    RiichiLocked,
+    /// 吃 is not allowed by the rules, e.g. in 三麻.
+    ChiNotAllowed,
+    /// The card is of the void suit declared by the player in 血战到底,
+    /// see [crate::cards::Cards::declare_void].
+    VoidSuit,
+    /// The card called from the river is not the one the active player has just played,
+    /// see [crate::cards::Cards::last_discard].
+    NotLastDiscard,
+}
+
+impl fmt::Display for CallError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            Self::NotYourTurn => "the player can not do the call action now",
+            Self::CardNotInHand => "the cards are not in hand",
+            Self::InvalidMeldShape => "the cards do not form the case",
+            Self::NoTriplet => "there is no triplet in open to add the card to",
+            Self::RiichiLocked => "only a concealed kong keeping the waits is allowed after riichi",
+            Self::ChiNotAllowed => "chi is not allowed by the rules",
+            Self::VoidSuit => "the card is of the void suit",
+            Self::NotLastDiscard => "the card is not the one just played",
+        };
+        f.write_str(msg)
+    }
+}
+
+impl std::error::Error for CallError {}
+
+/// The `ParseCardError` type. Represents why a string can not be parsed into cards.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ParseCardError {
+    /// The character is neither a rank, a suit nor a 字.
+    InvalidChar(char),
+    /// The rank does not exist in the suit, e.g. `8z`.
+    InvalidRank(char),
+    /// The ranks at the end are not followed by a suit.
+    MissingSuit,
+    /// The string is not exactly one card.
+    NotSingleCard,
+    /// A card appears more than 4 times in a hand.
+    TooManyCopies,
+}
+
+impl fmt::Display for ParseCardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidChar(c) => write!(f, "invalid character `{c}`"),
+            Self::InvalidRank(c) => write!(f, "invalid rank `{c}`"),
+            Self::MissingSuit => f.write_str("ranks not followed by a suit"),
+            Self::NotSingleCard => f.write_str("not exactly one card"),
+            Self::TooManyCopies => f.write_str("a card appears more than 4 times"),
+        }
+    }
+}
+
+impl std::error::Error for ParseCardError {}
+
+/// The `LogError` type. Represents why a game log can not be imported.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum LogError {
+    /// The log is not in the format expected, e.g. not valid JSON.
+    InvalidFormat,
+    /// A field is missing or of a wrong type.
+    InvalidField(&'static str),
+    /// The code does not stand for a card.
+    InvalidCard(i64),
+    /// The call action can not be read.
+    InvalidCall,
+    /// The cards or the actions contradict each other, e.g. more than 4 cards of a kind.
+    Inconsistent,
+}
+
+impl fmt::Display for LogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidFormat => f.write_str("the log is not in the format expected"),
+            Self::InvalidField(field) => write!(f, "the field `{field}` is missing or invalid"),
+            Self::InvalidCard(code) => write!(f, "the code {code} is not a card"),
+            Self::InvalidCall => f.write_str("the call action can not be read"),
+            Self::Inconsistent => f.write_str("the cards or the actions contradict each other"),
+        }
+    }
+}
+
+impl std::error::Error for LogError {}
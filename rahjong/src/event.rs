@@ -0,0 +1,191 @@
+//! Events of a hand(局) recorded by the engine, and their associated functions.
+//!
+//! Every state transition made by [crate::engine::Engine] is recorded as a [GameEvent]
+//! in the append-only log [crate::engine::Engine::events].
+//! The log starts with [GameEvent::Start], holding the wall the hand was dealt from,
+//! and the events caused by the actions of players follow,
+//! so any position of the hand can be rebuilt from the log, see [crate::engine::Engine::replay].
+//!
+//! Some events are the actions of players, see [GameEvent::action],
+//! while the others, e.g. [GameEvent::Call], [GameEvent::Ron] and [GameEvent::End], are their results.
+//!
+//! Besides the log, the events can be followed as they happen by an [EventListener],
+//! see [crate::engine::Engine::on_event].
+
+use crate::{
+    card_type::{CardType, FengType, SuitType},
+    case_type::CaseType,
+    claim::Claim,
+    dice::Dice,
+    engine::{Action, HandEnd},
+};
+
+/// The function called with every event recorded by the engine, see [crate::engine::Engine::on_event].
+pub type EventListener = Box<dyn Fn(&GameEvent) + Send + Sync>;
+
+/// The `GameEvent` type. Represents a state transition of a hand(局).
+#[derive(PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GameEvent {
+    /// The hand starts with the wall dealt, see [crate::cards::Cards::wall].
+    Start {
+        /// The wind of the round, known as 场风 in Chinese.
+        round_wind: FengType,
+        /// The dealer of the hand, known as 庄家 in Chinese.
+        dealer: FengType,
+        /// The index of the hand in the round, see [crate::game::Game::kyoku].
+        kyoku: u8,
+        /// The counter of the hand, known as 本场 in Chinese.
+        honba: u8,
+        /// The number of riichi sticks(供托) on the table.
+        riichi_sticks: u8,
+        /// The points of players, in the order of players.
+        scores: [i32; 4],
+        /// The wall the hand was dealt from.
+        wall: Vec<CardType>,
+        /// Whether each card of the wall is a red five(赤宝牌), see [crate::cards::Cards::wall_reds].
+        reds: Vec<bool>,
+        /// The dice rolled by the dealer, see [crate::cards::Cards::dice].
+        #[cfg_attr(feature = "serde", serde(default))]
+        dice: Dice,
+    },
+    /// The player draws a card, or a replacement card(岭上牌) after a 杠 or 拔北.
+    Draw {
+        /// The player drawing.
+        side: FengType,
+        /// The card drawn.
+        card: CardType,
+        /// Whether the card is a replacement card(岭上牌).
+        replacement: bool,
+    },
+    /// The player plays a card from hand.
+    Discard {
+        /// The player playing.
+        side: FengType,
+        /// The card played.
+        card: CardType,
+        /// Whether the player declares 立直 by playing the card.
+        riichi: bool,
+        /// Whether the 立直 is declared openly, known as 开立直 in Chinese,
+        /// see [crate::cards::Cards::declare_open_riichi].
+        #[cfg_attr(feature = "serde", serde(default))]
+        open: bool,
+    },
+    /// The player claims the card played or added to an 加杠.
+    Claim {
+        /// The player claiming.
+        side: FengType,
+        /// The claim submitted, see [crate::claim::ClaimArbitrator::submit].
+        claim: Claim,
+    },
+    /// The player passes on the card played or added to an 加杠.
+    Pass {
+        /// The player passing.
+        side: FengType,
+    },
+    /// The winning claim of a call action is applied, after all the players have responded.
+    Call {
+        /// The player doing the call action.
+        side: FengType,
+        /// The case(面子) made.
+        case: CaseType,
+        /// The player who played the card.
+        from: FengType,
+    },
+    /// The winning claims of 荣和 are applied, after all the players have responded.
+    ///
+    /// In 血战到底 the hand may go on after it, see [crate::cards::Cards::finished].
+    Ron {
+        /// The players completing, in the order of players after the one who played the card.
+        winners: Vec<FengType>,
+        /// The player who played the card(放铳).
+        from: FengType,
+    },
+    /// The player does an 暗杠 with the card.
+    AnGang {
+        /// The player doing the 杠.
+        side: FengType,
+        /// The card of the 杠.
+        card: CardType,
+    },
+    /// The player adds the card to a 刻子 of the open, known as 加杠 in Chinese.
+    JiaGang {
+        /// The player doing the 杠.
+        side: FengType,
+        /// The card added.
+        card: CardType,
+    },
+    /// The player sets aside a 北 as a dora in 三麻, known as 拔北 in Chinese.
+    Nuki {
+        /// The player setting aside the 北.
+        side: FengType,
+    },
+    /// The player declares the void suit in 血战到底, known as 定缺 in Chinese.
+    DeclareVoid {
+        /// The player declaring.
+        side: FengType,
+        /// The suit declared void.
+        suit: SuitType,
+    },
+    /// The player completes with the card drawn(自摸).
+    Tsumo {
+        /// The player completing.
+        side: FengType,
+    },
+    /// A new dora indicator(宝牌指示牌) is revealed.
+    Dora {
+        /// The indicator revealed.
+        indicator: CardType,
+    },
+    /// The hand ends.
+    End(HandEnd),
+}
+
+impl GameEvent {
+    /// Returns the player and the action causing the event,
+    /// or `None` if the event is not an action but its result, e.g. [GameEvent::Call].
+    pub fn action(&self) -> Option<(FengType, Action)> {
+        let res = match *self {
+            Self::Draw { side, .. } => (side, Action::Draw),
+            Self::Discard {
+                side,
+                card,
+                riichi: false,
+                ..
+            } => (side, Action::Discard(card)),
+            Self::Discard {
+                side,
+                card,
+                riichi: true,
+                open: false,
+            } => (side, Action::Riichi(card)),
+            Self::Discard {
+                side,
+                card,
+                riichi: true,
+                open: true,
+            } => (side, Action::OpenRiichi(card)),
+            Self::Claim { side, claim } => (
+                side,
+                match claim {
+                    Claim::Ron => Action::Ron,
+                    Claim::Call(CaseType::Shun(start)) => Action::Chi(start),
+                    Claim::Call(CaseType::Ke(_)) => Action::Pon,
+                    Claim::Call(CaseType::Gang(card) | CaseType::AnGang(card)) => Action::Kan(card),
+                },
+            ),
+            Self::Pass { side } => (side, Action::Pass),
+            Self::AnGang { side, card } => (side, Action::AnKan(card)),
+            Self::JiaGang { side, card } => (side, Action::Kan(card)),
+            Self::Nuki { side } => (side, Action::Nuki),
+            Self::Tsumo { side } => (side, Action::Tsumo),
+            Self::DeclareVoid { side, suit } => (side, Action::DeclareVoid(suit)),
+            Self::Start { .. }
+            | Self::Call { .. }
+            | Self::Ron { .. }
+            | Self::Dora { .. }
+            | Self::End(_) => return None,
+        };
+        Some(res)
+    }
+}
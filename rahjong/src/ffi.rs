@@ -0,0 +1,325 @@
+//! A C API over the engine, behind the `ffi` feature, for Unity, Godot, C++ and other hosts.
+//!
+//! A game is a handle, i.e. an opaque pointer to [RahjongGame],
+//! created by [rahjong_game_new] and released by [rahjong_game_free],
+//! and every other function takes the handle as its first argument.
+//! The handle must not be used by more than one thread at a time.
+//!
+//! Players are the numbers `0` to `3` in the order of players, i.e. 东 is `0`,
+//! and cards are their indices, see [CardType::index].
+//! An action is a [RahjongAction], whose card is ignored unless the kind needs one.
+//! The state shown to a player is returned as a JSON string, see [rahjong_view_json],
+//! and should be released by [rahjong_string_free].
+//!
+//! The crate is built as a library for Rust, so the library for the host is built on demand, e.g.
+//!
+//! ```text
+//! cargo rustc -p rahjong --release --features ffi --crate-type cdylib
+//! ```
+//!
+//! or with `--crate-type staticlib` to link it statically.
+
+use std::{
+    ffi::{c_char, CString},
+    ptr,
+};
+
+use crate::{
+    card_type::{CardType, FengType, RankType, SuitType},
+    cards::Cards,
+    engine::{Action, Engine, HandEnd},
+    game::{Game, GameLength},
+};
+
+/// A game of riichi mahjong driven by the actions of players, see [Engine].
+pub struct RahjongGame {
+    engine: Engine,
+}
+
+/// The kind of a [RahjongAction], one for each [Action], as the byte of [RahjongAction::kind].
+#[repr(u8)]
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub enum RahjongActionKind {
+    /// [Action::Draw].
+    Draw = 0,
+    /// [Action::Discard], with the card played.
+    Discard = 1,
+    /// [Action::Riichi], with the card played.
+    Riichi = 2,
+    /// [Action::Chi], with the card starting the 顺子.
+    Chi = 3,
+    /// [Action::Pon].
+    Pon = 4,
+    /// [Action::Kan], with the card of the 杠.
+    Kan = 5,
+    /// [Action::AnKan], with the card of the 杠.
+    AnKan = 6,
+    /// [Action::Tsumo].
+    Tsumo = 7,
+    /// [Action::Ron].
+    Ron = 8,
+    /// [Action::Pass].
+    Pass = 9,
+    /// [Action::Nuki].
+    Nuki = 10,
+    /// [Action::DeclareVoid], with any card of the suit declared.
+    DeclareVoid = 11,
+    /// [Action::OpenRiichi], with the card played.
+    OpenRiichi = 12,
+}
+
+impl TryFrom<u8> for RahjongActionKind {
+    type Error = u8;
+
+    /// Returns the kind of the byte, or the byte back if it is no kind.
+    fn try_from(value: u8) -> Result<Self, u8> {
+        Ok(match value {
+            0 => Self::Draw,
+            1 => Self::Discard,
+            2 => Self::Riichi,
+            3 => Self::Chi,
+            4 => Self::Pon,
+            5 => Self::Kan,
+            6 => Self::AnKan,
+            7 => Self::Tsumo,
+            8 => Self::Ron,
+            9 => Self::Pass,
+            10 => Self::Nuki,
+            11 => Self::DeclareVoid,
+            12 => Self::OpenRiichi,
+            _ => return Err(value),
+        })
+    }
+}
+
+/// An [Action] in C, as its kind and the index of its card if any, see [CardType::index].
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct RahjongAction {
+    /// The kind of the action, see [RahjongActionKind].
+    ///
+    /// A plain byte rather than the enum, since the host may pass any value.
+    pub kind: u8,
+    /// The index of the card of the action, or `0` if the kind has no card.
+    pub card: u8,
+}
+
+impl From<Action> for RahjongAction {
+    fn from(action: Action) -> Self {
+        let (kind, card) = match action {
+            Action::Draw => (RahjongActionKind::Draw, None),
+            Action::Discard(c) => (RahjongActionKind::Discard, Some(c)),
+            Action::Riichi(c) => (RahjongActionKind::Riichi, Some(c)),
+            Action::Chi(c) => (RahjongActionKind::Chi, Some(c)),
+            Action::Pon => (RahjongActionKind::Pon, None),
+            Action::Kan(c) => (RahjongActionKind::Kan, Some(c)),
+            Action::AnKan(c) => (RahjongActionKind::AnKan, Some(c)),
+            Action::Tsumo => (RahjongActionKind::Tsumo, None),
+            Action::Ron => (RahjongActionKind::Ron, None),
+            Action::Pass => (RahjongActionKind::Pass, None),
+            Action::Nuki => (RahjongActionKind::Nuki, None),
+            Action::DeclareVoid(suit) => (RahjongActionKind::DeclareVoid, suit_card(suit)),
+            Action::OpenRiichi(c) => (RahjongActionKind::OpenRiichi, Some(c)),
+        };
+        Self {
+            kind: kind as u8,
+            card: card.map_or(0, |c| c.index() as u8),
+        }
+    }
+}
+
+impl RahjongAction {
+    /// Returns the [Action], or `None` if the kind is unknown or the card is out of range.
+    pub fn action(&self) -> Option<Action> {
+        let card = CardType::from_index(self.card as usize);
+        Some(match RahjongActionKind::try_from(self.kind).ok()? {
+            RahjongActionKind::Draw => Action::Draw,
+            RahjongActionKind::Discard => Action::Discard(card?),
+            RahjongActionKind::Riichi => Action::Riichi(card?),
+            RahjongActionKind::Chi => Action::Chi(card?),
+            RahjongActionKind::Pon => Action::Pon,
+            RahjongActionKind::Kan => Action::Kan(card?),
+            RahjongActionKind::AnKan => Action::AnKan(card?),
+            RahjongActionKind::Tsumo => Action::Tsumo,
+            RahjongActionKind::Ron => Action::Ron,
+            RahjongActionKind::Pass => Action::Pass,
+            RahjongActionKind::Nuki => Action::Nuki,
+            RahjongActionKind::DeclareVoid => Action::DeclareVoid(card?.suit()),
+            RahjongActionKind::OpenRiichi => Action::OpenRiichi(card?),
+        })
+    }
+}
+
+/// Returns the 1 of the number suit standing for it, or `None` for the honors.
+fn suit_card(suit: SuitType) -> Option<CardType> {
+    match suit {
+        SuitType::Wan => Some(CardType::Wan(RankType::One)),
+        SuitType::Tiao => Some(CardType::Tiao(RankType::One)),
+        SuitType::Tong => Some(CardType::Tong(RankType::One)),
+        SuitType::Zi => None,
+    }
+}
+
+/// Returns the player of the number, where 东 is `0`.
+fn side_of(index: u8) -> Option<FengType> {
+    FengType::ALL.get(index as usize).copied()
+}
+
+/// Creates a game of riichi mahjong whose first hand has just started,
+/// see [Cards::with_riichi_rules].
+///
+/// The walls are built from the seed if `seeded` is true, see [Cards::with_riichi_rules_seeded].
+/// The handle returned should be released by [rahjong_game_free].
+#[no_mangle]
+pub extern "C" fn rahjong_game_new(seeded: bool, seed: u64) -> *mut RahjongGame {
+    let cards = if seeded {
+        Cards::with_riichi_rules_seeded(seed)
+    } else {
+        Cards::with_riichi_rules()
+    };
+    let engine = Engine::new(Game::new(cards, GameLength::default()));
+    Box::into_raw(Box::new(RahjongGame { engine }))
+}
+
+/// Releases the game. Nothing is done if the handle is null.
+///
+/// # Safety
+///
+/// The handle should come from [rahjong_game_new] and not have been released.
+#[no_mangle]
+pub unsafe extern "C" fn rahjong_game_free(game: *mut RahjongGame) {
+    if !game.is_null() {
+        drop(Box::from_raw(game));
+    }
+}
+
+/// Returns the player who should play a card, or `-1` if the handle is null.
+///
+/// # Safety
+///
+/// The handle should be null or valid, see [rahjong_game_free].
+#[no_mangle]
+pub unsafe extern "C" fn rahjong_active_player(game: *const RahjongGame) -> i32 {
+    let Some(game) = game.as_ref() else {
+        return -1;
+    };
+    let side = game.engine.cards().active_player;
+    FengType::ALL.iter().position(|&s| s == side).unwrap_or(0) as i32
+}
+
+/// Applies the action of the player if it is legal at the moment, see [Engine::apply].
+///
+/// Returns whether the action was applied, and `false` for an unknown kind or card.
+///
+/// # Safety
+///
+/// The handle should be null or valid, see [rahjong_game_free].
+#[no_mangle]
+pub unsafe extern "C" fn rahjong_apply(
+    game: *mut RahjongGame,
+    side: u8,
+    action: RahjongAction,
+) -> bool {
+    let (Some(game), Some(side), Some(action)) = (game.as_mut(), side_of(side), action.action())
+    else {
+        return false;
+    };
+    game.engine.apply(side, action)
+}
+
+/// Writes the actions the player can do at the moment into the buffer, see [Engine::legal_actions].
+///
+/// Returns the number of the actions, which may be larger than the capacity of the buffer,
+/// and only as many as the capacity are written.
+///
+/// # Safety
+///
+/// The handle should be null or valid, see [rahjong_game_free],
+/// and the buffer should hold `capacity` actions, or be null if `capacity` is `0`.
+#[no_mangle]
+pub unsafe extern "C" fn rahjong_legal_actions(
+    game: *const RahjongGame,
+    side: u8,
+    buffer: *mut RahjongAction,
+    capacity: usize,
+) -> usize {
+    let (Some(game), Some(side)) = (game.as_ref(), side_of(side)) else {
+        return 0;
+    };
+    let actions = game.engine.legal_actions(side);
+    for (i, &action) in actions.iter().take(capacity).enumerate() {
+        buffer.add(i).write(action.into());
+    }
+    actions.len()
+}
+
+/// Returns how the hand ended:
+/// `0` if it is still going on, `1` by 自摸, `2` by 荣和, `3` by a draw(流局),
+/// or `-1` if the handle is null.
+///
+/// # Safety
+///
+/// The handle should be null or valid, see [rahjong_game_free].
+#[no_mangle]
+pub unsafe extern "C" fn rahjong_result(game: *const RahjongGame) -> i32 {
+    let Some(game) = game.as_ref() else {
+        return -1;
+    };
+    match game.engine.result {
+        None => 0,
+        Some(HandEnd::Tsumo(_)) => 1,
+        Some(HandEnd::Ron { .. }) => 2,
+        Some(HandEnd::Draw(_)) => 3,
+    }
+}
+
+/// Returns the state of the hand shown to the player as a JSON string, see [Cards::view],
+/// or null if the handle is null or the player is out of range.
+///
+/// The string should be released by [rahjong_string_free].
+///
+/// # Safety
+///
+/// The handle should be null or valid, see [rahjong_game_free].
+#[no_mangle]
+pub unsafe extern "C" fn rahjong_view_json(game: *const RahjongGame, side: u8) -> *mut c_char {
+    let (Some(game), Some(side)) = (game.as_ref(), side_of(side)) else {
+        return ptr::null_mut();
+    };
+    serde_json::to_string(&game.engine.cards().view(side))
+        .ok()
+        .and_then(|s| CString::new(s).ok())
+        .map_or(ptr::null_mut(), CString::into_raw)
+}
+
+/// Releases a string returned by this module. Nothing is done if the string is null.
+///
+/// # Safety
+///
+/// The string should come from this module and not have been released.
+#[no_mangle]
+pub unsafe extern "C" fn rahjong_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+/// Takes back the last action applied, see [Engine::undo].
+///
+/// # Safety
+///
+/// The handle should be null or valid, see [rahjong_game_free].
+#[no_mangle]
+pub unsafe extern "C" fn rahjong_undo(game: *mut RahjongGame) -> bool {
+    game.as_mut().is_some_and(|game| game.engine.undo())
+}
+
+/// Applies again the last action taken back, see [Engine::redo].
+///
+/// # Safety
+///
+/// The handle should be null or valid, see [rahjong_game_free].
+#[no_mangle]
+pub unsafe extern "C" fn rahjong_redo(game: *mut RahjongGame) -> bool {
+    game.as_mut().is_some_and(|game| game.engine.redo())
+}
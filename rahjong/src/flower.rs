@@ -0,0 +1,89 @@
+//! Flower cards(花牌) and their associated functions.
+//!
+//! Flower cards are shuffled into the wall with the other cards,
+//! but a player drawing one sets it aside and draws another card at once(补花),
+//! so they never stay in hand.
+//! The [Flowers] struct schedules when each flower card is drawn
+//! and collects the flower cards of each player.
+
+use std::collections::BTreeMap;
+
+use rand::Rng;
+
+use crate::card_type::{FengType, HuaType};
+
+/// The struct tracking the flower cards(花牌) of the game.
+#[derive(Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Flowers {
+    /// Whether the eight flower cards are in the wall.
+    ///
+    /// Takes effect when the wall is built, see [crate::cards::Cards::reset_for_next_hand].
+    pub enabled: bool,
+    /// The flower cards in mountain,
+    /// each with the number of cards left in mountain when it is drawn.
+    pub schedule: Vec<(usize, HuaType)>,
+    /// The flower cards set aside by each player.
+    pub collected: BTreeMap<FengType, Vec<HuaType>>,
+}
+
+impl Flowers {
+    /// Shuffles the flower cards into the wall if enabled,
+    /// given the players dealt, the number of cards dealt to each of them,
+    /// and the number of cards left in mountain after dealing,
+    /// and clears the flower cards of players.
+    ///
+    /// Flower cards dealt are set aside at once.
+    pub fn mark<R: Rng + ?Sized>(
+        &mut self,
+        players: &[FengType],
+        hand_size: usize,
+        mountain_len: usize,
+        rng: &mut R,
+    ) {
+        self.schedule.clear();
+        self.collected.clear();
+        if !self.enabled {
+            return;
+        }
+        let dealt = hand_size * players.len();
+        for hua in HuaType::ALL {
+            let position = rng.gen_range(0..dealt + mountain_len);
+            if position < dealt {
+                let side = players[position / hand_size];
+                self.collected.entry(side).or_default().push(hua);
+            } else {
+                self.schedule.push((mountain_len - (position - dealt), hua));
+            }
+        }
+    }
+
+    /// Sets aside the flower cards drawn by the player before a card,
+    /// given the number of cards left in mountain.
+    ///
+    /// Returns the flower cards drawn.
+    pub fn draw(&mut self, side: FengType, mountain_len: usize) -> Vec<HuaType> {
+        let (res, rest): (Vec<_>, Vec<_>) =
+            self.schedule.iter().partition(|&&(n, _)| n == mountain_len);
+        self.schedule = rest;
+        let res: Vec<_> = res.into_iter().map(|(_, hua)| hua).collect();
+        self.collected
+            .entry(side)
+            .or_default()
+            .extend(res.iter().copied());
+        res
+    }
+
+    /// Takes the last card of the mountain away, e.g. into the dead wall,
+    /// so each flower card keeps its place among the cards left.
+    pub fn shift(&mut self) {
+        for (n, _) in &mut self.schedule {
+            *n = n.saturating_sub(1);
+        }
+    }
+
+    /// Returns the flower cards set aside by the player.
+    pub fn of(&self, side: FengType) -> &[HuaType] {
+        self.collected.get(&side).map_or(&[], Vec::as_slice)
+    }
+}
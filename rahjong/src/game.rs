@@ -0,0 +1,313 @@
+//! Game sessions of several hands, and their associated methods.
+//!
+//! The core of this module is the [Game] struct,
+//! which wraps the [Cards] of the current hand(局)
+//! and manages the flow from one hand to the next.
+
+use crate::{
+    card_type::{CardType, FengType, Next},
+    cards::Cards,
+    error::PlayError,
+    river_type::RiverType,
+    scoring::{AgariResult, Payment},
+    seating::Seating,
+    standings::{FinalResult, Tobi, UmaOka},
+};
+
+/// The points a player deposits on the table when declaring 立直, known as 立直棒 in Chinese.
+pub const RIICHI_DEPOSIT: i32 = 1000;
+
+/// The `GameLength` type. Represents how many rounds(场) a game lasts.
+#[derive(PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GameLength {
+    /// The game of the 东 round only, known as 东风战 in Chinese.
+    DongFeng,
+    /// The game of the 东 and 南 rounds, known as 半庄 in Chinese.
+    #[default]
+    BanZhuang,
+    /// The game of all the four rounds, known as 全庄 in Chinese.
+    QuanZhuang,
+}
+
+impl GameLength {
+    /// Returns the number of rounds(场) of the game.
+    pub fn rounds(&self) -> u8 {
+        match self {
+            Self::DongFeng => 1,
+            Self::BanZhuang => 2,
+            Self::QuanZhuang => 4,
+        }
+    }
+}
+
+/// The struct containing the states of a game session.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Game {
+    /// The cards of the current hand(局).
+    pub cards: Cards,
+    /// How many rounds the game lasts.
+    pub length: GameLength,
+    /// The number of rounds finished, deciding the end of the game.
+    pub rounds_finished: u8,
+    /// The index of the current hand in the round, from 0 to 3, known as 局 in Chinese.
+    ///
+    /// It does not increase when the dealer repeats(连庄).
+    pub kyoku: u8,
+    /// The counter of hands the dealer repeated or drawn in a row, known as 本场 in Chinese.
+    pub honba: u8,
+    /// The number of riichi sticks(立直棒) on the table, known as 供托 in Chinese.
+    ///
+    /// They are taken by the next winner, and carried to the next hand otherwise.
+    pub riichi_sticks: u8,
+    /// The seats of the players and the first dealer(起家), see [Game::with_seating].
+    pub seating: Seating,
+    /// The points below which a player ends the game, known as 击飞 or tobi in Chinese,
+    /// e.g. `Some(0)` in common riichi rules, or `None` for no such end.
+    pub tobi_below: Option<i32>,
+    /// How the game ended by points below [Game::tobi_below], if it did.
+    pub tobi: Option<Tobi>,
+}
+
+impl Game {
+    /// Creates a new [Game] from the cards of the first hand,
+    /// whose dealer is the 东 player.
+    ///
+    /// The cards should be well initialized, e.g. by [Cards::new].
+    pub fn new(cards: Cards, length: GameLength) -> Self {
+        Self {
+            seating: Seating::fixed(cards.players()),
+            cards,
+            length,
+            rounds_finished: 0,
+            kyoku: 0,
+            honba: 0,
+            riichi_sticks: 0,
+            tobi_below: None,
+            tobi: None,
+        }
+    }
+
+    /// Creates a new [Game] like [Game::new] with the players seated as given,
+    /// whose dealer is the first dealer(起家) of the seating, see [Seating::choose].
+    ///
+    /// The first hand is dealt again for the dealer.
+    pub fn with_seating(mut cards: Cards, length: GameLength, seating: Seating) -> Self {
+        cards.reset_for_next_hand(seating.first_dealer);
+        Self {
+            seating,
+            ..Self::new(cards, length)
+        }
+    }
+
+    /// Returns the final standings of the players by their points, see [FinalResult::rank],
+    /// with the uma(顺位马) and the oka(头名奖) given.
+    ///
+    /// Ties are broken by the seats from the first dealer(起家), see [Seating::first_dealer].
+    pub fn final_result(&self, uma_oka: &UmaOka) -> FinalResult {
+        let mut side = self.seating.first_dealer;
+        let mut scores = Vec::new();
+        for _ in self.cards.players() {
+            scores.push((side, self.cards.score(side)));
+            side = self.cards.next_player(side);
+        }
+        FinalResult {
+            tobi: self.tobi.clone(),
+            ..FinalResult::rank(&scores, &self.seating, self.riichi_sticks, uma_oka)
+        }
+    }
+
+    /// Plays a card by the active player, see [Cards::play].
+    ///
+    /// When the player declares 立直, the deposit is put on the table,
+    /// see [RIICHI_DEPOSIT].
+    ///
+    /// Returns an error telling why if the card can not be played.
+    pub fn play(&mut self, discard: RiverType) -> Result<(), PlayError> {
+        let side = self.cards.active_player;
+        self.cards.play(discard)?;
+        if matches!(discard, RiverType::Drawing(_)) {
+            *self.cards.score_mut(side) -= RIICHI_DEPOSIT;
+            self.riichi_sticks += 1;
+        }
+        Ok(())
+    }
+
+    /// Declares 立直 openly(开立直) by playing the card, see [Cards::declare_open_riichi],
+    /// putting the deposit on the table like [Game::play].
+    ///
+    /// Returns an error telling why if the declaration failed.
+    pub fn declare_open_riichi(&mut self, card: CardType) -> Result<(), PlayError> {
+        let side = self.cards.active_player;
+        self.cards.declare_open_riichi(card)?;
+        *self.cards.score_mut(side) -= RIICHI_DEPOSIT;
+        self.riichi_sticks += 1;
+        Ok(())
+    }
+
+    /// Applies the payment of a completion(和牌),
+    /// adding the bonus of the counters(本场) and the riichi sticks(供托) on the table,
+    /// which are taken by the winner, in rules with them, see [crate::ruleset::Ruleset::has_honba].
+    ///
+    /// The game ends if any player's points fall below [Game::tobi_below], see [Game::tobi].
+    ///
+    /// Returns the payment applied.
+    pub fn settle_win(&mut self, mut payment: Payment) -> Payment {
+        if self.cards.rules().has_honba() {
+            payment.add_honba(self.honba);
+            payment.deposit += self.riichi_sticks as u32 * RIICHI_DEPOSIT as u32;
+            self.riichi_sticks = 0;
+        }
+        #[cfg(feature = "tracing")]
+        tracing::info!(winner = %payment.winner, points = payment.total(), "completion settled");
+        self.cards.apply_payment(&payment);
+        self.check_tobi(Some(&payment));
+        payment
+    }
+
+    /// Ends the game if any player's points are below [Game::tobi_below],
+    /// recording the payment taking them there, see [Game::tobi].
+    fn check_tobi(&mut self, payment: Option<&Payment>) {
+        let Some(below) = self.tobi_below else {
+            return;
+        };
+        if self.tobi.is_some() {
+            return;
+        }
+        let sides: Vec<_> = self
+            .cards
+            .players()
+            .iter()
+            .copied()
+            .filter(|&side| self.cards.score(side) < below)
+            .collect();
+        if !sides.is_empty() {
+            #[cfg(feature = "tracing")]
+            tracing::info!(sides = ?sides, "points below the threshold");
+            self.tobi = Some(Tobi {
+                sides,
+                payment: payment.cloned(),
+            });
+        }
+    }
+
+    /// Returns the score of the player completing with the card, see [Cards::score_win].
+    ///
+    /// The payment does not include the counters(本场) and the riichi sticks(供托) yet,
+    /// which are added by [Game::settle_win].
+    pub fn score_win(&self, side: FengType, winning: CardType) -> Option<AgariResult> {
+        self.cards.score_win(side, winning)
+    }
+
+    /// Checks if the game is over, i.e. all the rounds have been finished,
+    /// or a player's points have fallen below [Game::tobi_below].
+    pub fn is_over(&self) -> bool {
+        self.rounds_finished >= self.length.rounds() || self.tobi.is_some()
+    }
+
+    /// Ends the current hand as the mountain runs out(荒牌流局) and starts the next one,
+    /// see [Cards::is_exhausted] and [Game::settle_draw].
+    ///
+    /// The dealer repeats(连庄) if in drawing hand(听牌), and the counter(本场) increases anyway,
+    /// while the riichi sticks(供托) stay on the table.
+    ///
+    /// Returns the players in drawing hand.
+    /// The game is over if no hand has been started, see [Game::is_over].
+    pub fn exhaustive_draw(&mut self) -> Vec<FengType> {
+        let tenpai = self.settle_draw();
+        self.next_hand(tenpai.contains(&self.cards.dealer), true);
+        tenpai
+    }
+
+    /// Applies the payments of the current hand drawn as the mountain runs out(荒牌流局),
+    /// without starting the next one, see [Game::exhaustive_draw].
+    ///
+    /// The players not in drawing hand(听牌) pay those in drawing hand, see [crate::scoring::noten_payments].
+    /// If any player meets 流局满贯, see [Cards::is_nagashi],
+    /// each of them is paid as a 满贯 by 自摸 instead.
+    /// The payments are given by the rules, see [crate::ruleset::Ruleset::noten_payments] and [crate::ruleset::Ruleset::payment],
+    /// and there is no 流局满贯 in rules without, see [crate::ruleset::Ruleset::has_nagashi].
+    ///
+    /// Returns the players in drawing hand.
+    pub fn settle_draw(&mut self) -> Vec<FengType> {
+        let tenpai = self.cards.tenpai_players();
+        #[cfg(feature = "tracing")]
+        tracing::info!(tenpai = ?tenpai, "hand drawn as the mountain runs out");
+        let rules = self.cards.rules();
+        let nagashi = if rules.has_nagashi() {
+            self.cards.nagashi_players()
+        } else {
+            Vec::new()
+        };
+        let noten = if nagashi.is_empty() {
+            rules.noten_payments(&tenpai)
+        } else {
+            Vec::new()
+        };
+        let payments: Vec<_> = nagashi
+            .into_iter()
+            .map(|side| rules.payment(&self.cards.config, 5, 30, side, self.cards.dealer, None))
+            .collect();
+        for (side, points) in noten {
+            *self.cards.score_mut(side) += points;
+        }
+        for payment in &payments {
+            self.cards.apply_payment(payment);
+        }
+        self.check_tobi(payments.last());
+        tenpai
+    }
+
+    /// Ends the current hand in an abortive draw(途中流局) and starts the next one,
+    /// whatever the [crate::draw_reason::DrawReason] is, see [Cards::check_abortive_draw].
+    ///
+    /// The dealer repeats(连庄) and the counter(本场) increases,
+    /// while the riichi sticks(供托) stay on the table.
+    ///
+    /// Returns false without starting a hand if the game is over, otherwise true.
+    pub fn abort_hand(&mut self) -> bool {
+        self.next_hand(true, true)
+    }
+
+    /// Finishes the current hand and starts the next one.
+    ///
+    /// If the dealer repeats(连庄), e.g. by completing, the dealer keeps the seat.
+    /// Otherwise the next player becomes the dealer,
+    /// and the round ends after four hands, or three in 三麻.
+    ///
+    /// The counter(本场) increases if the dealer repeats or the hand is drawn(流局, `is_draw`),
+    /// and is reset otherwise.
+    ///
+    /// Returns false without starting a hand if the game is over, otherwise true.
+    pub fn next_hand(&mut self, dealer_repeats: bool, is_draw: bool) -> bool {
+        if dealer_repeats || is_draw {
+            self.honba += 1;
+        } else {
+            self.honba = 0;
+        }
+        if !dealer_repeats {
+            self.cards.dealer = self.cards.next_player(self.cards.dealer);
+            self.kyoku += 1;
+            if self.kyoku as usize == self.cards.players().len() {
+                self.kyoku = 0;
+                self.cards.round_wind = self.cards.round_wind.next();
+                self.rounds_finished += 1;
+            }
+        }
+        if self.is_over() {
+            #[cfg(feature = "tracing")]
+            tracing::info!("game over");
+            return false;
+        }
+        #[cfg(feature = "tracing")]
+        tracing::info!(
+            round_wind = %self.cards.round_wind,
+            dealer = %self.cards.dealer,
+            kyoku = self.kyoku,
+            honba = self.honba,
+            "next hand"
+        );
+        self.cards.reset_for_next_hand(self.cards.dealer);
+        true
+    }
+}
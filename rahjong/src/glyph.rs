@@ -0,0 +1,140 @@
+//! Rendering of cards with the Unicode Mahjong Tiles block, for terminals and chat bots.
+//!
+//! Each card is a single glyph from `🀀` to `🀫`, see [card],
+//! and [render] draws a whole [Cards] as a text diagram, one player a block.
+//!
+//! Note that the glyphs are wide in most fonts, and may not be supported in every terminal.
+
+use std::fmt::Write;
+
+use crate::{
+    card_type::{CardType, FengType, HuaType, JianType, ZiType},
+    cards::{Cards, Hand},
+    case_type::CaseType,
+    river_type::RiverCard,
+};
+
+/// The glyph of the back of a card, used for the cards not shown.
+pub const BACK: char = '\u{1F02B}';
+
+/// Returns the glyph of the card.
+pub fn card(card: CardType) -> char {
+    let offset = match card {
+        CardType::Zi(ZiType::Feng(feng)) => feng as u32,
+        CardType::Zi(ZiType::Jian(JianType::Zhong)) => 4,
+        CardType::Zi(ZiType::Jian(JianType::Fa)) => 5,
+        CardType::Zi(ZiType::Jian(JianType::Bai)) => 6,
+        CardType::Wan(n) => 7 + n as u32,
+        CardType::Tiao(n) => 16 + n as u32,
+        CardType::Tong(n) => 25 + n as u32,
+    };
+    char::from_u32(0x1F000 + offset).unwrap_or(BACK)
+}
+
+/// Returns the glyph of the flower card(花牌).
+pub fn hua(hua: HuaType) -> char {
+    let offset = match hua {
+        HuaType::Mei => 0,
+        HuaType::Lan => 1,
+        HuaType::Zhu => 2,
+        HuaType::Ju => 3,
+        HuaType::Chun => 4,
+        HuaType::Xia => 5,
+        HuaType::Qiu => 6,
+        HuaType::Dong => 7,
+    };
+    char::from_u32(0x1F022 + offset).unwrap_or(BACK)
+}
+
+/// Returns the glyphs of the cards in hand, in order.
+pub fn hand(hand: &Hand) -> String {
+    hand.iter()
+        .flat_map(|(&c, &n)| std::iter::repeat_n(card(c), n as usize))
+        .collect()
+}
+
+/// Returns the glyphs of the case(面子).
+///
+/// The cards of an 暗杠 at both ends are shown by their backs.
+pub fn case(case: CaseType) -> String {
+    match case {
+        CaseType::AnGang(c) => [BACK, card(c), card(c), BACK].into_iter().collect(),
+        _ => case.cards().into_iter().map(card).collect(),
+    }
+}
+
+/// Returns the glyphs of the cards in river, in the order of being played,
+/// leaving out those taken by call actions, see [RiverCard::claimed].
+///
+/// A drawing hand indicator is followed by `*`, as in [RiverCard]'s `Display`.
+pub fn river(river: &[RiverCard]) -> String {
+    let mut res = String::new();
+    for r in river.iter().filter(|r| !r.claimed) {
+        res.push(card(r.card()));
+        if r.is_drawing() {
+            res.push('*');
+        }
+    }
+    res
+}
+
+/// Renders the cards as a text diagram, showing everything including the hands of all players.
+///
+/// The first line shows the dora indicators(宝牌指示牌), with the unrevealed ones by their backs,
+/// and the number of cards left in mountain.
+/// Then comes a block for each player, the active one marked by `>`,
+/// where the card just drawn is set apart at the end of the hand,
+/// and the open, river and flower lines are left out if empty.
+pub fn render(cards: &Cards) -> String {
+    let mut res = String::new();
+    let mut dora: String = cards.dora_indicators().into_iter().map(card).collect();
+    for _ in cards.dora_revealed..5 {
+        dora.push(BACK);
+    }
+    let _ = writeln!(res, "dora: {dora}  mountain: {}", cards.card_mountain.len());
+    for side in FengType::ALL {
+        let active = side == cards.active_player;
+        let _ = writeln!(
+            res,
+            "{} {side} {}{}",
+            if active { '>' } else { ' ' },
+            cards.score(side),
+            if cards.is_riichi(side) { " riichi" } else { "" },
+        );
+        let mut held = cards.hand(side).clone();
+        let drawn = cards
+            .drawn
+            .filter(|_| active && cards.card_count(side) == cards.hand_size() + 1);
+        if let Some(c) = drawn {
+            if let Some(n) = held.get_mut(&c) {
+                *n -= 1;
+                if *n == 0 {
+                    held.remove(&c);
+                }
+            }
+        }
+        let mut line = hand(&held);
+        if let Some(c) = drawn {
+            line.push(' ');
+            line.push(card(c));
+        }
+        let _ = writeln!(res, "    hand:  {line}");
+        let open = cards.open(side);
+        if !open.is_empty() {
+            let melds: Vec<_> = open.iter().map(|&c| case(c)).collect();
+            let _ = writeln!(res, "    open:  {}", melds.join(" "));
+        }
+        if !cards.river(side).is_empty() {
+            let _ = writeln!(res, "    river: {}", river(cards.river(side)));
+        }
+        let flowers = cards.flowers.of(side);
+        if !flowers.is_empty() {
+            let _ = writeln!(
+                res,
+                "    hua:   {}",
+                flowers.iter().map(|&h| hua(h)).collect::<String>()
+            );
+        }
+    }
+    res
+}
@@ -0,0 +1,199 @@
+//! Built-in situations and completions of Hong Kong Old Style mahjong(香港麻将), known as HKOS.
+//!
+//! The core of this module is [register], which fills the checkers of a [Cards]
+//! with the common faan(番) of HKOS, and requires 3 faan to complete(三番起和),
+//! see also [Cards::with_hkos_rules].
+//! There is no 立直, see [crate::ruleset::Ruleset::allows_riichi], and no dora.
+//! The faan double the points paid, see [crate::scoring::hkos_payment].
+//!
+//! Limit hands(爆棚) count [HKOS_LIMIT_FAAN],
+//! and 七对子 is not a complete form, so a hand only complete as seven pairs meets no completion.
+//!
+//! Situation checkers see the hand, the river and the open of a player
+//! together with the last card and where it comes from, see [WinContext],
+//! so the faan depending on the winds of the round and the seat, or the flower cards(花牌),
+//! are not included.
+
+use std::collections::HashMap;
+
+use crate::{
+    analysis::{
+        all_cases, complete_hand, winning_concealed_triplets, winning_decompositions,
+        winning_forms, FormType,
+    },
+    card_type::{CardType, SuitType},
+    cards::{Cards, Completion, Hand, Open, River, SituationChecker, SituationFn, WinContext},
+    case_type::CaseType,
+    situations::{self, has_triplet, suits, triplets_and_pairs, FENGS, JIANS},
+};
+
+/// The faan(番) of a limit hand(爆棚), the most a hand counts in HKOS.
+pub const HKOS_LIMIT_FAAN: u16 = 10;
+
+/// Checks if the winning hand is of the complete form.
+fn is_form(
+    hand: &Hand,
+    open: &Open,
+    draw: CardType,
+    context: &WinContext,
+    form: fn(&FormType) -> bool,
+) -> bool {
+    winning_forms(hand, open, draw, context).iter().any(form)
+}
+
+/// 平和: 顺子 only.
+fn ping_he(hand: &Hand, _: &River, open: &Open, draw: CardType, context: &WinContext) -> bool {
+    winning_decompositions(hand, open, draw, context)
+        .iter()
+        .any(|d| {
+            all_cases(d, open)
+                .iter()
+                .all(|c| matches!(c, CaseType::Shun(_)))
+        })
+}
+
+/// 坎坎和: four concealed 刻子 or 暗杠.
+///
+/// A 刻子 completed by 荣和 is not concealed.
+fn kan_kan_he(hand: &Hand, _: &River, open: &Open, draw: CardType, context: &WinContext) -> bool {
+    let (_, tsumo) = complete_hand(hand, open, draw);
+    winning_decompositions(hand, open, draw, context)
+        .iter()
+        .any(|d| winning_concealed_triplets(d, open, draw, tsumo, context) == 4)
+}
+
+/// 九莲宝灯: a concealed 1112345678999 of one suit with any card of the suit.
+fn jiu_lian_bao_deng(hand: &Hand, _: &River, open: &Open, draw: CardType, _: &WinContext) -> bool {
+    let (complete, _) = complete_hand(hand, open, draw);
+    let suit = draw.suit();
+    open.is_empty()
+        && suit != SuitType::Zi
+        && complete.keys().all(|c| c.suit() == suit)
+        && complete.len() == 9
+        && complete
+            .iter()
+            .all(|(c, &n)| n >= if c.is_yao_jiu() { 3 } else { 1 })
+}
+
+/// Returns the situation checkers of HKOS, named after the faan in Chinese.
+pub fn situation_checkers() -> HashMap<&'static str, SituationChecker> {
+    let checkers: [(&'static str, SituationFn); 24] = [
+        ("门前清", situations::men_qian_qing),
+        ("自摸", situations::zi_mo),
+        ("平和", ping_he),
+        ("三元牌白", |h, _, o, d, _| {
+            has_triplet(h, o, d, JIANS[0])
+        }),
+        ("三元牌发", |h, _, o, d, _| {
+            has_triplet(h, o, d, JIANS[1])
+        }),
+        ("三元牌中", |h, _, o, d, _| {
+            has_triplet(h, o, d, JIANS[2])
+        }),
+        ("海底捞月", situations::hai_di_mo_yue),
+        ("杠上开花", situations::ling_shang_kai_hua),
+        ("抢杠", situations::qiang_gang),
+        ("混幺九", situations::hun_lao_tou),
+        ("对对和", situations::dui_dui_he),
+        ("混一色", situations::hun_yi_se),
+        ("小三元", |h, _, o, d, _| {
+            triplets_and_pairs(h, o, d, &JIANS) == (2, 1)
+        }),
+        ("小四喜", |h, _, o, d, _| {
+            triplets_and_pairs(h, o, d, &FENGS) == (3, 1)
+        }),
+        ("清一色", situations::qing_yi_se),
+        ("大三元", |h, _, o, d, _| {
+            triplets_and_pairs(h, o, d, &JIANS).0 == 3
+        }),
+        ("大四喜", |h, _, o, d, _| {
+            triplets_and_pairs(h, o, d, &FENGS).0 == 4
+        }),
+        ("字一色", |h, _, o, d, _| {
+            suits(h, o, d) == [SuitType::Zi]
+        }),
+        ("清幺九", situations::qing_lao_tou),
+        ("坎坎和", kan_kan_he),
+        ("十八罗汉", |_, _, o, _, _| {
+            o.iter()
+                .filter(|c| matches!(c, CaseType::Gang(_) | CaseType::AnGang(_)))
+                .count()
+                == 4
+        }),
+        ("九莲宝灯", jiu_lian_bao_deng),
+        ("十三幺", |h, _, o, d, ctx| {
+            is_form(h, o, d, ctx, |f| matches!(f, FormType::GuoShi(_)))
+        }),
+        ("七对子", |h, _, o, d, ctx| {
+            !is_form(h, o, d, ctx, |f| {
+                matches!(f, FormType::Standard(_) | FormType::GuoShi(_))
+            })
+        }),
+    ];
+    situations::collect_checkers(checkers)
+}
+
+/// Creates a completion requiring the situation of its name,
+/// which is never met by a hand only complete as 七对子.
+fn completion(name: &'static str, forbidden: Vec<&'static str>, fan: u16) -> Completion {
+    Completion::builder(name)
+        .require(name)
+        .forbid_all(forbidden)
+        .forbid("七对子")
+        .fan(fan)
+        .build()
+}
+
+/// The limit hands(爆棚), each counting [HKOS_LIMIT_FAAN].
+const LIMIT_HANDS: [&str; 8] = [
+    "大三元",
+    "大四喜",
+    "字一色",
+    "清幺九",
+    "坎坎和",
+    "十八罗汉",
+    "九莲宝灯",
+    "十三幺",
+];
+
+/// Returns the completions of HKOS, i.e. the faan(番) as the fan(番数).
+///
+/// A limit hand(爆棚) counts [HKOS_LIMIT_FAAN] alone,
+/// so it forbids the other completions and the limit hands listed after it.
+pub fn completions() -> Vec<Completion> {
+    let mut res: Vec<_> = [
+        ("门前清", vec![], 1),
+        ("自摸", vec![], 1),
+        ("平和", vec![], 1),
+        ("三元牌白", vec!["小三元"], 1),
+        ("三元牌发", vec!["小三元"], 1),
+        ("三元牌中", vec!["小三元"], 1),
+        ("海底捞月", vec![], 1),
+        ("杠上开花", vec![], 1),
+        ("抢杠", vec![], 1),
+        ("混幺九", vec![], 1),
+        ("对对和", vec![], 3),
+        ("混一色", vec![], 3),
+        ("小三元", vec![], 5),
+        ("小四喜", vec![], 6),
+        ("清一色", vec![], 7),
+    ]
+    .into_iter()
+    .map(|(name, mut forbidden, fan)| {
+        forbidden.extend(LIMIT_HANDS);
+        completion(name, forbidden, fan)
+    })
+    .collect();
+    for (i, name) in LIMIT_HANDS.into_iter().enumerate() {
+        res.push(completion(name, LIMIT_HANDS[..i].to_vec(), HKOS_LIMIT_FAAN));
+    }
+    res
+}
+
+/// Registers the situations and completions of HKOS into the cards.
+///
+/// 3 faan(番) are required to complete, see [crate::ruleset::HkosRules].
+pub fn register(cards: &mut Cards) {
+    cards.situation_checkers.extend(situation_checkers());
+    cards.completion_checkers.extend(completions());
+}
@@ -1,6 +1,47 @@
 #![doc = include_str!("../../README.md")]
 
+pub mod aka;
+pub mod analysis;
+pub mod bot;
 pub mod card_type;
 pub mod cards;
 pub mod case_type;
+pub mod claim;
+pub mod defense;
+pub mod delta;
+pub mod dice;
+pub mod draw_reason;
+pub mod engine;
+pub mod error;
+pub mod event;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod flower;
+pub mod game;
+pub mod glyph;
+pub mod hkos;
+pub mod mcr;
+#[cfg(feature = "mjai")]
+pub mod mjai;
+pub mod packed_hand;
+#[cfg(feature = "serde")]
+pub mod protocol;
+pub mod riichi;
 pub mod river_type;
+pub mod rule_config;
+pub mod ruleset;
+pub mod scoring;
+pub mod seating;
+pub mod shanten_table;
+pub mod sichuan;
+pub mod simulation;
+pub mod situations;
+pub mod standings;
+pub mod taiwanese;
+#[cfg(feature = "tenhou")]
+pub mod tenhou;
+pub mod tile_counts;
+pub mod view;
+#[cfg(feature = "wasm-bindgen")]
+pub mod wasm;
+pub mod zung_jung;
@@ -0,0 +1,818 @@
+//! Built-in situations and completions of Chinese official mahjong(国标麻将),
+//! known as MCR(Mahjong Competition Rules).
+//!
+//! The core of this module is [register], which fills the checkers of a [Cards]
+//! with the fans(番种) of MCR, and requires 8 fans to complete(八番起和),
+//! see also [Cards::with_mcr_rules].
+//!
+//! The fans implied by another fan(不计) are listed as forbidden situations of the completions,
+//! so a completion is only met if none of the fans implying it is met.
+//!
+//! Situation checkers see the hand, the river and the open of a player
+//! together with the last card and its context, see [crate::cards::WinContext],
+//! which gives the winds of 圈风刻 and 门风刻 and the cards shown for 和绝张.
+//! The knitted forms of 全不靠, 七星不靠 and 组合龙 complete in MCR only,
+//! see [crate::analysis::knitted_forms], where the knitted straight of 组合龙 counts as three 顺子.
+//! 花牌 is not included, since the flower cards are not part of the hand
+//! and do not count toward the 8 fans, see [crate::flower].
+//!
+//! A completion counts once,
+//! so a fan appearing twice in a hand, such as two 幺九刻, is only counted once.
+
+use std::collections::HashMap;
+
+use crate::{
+    analysis::{
+        self, all_cards, all_cases, complete_hand, winning_concealed_triplets,
+        winning_decompositions, winning_forms, winning_waits, FormType, WaitType,
+    },
+    card_type::{CardType, FengType, JianType, SuitType, ZiType},
+    cards::{CardSource, Cards, Completion, Hand, Open, SituationChecker, SituationFn, WinContext},
+    case_type::CaseType,
+    situations::{self, suits},
+};
+
+/// The number suits.
+const NUMBER_SUITS: [SuitType; 3] = [SuitType::Wan, SuitType::Tiao, SuitType::Tong];
+
+/// Returns the number of a number card, from 1 to 9.
+fn num(card: CardType) -> Option<usize> {
+    card.rank().map(|_| card.index() % 9 + 1)
+}
+
+/// Checks if the card is a 风牌.
+fn is_feng(card: CardType) -> bool {
+    matches!(card, CardType::Zi(ZiType::Feng(_)))
+}
+
+/// Checks if the card is a 箭牌.
+fn is_jian(card: CardType) -> bool {
+    matches!(card, CardType::Zi(ZiType::Jian(_)))
+}
+
+/// Returns the starting cards of the 顺子.
+fn shuns(cases: &[CaseType]) -> Vec<CardType> {
+    cases
+        .iter()
+        .filter_map(|&c| match c {
+            CaseType::Shun(start) => Some(start),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns the cards of the 刻子 and 杠子.
+fn kes(cases: &[CaseType]) -> Vec<CardType> {
+    cases
+        .iter()
+        .filter_map(|&c| match c {
+            CaseType::Ke(c) | CaseType::Gang(c) | CaseType::AnGang(c) => Some(c),
+            CaseType::Shun(_) => None,
+        })
+        .collect()
+}
+
+/// Returns the number of cards of the suit and the number.
+fn count(cards: &[CardType], suit: SuitType, n: usize) -> usize {
+    cards
+        .iter()
+        .filter(|&&c| c.suit() == suit && num(c) == Some(n))
+        .count()
+}
+
+/// Checks if there are `len` cards of a number suit,
+/// whose numbers increase by `step` from one to the next.
+///
+/// When `step` is zero, there should be `len` same cards.
+fn same_suit_chain(cards: &[CardType], len: usize, step: usize) -> bool {
+    NUMBER_SUITS.iter().any(|&suit| {
+        (1..=9).any(|x| {
+            (0..len).all(|i| {
+                let need = if step == 0 { len } else { 1 };
+                count(cards, suit, x + i * step) >= need
+            })
+        })
+    })
+}
+
+/// Checks if there are three cards of different number suits,
+/// whose numbers increase by `step` from one to the next.
+fn three_suit_chain(cards: &[CardType], step: usize) -> bool {
+    use SuitType::{Tiao, Tong, Wan};
+    let orders = [
+        [Wan, Tiao, Tong],
+        [Wan, Tong, Tiao],
+        [Tiao, Wan, Tong],
+        [Tiao, Tong, Wan],
+        [Tong, Wan, Tiao],
+        [Tong, Tiao, Wan],
+    ];
+    orders.iter().any(|order| {
+        (1..=9).any(|x| {
+            order
+                .iter()
+                .enumerate()
+                .all(|(i, &suit)| count(cards, suit, x + i * step) >= 1)
+        })
+    })
+}
+
+/// Checks if there are two cards of different number suits with the same number.
+fn two_suit_pair(cards: &[CardType]) -> bool {
+    cards.iter().enumerate().any(|(i, &a)| {
+        cards[i + 1..]
+            .iter()
+            .any(|&b| a.suit() != b.suit() && num(a).is_some() && num(a) == num(b))
+    })
+}
+
+/// Checks if any standard decomposition of the winning hand meets the predicate,
+/// which is given the pair and all the cases including those in open.
+fn any_decomposition(
+    hand: &Hand,
+    open: &Open,
+    draw: CardType,
+    context: &WinContext,
+    predicate: impl Fn(CardType, &[CaseType]) -> bool,
+) -> bool {
+    winning_decompositions(hand, open, draw, context)
+        .iter()
+        .any(|d| predicate(d.pair, &all_cases(d, open)))
+}
+
+/// Checks if all the cards of the winning hand meet the predicate.
+fn all_of(hand: &Hand, open: &Open, draw: CardType, predicate: impl Fn(CardType) -> bool) -> bool {
+    all_cards(hand, open, draw).into_iter().all(predicate)
+}
+
+/// Returns the number of the 刻子 and 杠子 of the cards meeting the predicate,
+/// which does not depend on the decomposition for honors.
+fn count_kes(
+    hand: &Hand,
+    open: &Open,
+    draw: CardType,
+    context: &WinContext,
+    predicate: fn(CardType) -> bool,
+) -> usize {
+    winning_decompositions(hand, open, draw, context)
+        .iter()
+        .map(|d| {
+            kes(&all_cases(d, open))
+                .into_iter()
+                .filter(|&c| predicate(c))
+                .count()
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+/// Checks if any decomposition has a 刻子 or 杠子 of the wind, if any.
+fn has_feng_ke(
+    hand: &Hand,
+    open: &Open,
+    draw: CardType,
+    context: &WinContext,
+    feng: Option<&FengType>,
+) -> bool {
+    feng.is_some_and(|&feng| {
+        any_decomposition(hand, open, draw, context, |_, cases| {
+            kes(cases).contains(&CardType::Zi(ZiType::Feng(feng)))
+        })
+    })
+}
+
+/// Checks if the pair of any decomposition meets the predicate.
+fn pair_is(
+    hand: &Hand,
+    open: &Open,
+    draw: CardType,
+    context: &WinContext,
+    predicate: fn(CardType) -> bool,
+) -> bool {
+    any_decomposition(hand, open, draw, context, |pair, _| predicate(pair))
+}
+
+/// Returns the number of the cases in open meeting the predicate.
+fn count_open(open: &Open, predicate: fn(&CaseType) -> bool) -> usize {
+    open.iter().filter(|c| predicate(c)).count()
+}
+
+/// Checks if any decomposition has the given number of concealed 刻子(暗刻).
+fn an_ke(hand: &Hand, open: &Open, draw: CardType, context: &WinContext, n: usize) -> bool {
+    let (_, tsumo) = complete_hand(hand, open, draw);
+    winning_decompositions(hand, open, draw, context)
+        .iter()
+        .any(|d| winning_concealed_triplets(d, open, draw, tsumo, context) == n)
+}
+
+/// Returns the hand before the winning card.
+fn hand_before(hand: &Hand, open: &Open, draw: CardType) -> Hand {
+    let (mut res, _) = complete_hand(hand, open, draw);
+    if let Some(n) = res.get_mut(&draw) {
+        *n -= 1;
+        if *n == 0 {
+            res.remove(&draw);
+        }
+    }
+    res
+}
+
+/// Checks if the winning card is the only card completing the hand,
+/// and it can be taken as completing the wait shape.
+fn only_wait(
+    hand: &Hand,
+    open: &Open,
+    draw: CardType,
+    context: &WinContext,
+    wait: WaitType,
+) -> bool {
+    analysis::waits(&hand_before(hand, open, draw), open) == [draw]
+        && winning_decompositions(hand, open, draw, context)
+            .iter()
+            .any(|d| winning_waits(d, draw, context).contains(&wait))
+}
+
+/// Checks if the winning hand is of the complete form.
+fn is_form(
+    hand: &Hand,
+    open: &Open,
+    draw: CardType,
+    context: &WinContext,
+    form: fn(&FormType) -> bool,
+) -> bool {
+    winning_forms(hand, open, draw, context).iter().any(form)
+}
+
+/// Checks if the player has not done any call action, 暗杠 excluded.
+fn is_concealed(open: &Open) -> bool {
+    !open.iter().any(CaseType::is_open)
+}
+
+/// Returns the situation checkers of MCR, named after the fans in Chinese.
+pub fn situation_checkers() -> HashMap<&'static str, SituationChecker> {
+    let checkers: [(&'static str, SituationFn); 80] = [
+        // 88 番
+        ("大四喜", |h, _, o, d, ctx| {
+            count_kes(h, o, d, ctx, is_feng) == 4
+        }),
+        ("大三元", |h, _, o, d, ctx| {
+            count_kes(h, o, d, ctx, is_jian) == 3
+        }),
+        ("绿一色", |h, _, o, d, _| {
+            all_of(h, o, d, |c| {
+                c == CardType::Zi(ZiType::Jian(JianType::Fa))
+                    || c.suit() == SuitType::Tiao && matches!(num(c), Some(2 | 3 | 4 | 6 | 8))
+            })
+        }),
+        ("九莲宝灯", |h, _, o, d, _| {
+            let before = hand_before(h, o, d);
+            o.is_empty()
+                && NUMBER_SUITS.iter().any(|&suit| {
+                    [3, 1, 1, 1, 1, 1, 1, 1, 3]
+                        .iter()
+                        .enumerate()
+                        .all(|(i, &n)| {
+                            let card = before
+                                .iter()
+                                .find(|(c, _)| c.suit() == suit && num(**c) == Some(i + 1));
+                            card.map_or(0, |(_, &k)| k) == n
+                        })
+                        && before.values().sum::<u8>() == 13
+                        && d.suit() == suit
+                })
+        }),
+        ("四杠", |_, _, o, _, _| {
+            count_open(o, |c| matches!(c, CaseType::Gang(_) | CaseType::AnGang(_))) == 4
+        }),
+        ("连七对", |h, _, o, d, ctx| {
+            is_form(h, o, d, ctx, |f| match f {
+                FormType::QiDui(pairs) => {
+                    pairs.iter().all(|&c| c.suit() == pairs[0].suit())
+                        && pairs
+                            .windows(2)
+                            .all(|w| num(w[0]).is_some() && num(w[1]) == num(w[0]).map(|n| n + 1))
+                }
+                _ => false,
+            })
+        }),
+        ("十三幺", |h, _, o, d, ctx| {
+            is_form(h, o, d, ctx, |f| matches!(f, FormType::GuoShi(_)))
+        }),
+        // 64 番
+        ("清幺九", |h, _, o, d, _| {
+            all_of(h, o, d, |c| matches!(num(c), Some(1 | 9)))
+        }),
+        ("小四喜", |h, _, o, d, ctx| {
+            count_kes(h, o, d, ctx, is_feng) == 3 && pair_is(h, o, d, ctx, is_feng)
+        }),
+        ("小三元", |h, _, o, d, ctx| {
+            count_kes(h, o, d, ctx, is_jian) == 2 && pair_is(h, o, d, ctx, is_jian)
+        }),
+        ("字一色", |h, _, o, d, _| {
+            all_of(h, o, d, |c| num(c).is_none())
+        }),
+        ("四暗刻", |h, _, o, d, ctx| an_ke(h, o, d, ctx, 4)),
+        ("一色双龙会", |h, _, o, d, ctx| {
+            any_decomposition(h, o, d, ctx, |pair, cases| {
+                let shuns = shuns(cases);
+                let suit = pair.suit();
+                num(pair) == Some(5)
+                    && shuns.len() == 4
+                    && count(&shuns, suit, 1) == 2
+                    && count(&shuns, suit, 7) == 2
+            })
+        }),
+        // 48 番
+        ("一色四同顺", |h, _, o, d, ctx| {
+            any_decomposition(h, o, d, ctx, |_, cases| {
+                same_suit_chain(&shuns(cases), 4, 0)
+            })
+        }),
+        ("一色四节高", |h, _, o, d, ctx| {
+            any_decomposition(h, o, d, ctx, |_, cases| same_suit_chain(&kes(cases), 4, 1))
+        }),
+        // 32 番
+        ("一色四步高", |h, _, o, d, ctx| {
+            any_decomposition(h, o, d, ctx, |_, cases| {
+                let shuns = shuns(cases);
+                same_suit_chain(&shuns, 4, 1) || same_suit_chain(&shuns, 4, 2)
+            })
+        }),
+        ("三杠", |_, _, o, _, _| {
+            count_open(o, |c| matches!(c, CaseType::Gang(_) | CaseType::AnGang(_))) == 3
+        }),
+        ("混幺九", |h, _, o, d, _| {
+            all_of(h, o, d, |c| c.is_yao_jiu())
+        }),
+        // 24 番
+        ("七对", |h, _, o, d, ctx| {
+            is_form(h, o, d, ctx, |f| matches!(f, FormType::QiDui(_)))
+        }),
+        ("全双刻", |h, _, o, d, ctx| {
+            any_decomposition(h, o, d, ctx, |pair, cases| {
+                kes(cases).len() == 4
+                    && kes(cases)
+                        .into_iter()
+                        .chain([pair])
+                        .all(|c| num(c).is_some_and(|n| n % 2 == 0))
+            })
+        }),
+        ("清一色", |h, _, o, d, _| {
+            let suits = suits(h, o, d);
+            suits.len() == 1 && suits[0] != SuitType::Zi
+        }),
+        ("一色三同顺", |h, _, o, d, ctx| {
+            any_decomposition(h, o, d, ctx, |_, cases| {
+                same_suit_chain(&shuns(cases), 3, 0)
+            })
+        }),
+        ("一色三节高", |h, _, o, d, ctx| {
+            any_decomposition(h, o, d, ctx, |_, cases| same_suit_chain(&kes(cases), 3, 1))
+        }),
+        ("全大", |h, _, o, d, _| {
+            all_of(h, o, d, |c| num(c).is_some_and(|n| n >= 7))
+        }),
+        ("全中", |h, _, o, d, _| {
+            all_of(h, o, d, |c| num(c).is_some_and(|n| (4..=6).contains(&n)))
+        }),
+        ("全小", |h, _, o, d, _| {
+            all_of(h, o, d, |c| num(c).is_some_and(|n| n <= 3))
+        }),
+        ("七星不靠", |h, _, o, d, ctx| {
+            is_form(h, o, d, ctx, |f| match f {
+                FormType::QuanBuKao(cards) => {
+                    cards.iter().filter(|&&c| num(c).is_none()).count() == 7
+                }
+                _ => false,
+            })
+        }),
+        // 16 番
+        ("清龙", |h, _, o, d, ctx| {
+            any_decomposition(h, o, d, ctx, |_, cases| {
+                same_suit_chain(&shuns(cases), 3, 3)
+            })
+        }),
+        ("三色双龙会", |h, _, o, d, ctx| {
+            any_decomposition(h, o, d, ctx, |pair, cases| {
+                let shuns = shuns(cases);
+                num(pair) == Some(5)
+                    && NUMBER_SUITS
+                        .iter()
+                        .filter(|&&suit| suit != pair.suit())
+                        .all(|&suit| count(&shuns, suit, 1) == 1 && count(&shuns, suit, 7) == 1)
+            })
+        }),
+        ("一色三步高", |h, _, o, d, ctx| {
+            any_decomposition(h, o, d, ctx, |_, cases| {
+                let shuns = shuns(cases);
+                same_suit_chain(&shuns, 3, 1) || same_suit_chain(&shuns, 3, 2)
+            })
+        }),
+        ("全带五", |h, _, o, d, ctx| {
+            any_decomposition(h, o, d, ctx, |pair, cases| {
+                num(pair) == Some(5)
+                    && cases.len() == 4
+                    && cases
+                        .iter()
+                        .all(|c| c.cards().iter().any(|&c| num(c) == Some(5)))
+            })
+        }),
+        ("三同刻", |h, _, o, d, ctx| {
+            any_decomposition(h, o, d, ctx, |_, cases| three_suit_chain(&kes(cases), 0))
+        }),
+        ("三暗刻", |h, _, o, d, ctx| an_ke(h, o, d, ctx, 3)),
+        // 12 番
+        ("大于五", |h, _, o, d, _| {
+            all_of(h, o, d, |c| num(c).is_some_and(|n| n > 5))
+        }),
+        ("小于五", |h, _, o, d, _| {
+            all_of(h, o, d, |c| num(c).is_some_and(|n| n < 5))
+        }),
+        ("三风刻", |h, _, o, d, ctx| {
+            count_kes(h, o, d, ctx, is_feng) == 3
+        }),
+        ("全不靠", |h, _, o, d, ctx| {
+            is_form(h, o, d, ctx, |f| matches!(f, FormType::QuanBuKao(_)))
+        }),
+        ("组合龙", |h, _, o, d, ctx| {
+            is_form(h, o, d, ctx, |f| match f {
+                FormType::ZuHeLong(_) => true,
+                FormType::QuanBuKao(cards) => {
+                    cards.iter().filter(|&&c| num(c).is_some()).count() == 9
+                }
+                _ => false,
+            })
+        }),
+        // 8 番
+        ("花龙", |h, _, o, d, ctx| {
+            any_decomposition(h, o, d, ctx, |_, cases| three_suit_chain(&shuns(cases), 3))
+        }),
+        ("推不倒", |h, _, o, d, _| {
+            all_of(h, o, d, |c| match c.suit() {
+                SuitType::Tong => matches!(num(c), Some(1 | 2 | 3 | 4 | 5 | 8 | 9)),
+                SuitType::Tiao => matches!(num(c), Some(2 | 4 | 5 | 6 | 8 | 9)),
+                SuitType::Zi => c == CardType::Zi(ZiType::Jian(JianType::Bai)),
+                SuitType::Wan => false,
+            })
+        }),
+        ("三色三同顺", |h, _, o, d, ctx| {
+            any_decomposition(h, o, d, ctx, |_, cases| three_suit_chain(&shuns(cases), 0))
+        }),
+        ("三色三节高", |h, _, o, d, ctx| {
+            any_decomposition(h, o, d, ctx, |_, cases| three_suit_chain(&kes(cases), 1))
+        }),
+        ("妙手回春", |_, _, _, _, c| {
+            c.source == CardSource::Mountain && c.is_last
+        }),
+        ("海底捞月", |_, _, _, _, c| {
+            c.source == CardSource::River && c.is_last
+        }),
+        ("杠上开花", |_, _, _, _, c| {
+            c.source == CardSource::Replacement
+        }),
+        ("抢杠和", |_, _, _, _, c| c.source == CardSource::Robbed),
+        ("无番和", |h, _, o, d, ctx| {
+            !winning_forms(h, o, d, ctx).is_empty()
+        }),
+        // 6 番
+        ("碰碰和", |h, _, o, d, ctx| {
+            any_decomposition(h, o, d, ctx, |_, cases| kes(cases).len() == 4)
+        }),
+        ("混一色", |h, _, o, d, _| {
+            let suits = suits(h, o, d);
+            suits.len() == 2 && suits.contains(&SuitType::Zi)
+        }),
+        ("三色三步高", |h, _, o, d, ctx| {
+            any_decomposition(h, o, d, ctx, |_, cases| three_suit_chain(&shuns(cases), 1))
+        }),
+        ("五门齐", |h, _, o, d, _| {
+            let cards = all_cards(h, o, d);
+            suits(h, o, d).len() == 4
+                && cards.iter().any(|&c| is_feng(c))
+                && cards.iter().any(|&c| is_jian(c))
+        }),
+        ("全求人", |h, _, o, d, _| {
+            o.len() == 4 && o.iter().all(CaseType::is_open) && !complete_hand(h, o, d).1
+        }),
+        ("双暗杠", |_, _, o, _, _| {
+            count_open(o, |c| matches!(c, CaseType::AnGang(_))) == 2
+        }),
+        ("双箭刻", |h, _, o, d, ctx| {
+            count_kes(h, o, d, ctx, is_jian) == 2
+        }),
+        // 4 番
+        ("全带幺", |h, _, o, d, ctx| {
+            any_decomposition(h, o, d, ctx, |pair, cases| {
+                pair.is_yao_jiu()
+                    && cases.len() == 4
+                    && cases
+                        .iter()
+                        .all(|c| c.cards().iter().any(CardType::is_yao_jiu))
+            })
+        }),
+        ("不求人", |h, _, o, d, _| {
+            is_concealed(o) && complete_hand(h, o, d).1
+        }),
+        ("双明杠", |_, _, o, _, _| {
+            count_open(o, |c| matches!(c, CaseType::Gang(_))) == 2
+        }),
+        ("和绝张", |_, _, _, d, ctx| ctx.shown[d] == 3),
+        // 2 番
+        ("圈风刻", |h, _, o, d, ctx| {
+            has_feng_ke(h, o, d, ctx, ctx.winds.first())
+        }),
+        ("门风刻", |h, _, o, d, ctx| {
+            has_feng_ke(h, o, d, ctx, ctx.winds.get(1))
+        }),
+        ("箭刻", |h, _, o, d, ctx| {
+            count_kes(h, o, d, ctx, is_jian) == 1
+        }),
+        ("门前清", |h, _, o, d, _| {
+            is_concealed(o) && !complete_hand(h, o, d).1
+        }),
+        // The knitted straight of 组合龙 counts as 顺子, so all the other cases should be.
+        ("平和", |h, _, o, d, ctx| {
+            any_decomposition(h, o, d, ctx, |pair, cases| {
+                num(pair).is_some() && shuns(cases).len() == cases.len()
+            })
+        }),
+        ("四归一", |h, _, o, d, _| {
+            let cards = all_cards(h, o, d);
+            cards.iter().any(|&c| {
+                cards.iter().filter(|&&x| x == c).count() == 4
+                    && !o.contains(&CaseType::Gang(c))
+                    && !o.contains(&CaseType::AnGang(c))
+            })
+        }),
+        ("双同刻", |h, _, o, d, ctx| {
+            any_decomposition(h, o, d, ctx, |_, cases| two_suit_pair(&kes(cases)))
+        }),
+        ("双暗刻", |h, _, o, d, ctx| an_ke(h, o, d, ctx, 2)),
+        ("暗杠", |_, _, o, _, _| {
+            count_open(o, |c| matches!(c, CaseType::AnGang(_))) == 1
+        }),
+        ("断幺", |h, _, o, d, _| all_of(h, o, d, |c| c.is_simple())),
+        // 1 番
+        ("一般高", |h, _, o, d, ctx| {
+            any_decomposition(h, o, d, ctx, |_, cases| {
+                same_suit_chain(&shuns(cases), 2, 0)
+            })
+        }),
+        ("喜相逢", |h, _, o, d, ctx| {
+            any_decomposition(h, o, d, ctx, |_, cases| two_suit_pair(&shuns(cases)))
+        }),
+        ("连六", |h, _, o, d, ctx| {
+            any_decomposition(h, o, d, ctx, |_, cases| {
+                same_suit_chain(&shuns(cases), 2, 3)
+            })
+        }),
+        ("老少副", |h, _, o, d, ctx| {
+            any_decomposition(h, o, d, ctx, |_, cases| {
+                same_suit_chain(&shuns(cases), 2, 6)
+            })
+        }),
+        // The triplets of 箭刻, 圈风刻 and 门风刻 do not count as 幺九刻.
+        ("幺九刻", |h, _, o, d, ctx| {
+            any_decomposition(h, o, d, ctx, |_, cases| {
+                kes(cases).into_iter().any(|c| {
+                    c.is_yao_jiu()
+                        && !is_jian(c)
+                        && !ctx
+                            .winds
+                            .iter()
+                            .take(2)
+                            .any(|&feng| c == CardType::Zi(ZiType::Feng(feng)))
+                })
+            })
+        }),
+        ("明杠", |_, _, o, _, _| {
+            count_open(o, |c| matches!(c, CaseType::Gang(_))) == 1
+        }),
+        ("缺一门", |h, _, o, d, _| {
+            suits(h, o, d)
+                .iter()
+                .filter(|s| NUMBER_SUITS.contains(s))
+                .count()
+                == 2
+        }),
+        ("无字", |h, _, o, d, _| {
+            all_of(h, o, d, |c| num(c).is_some())
+        }),
+        ("边张", |h, _, o, d, ctx| {
+            only_wait(h, o, d, ctx, WaitType::BianZhang)
+        }),
+        ("坎张", |h, _, o, d, ctx| {
+            only_wait(h, o, d, ctx, WaitType::QianZhang)
+        }),
+        ("单钓将", |h, _, o, d, ctx| {
+            only_wait(h, o, d, ctx, WaitType::DanQi)
+        }),
+        ("自摸", |h, _, o, d, _| complete_hand(h, o, d).1),
+    ];
+    situations::collect_checkers(checkers)
+}
+
+/// Returns the completions of MCR, i.e. the fans(番种) and their fan(番数).
+///
+/// Each completion requires the situation of the same name,
+/// and forbids the situations of the fans implying it(不计).
+/// 无番和 forbids all the other fans,
+/// and 自摸 counts with the forms implying 不求人, which do not count it.
+pub fn completions() -> Vec<Completion> {
+    let fans: [(&'static str, u16, &[&'static str]); 80] = [
+        // 88 番
+        ("大四喜", 88, &[]),
+        ("大三元", 88, &[]),
+        ("绿一色", 88, &[]),
+        ("九莲宝灯", 88, &[]),
+        ("四杠", 88, &[]),
+        ("连七对", 88, &[]),
+        ("十三幺", 88, &[]),
+        // 64 番
+        ("清幺九", 64, &[]),
+        ("小四喜", 64, &[]),
+        ("小三元", 64, &[]),
+        ("字一色", 64, &["大四喜", "大三元"]),
+        ("四暗刻", 64, &[]),
+        ("一色双龙会", 64, &[]),
+        // 48 番
+        ("一色四同顺", 48, &[]),
+        ("一色四节高", 48, &[]),
+        // 32 番
+        ("一色四步高", 32, &[]),
+        ("三杠", 32, &[]),
+        ("混幺九", 32, &["十三幺", "清幺九", "字一色"]),
+        // 24 番
+        ("七对", 24, &["连七对"]),
+        ("全双刻", 24, &[]),
+        ("清一色", 24, &["九莲宝灯", "连七对", "一色双龙会"]),
+        ("一色三同顺", 24, &["一色四同顺"]),
+        ("一色三节高", 24, &["一色四节高"]),
+        ("全大", 24, &[]),
+        ("全中", 24, &[]),
+        ("全小", 24, &[]),
+        ("七星不靠", 24, &[]),
+        // 16 番
+        ("清龙", 16, &[]),
+        ("三色双龙会", 16, &[]),
+        ("一色三步高", 16, &["一色四步高"]),
+        ("全带五", 16, &[]),
+        ("三同刻", 16, &[]),
+        ("三暗刻", 16, &[]),
+        // 12 番
+        ("大于五", 12, &["全大"]),
+        ("小于五", 12, &["全小"]),
+        ("三风刻", 12, &["小四喜"]),
+        ("全不靠", 12, &["七星不靠"]),
+        ("组合龙", 12, &[]),
+        // 8 番
+        ("花龙", 8, &[]),
+        ("推不倒", 8, &[]),
+        ("三色三同顺", 8, &[]),
+        ("三色三节高", 8, &[]),
+        ("妙手回春", 8, &[]),
+        ("海底捞月", 8, &[]),
+        ("杠上开花", 8, &[]),
+        ("抢杠和", 8, &[]),
+        ("无番和", 8, &[]),
+        // 6 番
+        (
+            "碰碰和",
+            6,
+            &[
+                "大四喜",
+                "四杠",
+                "清幺九",
+                "字一色",
+                "四暗刻",
+                "一色四节高",
+                "混幺九",
+                "全双刻",
+            ],
+        ),
+        ("混一色", 6, &[]),
+        ("三色三步高", 6, &[]),
+        ("五门齐", 6, &["十三幺", "全不靠", "七星不靠"]),
+        ("全求人", 6, &[]),
+        ("双暗杠", 6, &["三杠", "四杠"]),
+        ("双箭刻", 6, &["小三元"]),
+        // 4 番
+        ("全带幺", 4, &["清幺九", "字一色", "混幺九"]),
+        (
+            "不求人",
+            4,
+            &["七对", "十三幺", "连七对", "全不靠", "七星不靠"],
+        ),
+        ("双明杠", 4, &["三杠", "四杠"]),
+        ("和绝张", 4, &["抢杠和"]),
+        // 2 番
+        ("圈风刻", 2, &["大四喜"]),
+        ("门风刻", 2, &["大四喜"]),
+        ("箭刻", 2, &[]),
+        (
+            "门前清",
+            2,
+            &[
+                "九莲宝灯",
+                "四暗刻",
+                "连七对",
+                "十三幺",
+                "七对",
+                "全不靠",
+                "七星不靠",
+            ],
+        ),
+        ("平和", 2, &["一色双龙会", "三色双龙会"]),
+        ("四归一", 2, &["一色四同顺"]),
+        ("双同刻", 2, &["三同刻", "清幺九"]),
+        ("双暗刻", 2, &[]),
+        ("暗杠", 2, &["三杠", "四杠"]),
+        ("断幺", 2, &["全双刻", "全中", "全带五"]),
+        // 1 番
+        ("一般高", 1, &["一色双龙会", "一色四同顺", "一色三同顺"]),
+        ("喜相逢", 1, &["三色双龙会", "三色三同顺"]),
+        ("连六", 1, &["清龙"]),
+        ("老少副", 1, &["一色双龙会", "三色双龙会", "清龙"]),
+        (
+            "幺九刻",
+            1,
+            &[
+                "大四喜",
+                "九莲宝灯",
+                "清幺九",
+                "小四喜",
+                "字一色",
+                "混幺九",
+                "三风刻",
+            ],
+        ),
+        ("明杠", 1, &["三杠", "四杠"]),
+        ("缺一门", 1, &["推不倒"]),
+        (
+            "无字",
+            1,
+            &[
+                "九莲宝灯",
+                "连七对",
+                "清幺九",
+                "一色双龙会",
+                "清一色",
+                "全大",
+                "全中",
+                "全小",
+                "三色双龙会",
+                "大于五",
+                "小于五",
+                "平和",
+            ],
+        ),
+        ("边张", 1, &[]),
+        ("坎张", 1, &[]),
+        (
+            "单钓将",
+            1,
+            &[
+                "四杠",
+                "连七对",
+                "十三幺",
+                "七对",
+                "全求人",
+                "全不靠",
+                "七星不靠",
+            ],
+        ),
+        ("自摸", 1, &["不求人", "妙手回春", "杠上开花"]),
+    ];
+    let names = fans.map(|(name, _, _)| name);
+    let mut res: Vec<_> = fans
+        .into_iter()
+        .map(|(name, fan, forbidden)| {
+            let builder = Completion::builder(name).require(name);
+            let builder = if name == "无番和" {
+                builder.forbid_all(names.into_iter().filter(|&n| n != name))
+            } else {
+                builder.forbid_all(forbidden.iter().copied())
+            };
+            builder.fan(fan).build()
+        })
+        .collect();
+    // 七对 includes 连七对, and 全不靠 includes 七星不靠.
+    for form in ["七对", "十三幺", "全不靠"] {
+        res.push(
+            Completion::builder("自摸")
+                .require_all(["自摸", "不求人", form])
+                .forbid_all(["妙手回春", "杠上开花"])
+                .fan(1)
+                .build(),
+        );
+    }
+    res
+}
+
+/// Registers the situations and completions of MCR into the cards.
+///
+/// 8 fans are required to complete, see [crate::ruleset::McrRules].
+pub fn register(cards: &mut Cards) {
+    cards.situation_checkers.extend(situation_checkers());
+    cards.completion_checkers.extend(completions());
+}
@@ -0,0 +1,459 @@
+//! The mjai protocol for riichi mahjong bots, behind the `mjai` feature.
+//!
+//! mjai is a protocol of JSON messages, one per line, used by bots such as Mortal and akochan.
+//! The core of this module is the [MjaiSession] struct,
+//! an adapter over an [Engine] which turns the actions applied into [Message]s,
+//! and the messages of bots back into actions, see [MjaiSession::respond].
+//!
+//! A card is written as a 牌 of mjai, e.g. `3m`, `5pr` for a red five,
+//! and `E`, `S`, `W`, `N`, `P`, `F` and `C` for 东, 南, 西, 北, 白, 发 and 中, see [pai].
+//! Players are the actors `0` to `3` in the order of players, i.e. 东 is `0`.
+
+use std::collections::BTreeSet;
+
+use crate::{
+    card_type::{CardType, FengType, JianType, ZiType},
+    case_type::CaseType,
+    engine::{Action, Engine, HandEnd},
+};
+
+/// The `Message` type. Represents a message of the mjai protocol.
+///
+/// Only the fields used by this crate are included, and the others are ignored when parsing.
+#[derive(Debug, PartialEq, Eq, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Message {
+    /// A game starts, telling the player its own actor.
+    StartGame {
+        /// The actor of the player receiving the message.
+        id: usize,
+        /// The names of players.
+        #[serde(default)]
+        names: Vec<String>,
+    },
+    /// A hand(局) starts.
+    StartKyoku {
+        /// The wind of the round(场风).
+        bakaze: String,
+        /// The hand in the round, from 1 to 4.
+        kyoku: u8,
+        /// The counter of hands the dealer repeated or drawn in a row(本场).
+        honba: u8,
+        /// The number of riichi sticks(立直棒) on the table.
+        kyotaku: u8,
+        /// The dealer(庄家).
+        oya: usize,
+        /// The first dora indicator(宝牌指示牌).
+        dora_marker: String,
+        /// The points of players.
+        scores: [i32; 4],
+        /// The hands of players, where the cards of the others are `?`.
+        tehais: Vec<Vec<String>>,
+    },
+    /// A player draws a card, which is `?` for the others.
+    Tsumo {
+        /// The player drawing.
+        actor: usize,
+        /// The card drawn.
+        pai: String,
+    },
+    /// A player plays a card.
+    Dahai {
+        /// The player playing.
+        actor: usize,
+        /// The card played.
+        pai: String,
+        /// Whether the card played is the one just drawn(摸切).
+        #[serde(default)]
+        tsumogiri: bool,
+    },
+    /// A player declares 立直, followed by the card played.
+    Reach {
+        /// The player declaring.
+        actor: usize,
+    },
+    /// The 立直 is accepted as no player completes with the card played.
+    ReachAccepted {
+        /// The player declaring.
+        actor: usize,
+    },
+    /// A player calls 吃.
+    Chi {
+        /// The player calling.
+        actor: usize,
+        /// The player who played the card.
+        target: usize,
+        /// The card called.
+        pai: String,
+        /// The cards from hand.
+        consumed: Vec<String>,
+    },
+    /// A player calls 碰.
+    Pon {
+        /// The player calling.
+        actor: usize,
+        /// The player who played the card.
+        target: usize,
+        /// The card called.
+        pai: String,
+        /// The cards from hand.
+        consumed: Vec<String>,
+    },
+    /// A player calls 大明杠.
+    Daiminkan {
+        /// The player calling.
+        actor: usize,
+        /// The player who played the card.
+        target: usize,
+        /// The card called.
+        pai: String,
+        /// The cards from hand.
+        consumed: Vec<String>,
+    },
+    /// A player does an 加杠.
+    Kakan {
+        /// The player calling.
+        actor: usize,
+        /// The card added.
+        pai: String,
+        /// The cards of the 刻子 in open.
+        consumed: Vec<String>,
+    },
+    /// A player does an 暗杠.
+    Ankan {
+        /// The player calling.
+        actor: usize,
+        /// The cards from hand.
+        consumed: Vec<String>,
+    },
+    /// Another dora indicator(宝牌指示牌) is revealed.
+    Dora {
+        /// The indicator revealed.
+        dora_marker: String,
+    },
+    /// A player completes(和牌).
+    Hora {
+        /// The player completing.
+        actor: usize,
+        /// The player who played the card, or the actor itself for 自摸.
+        target: usize,
+        /// The winning card.
+        pai: String,
+    },
+    /// The hand is drawn(流局).
+    Ryukyoku,
+    /// The hand ends.
+    EndKyoku,
+    /// The game ends.
+    EndGame,
+    /// A player passes, as a response only.
+    None,
+}
+
+/// Returns the 牌 of mjai of the card, see [the module level documentation](self).
+pub fn pai(card: CardType, red: bool) -> String {
+    match card {
+        CardType::Zi(zi) => match zi {
+            ZiType::Feng(FengType::Dong) => "E",
+            ZiType::Feng(FengType::Nan) => "S",
+            ZiType::Feng(FengType::Xi) => "W",
+            ZiType::Feng(FengType::Bei) => "N",
+            ZiType::Jian(JianType::Bai) => "P",
+            ZiType::Jian(JianType::Fa) => "F",
+            ZiType::Jian(JianType::Zhong) => "C",
+        }
+        .to_string(),
+        _ if red => format!("{card}r"),
+        _ => card.to_string(),
+    }
+}
+
+/// Returns the card of the 牌 of mjai, the reverse of [pai],
+/// or `None` if it is not a card, e.g. `?`.
+pub fn card_of_pai(pai: &str) -> Option<CardType> {
+    let zi = match pai {
+        "E" => ZiType::Feng(FengType::Dong),
+        "S" => ZiType::Feng(FengType::Nan),
+        "W" => ZiType::Feng(FengType::Xi),
+        "N" => ZiType::Feng(FengType::Bei),
+        "P" => ZiType::Jian(JianType::Bai),
+        "F" => ZiType::Jian(JianType::Fa),
+        "C" => ZiType::Jian(JianType::Zhong),
+        _ => return pai.strip_suffix('r').unwrap_or(pai).parse().ok(),
+    };
+    Some(CardType::Zi(zi))
+}
+
+/// Returns the actor of the player.
+fn actor(side: FengType) -> usize {
+    FengType::ALL.iter().position(|&s| s == side).unwrap_or(0)
+}
+
+/// Returns the message as seen by the player,
+/// i.e. the cards drawn by and the hands of the others are hidden as `?`.
+pub fn redact(message: &Message, side: FengType) -> Message {
+    let me = actor(side);
+    match message {
+        Message::Tsumo { actor, .. } if *actor != me => Message::Tsumo {
+            actor: *actor,
+            pai: "?".to_string(),
+        },
+        Message::StartKyoku { .. } => {
+            let mut res = message.clone();
+            if let Message::StartKyoku { tehais, .. } = &mut res {
+                for (i, hand) in tehais.iter_mut().enumerate() {
+                    if i != me {
+                        hand.iter_mut().for_each(|p| *p = "?".to_string());
+                    }
+                }
+            }
+            res
+        }
+        _ => message.clone(),
+    }
+}
+
+/// The struct adapting an [Engine] to the mjai protocol.
+pub struct MjaiSession {
+    /// The engine of the hand.
+    pub engine: Engine,
+    /// The players who have sent [Message::Reach] and are to play the card to declare 立直.
+    pub reaching: BTreeSet<FengType>,
+    /// The player whose 立直 is waiting to be accepted, see [Message::ReachAccepted].
+    accepting: Option<FengType>,
+}
+
+impl MjaiSession {
+    /// Creates a new [MjaiSession] from the engine, whose hand has just started.
+    pub fn new(engine: Engine) -> Self {
+        Self {
+            engine,
+            reaching: BTreeSet::new(),
+            accepting: None,
+        }
+    }
+
+    /// Returns the [Message::StartKyoku] of the hand, with the hands of all players.
+    ///
+    /// It should be sent before any action is applied, see [redact].
+    pub fn start_kyoku(&self) -> Message {
+        let game = &self.engine.game;
+        let cards = &game.cards;
+        let tehais = FengType::ALL
+            .into_iter()
+            .map(|side| {
+                let reds = cards.aka.in_hand.get(&side);
+                let mut res = Vec::new();
+                for (&card, &n) in cards.hand(side) {
+                    let red = reds.map_or(0, |v| v.iter().filter(|&&c| c == card).count());
+                    res.extend((0..n as usize).map(|i| pai(card, i < red)));
+                }
+                res
+            })
+            .collect();
+        let dora = cards.dora_indicators();
+        Message::StartKyoku {
+            bakaze: pai(CardType::Zi(ZiType::Feng(cards.round_wind)), false),
+            kyoku: game.kyoku + 1,
+            honba: game.honba,
+            kyotaku: game.riichi_sticks,
+            oya: actor(cards.dealer),
+            dora_marker: dora
+                .first()
+                .map_or_else(|| "?".to_string(), |&c| pai(c, false)),
+            scores: FengType::ALL.map(|side| cards.score(side)),
+            tehais,
+        }
+    }
+
+    /// Returns the action of the message sent by the player, if any.
+    ///
+    /// A [Message::Dahai] after a [Message::Reach] of the player is [Action::Riichi],
+    /// and [Message::None] is [Action::Pass].
+    pub fn action_of(&self, side: FengType, message: &Message) -> Option<Action> {
+        let card = |p: &str| card_of_pai(p);
+        Some(match message {
+            Message::Dahai { pai, .. } if self.reaching.contains(&side) => {
+                Action::Riichi(card(pai)?)
+            }
+            Message::Dahai { pai, .. } => Action::Discard(card(pai)?),
+            Message::Chi { pai, consumed, .. } => {
+                let cards = consumed
+                    .iter()
+                    .map(|p| card(p))
+                    .collect::<Option<Vec<_>>>()?;
+                Action::Chi(cards.into_iter().chain([card(pai)?]).min()?)
+            }
+            Message::Pon { .. } => Action::Pon,
+            Message::Daiminkan { pai, .. } | Message::Kakan { pai, .. } => Action::Kan(card(pai)?),
+            Message::Ankan { consumed, .. } => Action::AnKan(card(consumed.first()?)?),
+            Message::Hora { actor, target, .. } if actor == target => Action::Tsumo,
+            Message::Hora { .. } => Action::Ron,
+            Message::None => Action::Pass,
+            _ => return None,
+        })
+    }
+
+    /// Takes the message sent by the player, see [MjaiSession::action_of],
+    /// and applies its action, see [MjaiSession::apply].
+    ///
+    /// A [Message::Reach] is taken if the player can declare 立直 with some card,
+    /// waiting for the [Message::Dahai] of the card.
+    ///
+    /// Returns the messages of what happened, or `None` if the message is not taken.
+    pub fn respond(&mut self, side: FengType, message: &Message) -> Option<Vec<Message>> {
+        if let Message::Reach { .. } = message {
+            let cards = self.engine.cards();
+            let can = side == cards.active_player
+                && cards
+                    .current_hand()
+                    .keys()
+                    .any(|&c| cards.can_declare_riichi(c));
+            if can {
+                self.reaching.insert(side);
+                return Some(Vec::new());
+            }
+            return None;
+        }
+        let action = self.action_of(side, message)?;
+        self.apply(side, action)
+    }
+
+    /// Applies the action of the player by the engine, see [Engine::apply].
+    ///
+    /// Returns the messages of what happened, with nothing hidden, see [redact],
+    /// or `None` if the action is not applied.
+    pub fn apply(&mut self, side: FengType, action: Action) -> Option<Vec<Message>> {
+        let engine = &self.engine;
+        let cards = engine.cards();
+        let opens = FengType::ALL.map(|s| cards.open(s).len());
+        let claim = engine
+            .claims
+            .as_ref()
+            .map(|c| (c.discarder, c.card, cards.aka.last_played));
+        let drawn = cards.drawn;
+        let reds = cards.aka.count(side);
+        let revealed = cards.dora_revealed;
+        if !self.engine.apply(side, action) {
+            return None;
+        }
+        let engine = &self.engine;
+        let cards = engine.cards();
+        let me = actor(side);
+        let mut res = Vec::new();
+        match action {
+            Action::Draw => {
+                if let Some(card) = cards.drawn {
+                    res.push(Message::Tsumo {
+                        actor: me,
+                        pai: pai(card, cards.aka.count(side) > reds),
+                    });
+                }
+            }
+            // mjai has no 开立直, so it is sent as a 立直.
+            Action::Discard(card) | Action::Riichi(card) | Action::OpenRiichi(card) => {
+                if let Action::Riichi(_) | Action::OpenRiichi(_) = action {
+                    self.reaching.remove(&side);
+                    self.accepting = Some(side);
+                    res.push(Message::Reach { actor: me });
+                }
+                res.push(Message::Dahai {
+                    actor: me,
+                    pai: pai(card, cards.aka.last_played),
+                    tsumogiri: cards.river(side).last().is_some_and(|r| r.tsumogiri),
+                });
+            }
+            Action::AnKan(card) => res.push(Message::Ankan {
+                actor: me,
+                consumed: vec![pai(card, false); 4],
+            }),
+            Action::Kan(card) if claim.is_none() => res.push(Message::Kakan {
+                actor: me,
+                pai: pai(card, false),
+                consumed: vec![pai(card, false); 3],
+            }),
+            Action::Tsumo => res.extend(drawn.map(|card| Message::Hora {
+                actor: me,
+                target: me,
+                pai: pai(card, false),
+            })),
+            _ => {}
+        }
+        if let Some((discarder, card, red)) = claim {
+            for (caller, s) in FengType::ALL.into_iter().enumerate() {
+                if cards.open(s).len() <= opens[caller] {
+                    continue;
+                }
+                let Some(&case) = cards.open(s).last() else {
+                    continue;
+                };
+                let mut consumed = case.cards();
+                if let Some(pos) = consumed.iter().position(|&c| c == card) {
+                    consumed.remove(pos);
+                }
+                let consumed = consumed.into_iter().map(|c| pai(c, false)).collect();
+                let (actor, target, pai) = (caller, actor(discarder), pai(card, red));
+                res.push(match case {
+                    CaseType::Shun(_) => Message::Chi {
+                        actor,
+                        target,
+                        pai,
+                        consumed,
+                    },
+                    CaseType::Ke(_) => Message::Pon {
+                        actor,
+                        target,
+                        pai,
+                        consumed,
+                    },
+                    _ => Message::Daiminkan {
+                        actor,
+                        target,
+                        pai,
+                        consumed,
+                    },
+                });
+            }
+            if engine.claims.is_none() {
+                if let Some(riichi) = self.accepting.take() {
+                    if !matches!(engine.result, Some(HandEnd::Ron { .. })) {
+                        res.push(Message::ReachAccepted {
+                            actor: actor(riichi),
+                        });
+                    }
+                }
+            }
+            if let Some(HandEnd::Ron { winners, from }) = &engine.result {
+                res.extend(winners.iter().map(|&w| Message::Hora {
+                    actor: actor(w),
+                    target: actor(*from),
+                    pai: pai(card, red),
+                }));
+            }
+        }
+        res.extend(
+            cards
+                .dora_indicators()
+                .into_iter()
+                .skip(revealed as usize)
+                .map(|c| Message::Dora {
+                    dora_marker: pai(c, false),
+                }),
+        );
+        match engine.result {
+            Some(HandEnd::Draw(_)) => res.extend([Message::Ryukyoku, Message::EndKyoku]),
+            Some(_) => res.push(Message::EndKyoku),
+            None => {}
+        }
+        Some(res)
+    }
+
+    /// Returns the players expected to respond at the moment, i.e. those with legal actions,
+    /// see [Engine::legal_actions].
+    pub fn expecting(&self) -> Vec<FengType> {
+        FengType::ALL
+            .into_iter()
+            .filter(|&side| !self.engine.legal_actions(side).is_empty())
+            .collect()
+    }
+}
@@ -0,0 +1,123 @@
+//! A compact hand packed into a single integer, for search-based AI.
+//!
+//! A [PackedHand] keeps the count of each card type in 3 bits of a `u128`,
+//! so it is `Copy`, hashes and compares in a few instructions, and needs no allocation,
+//! unlike the [Hand] map used by [crate::cards::Cards].
+//! The analysis of a packed hand works on the counts directly,
+//! see [PackedHand::shanten], [PackedHand::useful_tiles] and [PackedHand::is_complete],
+//! where the open only matters by the number of its cases(面子).
+
+use crate::{analysis, card_type::CardType, cards::Hand, tile_counts::TileCounts};
+
+/// The number of bits of each count.
+const BITS: u32 = 3;
+
+/// The mask of a count.
+const MASK: u128 = (1 << BITS) - 1;
+
+/// The struct of a hand packed into a `u128`, 3 bits a card type in the order of [CardType::index].
+///
+/// Counts above 7 are not supported, which no hand reaches.
+#[derive(PartialEq, Eq, Clone, Copy, Default, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PackedHand(pub u128);
+
+impl PackedHand {
+    /// Creates a new [PackedHand] containing no card.
+    pub fn new() -> Self {
+        Self(0)
+    }
+
+    /// Returns the number of the card in hand.
+    pub fn count(&self, card: CardType) -> u8 {
+        ((self.0 >> (card.index() as u32 * BITS)) & MASK) as u8
+    }
+
+    /// Puts a card into the hand.
+    ///
+    /// Returns `false` and changes nothing if the hand already contains 7 of the card.
+    pub fn add(&mut self, card: CardType) -> bool {
+        if self.count(card) as u128 >= MASK {
+            return false;
+        }
+        self.0 += 1 << (card.index() as u32 * BITS);
+        true
+    }
+
+    /// Takes a card out of the hand.
+    ///
+    /// Returns `false` and changes nothing if the hand does not contain the card.
+    pub fn remove(&mut self, card: CardType) -> bool {
+        if self.count(card) == 0 {
+            return false;
+        }
+        self.0 -= 1 << (card.index() as u32 * BITS);
+        true
+    }
+
+    /// Returns the total number of cards in hand.
+    pub fn total(&self) -> usize {
+        self.counts().total()
+    }
+
+    /// Returns the counts of each card type.
+    pub fn counts(&self) -> TileCounts {
+        let mut res = TileCounts::new();
+        for (i, n) in res.0.iter_mut().enumerate() {
+            *n = ((self.0 >> (i as u32 * BITS)) & MASK) as u8;
+        }
+        res
+    }
+
+    /// Returns the shanten number(向听数) of the hand with the number of cases(面子) in open,
+    /// see [analysis::shanten].
+    pub fn shanten(&self, melds: usize) -> i8 {
+        analysis::counts_shanten(&mut self.counts().0, melds)
+    }
+
+    /// Checks if the hand with the number of cases(面子) in open is complete(和牌型),
+    /// see [analysis::is_complete].
+    pub fn is_complete(&self, melds: usize) -> bool {
+        analysis::is_counts_complete(&mut self.counts().0, melds)
+    }
+
+    /// Returns the cards that reduce the shanten number(向听数) of the hand
+    /// with the number of cases(面子) in open, see [analysis::useful_tiles].
+    pub fn useful_tiles(&self, melds: usize) -> Vec<CardType> {
+        analysis::counts_useful_tiles(&mut self.counts().0, melds)
+    }
+
+    /// Returns the cards that complete the hand with the number of cases(面子) in open,
+    /// see [analysis::waits].
+    pub fn waits(&self, melds: usize) -> Vec<CardType> {
+        analysis::counts_waits(&mut self.counts().0, melds)
+    }
+}
+
+impl From<&Hand> for PackedHand {
+    fn from(hand: &Hand) -> Self {
+        Self::from(TileCounts::from(hand))
+    }
+}
+
+impl From<TileCounts> for PackedHand {
+    fn from(counts: TileCounts) -> Self {
+        let mut res = 0;
+        for (i, &n) in counts.0.iter().enumerate() {
+            res |= (n.min(MASK as u8) as u128) << (i as u32 * BITS);
+        }
+        Self(res)
+    }
+}
+
+impl From<PackedHand> for TileCounts {
+    fn from(hand: PackedHand) -> Self {
+        hand.counts()
+    }
+}
+
+impl From<PackedHand> for Hand {
+    fn from(hand: PackedHand) -> Self {
+        hand.counts().into()
+    }
+}
@@ -0,0 +1,120 @@
+//! The wire protocol between a server running the engine and its clients, behind the `serde` feature.
+//!
+//! Messages are JSON objects tagged by their `type` in snake case,
+//! e.g. `{"type":"action","action":"Draw"}`, see [ClientMessage] and [ServerMessage].
+//!
+//! A client sends [ClientMessage::Join] with the [VERSION] of the protocol it speaks first,
+//! and is answered by [ServerMessage::Welcome] with its seat,
+//! or [ServerMessage::Error] if the versions differ.
+//! Once the hand(局) starts, the client is sent the whole state shown to its seat,
+//! then only the events since, see [ServerMessage::Delta], with the cards not shown hidden,
+//! and the result when the hand ends.
+
+use crate::{
+    card_type::FengType,
+    engine::{Action, HandEnd},
+    event::GameEvent,
+    view::PlayerView,
+};
+
+/// The version of the protocol, increased on every change breaking clients.
+pub const VERSION: u32 = 2;
+
+/// The `ClientMessage` type. Represents a message from a client to the server.
+#[derive(PartialEq, Eq, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    /// Joining a room, the first message of a client.
+    Join {
+        /// The version of the protocol the client speaks, see [VERSION].
+        version: u32,
+        /// The name of the room.
+        room: String,
+    },
+    /// The action of the client's seat, see [crate::engine::Engine::apply].
+    Action {
+        /// The action.
+        action: Action,
+    },
+}
+
+/// The `ServerMessage` type. Represents a message from the server to a client.
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMessage {
+    /// The client has joined the room.
+    Welcome {
+        /// The version of the protocol the server speaks, see [VERSION].
+        version: u32,
+        /// The seat of the client.
+        seat: FengType,
+    },
+    /// The whole state of the hand shown to the client's seat, sent when the hand starts.
+    State {
+        /// The state shown to the seat, see [crate::cards::Cards::view].
+        view: Box<PlayerView>,
+        /// The actions the seat can do at the moment.
+        actions: Vec<Action>,
+    },
+    /// The events since the last message, shown to the client's seat, see [redact].
+    Delta {
+        /// The events in order.
+        events: Vec<Update>,
+        /// The actions the seat can do at the moment.
+        actions: Vec<Action>,
+    },
+    /// The hand has ended.
+    Result {
+        /// How the hand ended.
+        end: HandEnd,
+        /// The points of players, in the order of players.
+        scores: [i32; 4],
+    },
+    /// The last message of the client is rejected.
+    Error {
+        /// Why it is rejected.
+        message: String,
+    },
+}
+
+/// The `Update` type. Represents an event shown to a player, see [redact].
+#[derive(PartialEq, Eq, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Update {
+    /// An event shown as it is.
+    Event {
+        /// The event.
+        event: GameEvent,
+    },
+    /// Another player draws a card, which is not shown.
+    Draw {
+        /// The player drawing.
+        side: FengType,
+        /// Whether the card is a replacement card(岭上牌).
+        replacement: bool,
+    },
+}
+
+/// Returns the event as shown to the player,
+/// where the card drawn by another player is hidden.
+///
+/// The responses of the other players to a card, i.e. [GameEvent::Claim] and [GameEvent::Pass],
+/// are not shown, as they tell about the hands of those players;
+/// only their outcome is, once all the players have responded,
+/// see [GameEvent::Call], [GameEvent::Ron] and [GameEvent::End].
+///
+/// Returns `None` for [GameEvent::Start], which holds the whole wall,
+/// since the state of the hand is sent instead, see [ServerMessage::State],
+/// and for the responses not shown.
+pub fn redact(event: &GameEvent, for_side: FengType) -> Option<Update> {
+    match *event {
+        GameEvent::Start { .. } => None,
+        GameEvent::Draw {
+            side, replacement, ..
+        } if side != for_side => Some(Update::Draw { side, replacement }),
+        GameEvent::Claim { side, .. } | GameEvent::Pass { side } if side != for_side => None,
+        _ => Some(Update::Event {
+            event: event.clone(),
+        }),
+    }
+}
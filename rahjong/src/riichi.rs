@@ -0,0 +1,699 @@
+//! Built-in situations and completions of riichi mahjong(日本麻将).
+//!
+//! The core of this module is [register], which fills the checkers of a [Cards]
+//! with the standard yaku(役) of riichi mahjong, see also [Cards::with_riichi_rules].
+//!
+//! Situation checkers see the hand, the river and the open of a player
+//! together with the last card and where it comes from, see [WinContext],
+//! so a few things are derived from them:
+//! the last card has been drawn(自摸) if the hand already counts 14 cards,
+//! and the player has declared 立直 if there is a `RiverType::Drawing` card in the river.
+//!
+//! The winds counted as 役牌 are those of the round and the seat given by [WinContext::winds],
+//! which give 场风 and 自风 and decide whether the pair allows 平和.
+//! The dora(宝牌) are not yaku and are not included, see [Cards::score_win].
+//! 一发, 两立直, 开立直, 天和 and 地和 are included through the situations given by [Cards::game_situations].
+//!
+//! Yakuman(役满) count [YAKUMAN_FAN] each without the other yaku, see [completions].
+//!
+//! Situations depending on how the hand is read only look at the interpretation being checked,
+//! see [WinContext::interpretation], and the hand scores the one scoring the most,
+//! see [Cards::interpret], so yaku of different decompositions of the same hand never add up.
+
+use std::collections::HashMap;
+
+use crate::{
+    analysis::{
+        all_cards, all_cases, complete_hand, winning_concealed_triplets, winning_decompositions,
+        winning_forms, winning_waits, Decomposition, FormType, Interpretation, WaitType,
+    },
+    card_type::{CardType, FengType, JianType, RankType, SuitType, ZiType},
+    cards::{Cards, Completion, Hand, Open, River, SituationChecker, SituationFn, WinContext},
+    case_type::CaseType,
+    completion,
+    river_type::RiverCard,
+    rule_config::RuleConfig,
+    situations::{self, has_triplet, FENGS, JIANS},
+};
+
+/// The fan(番数) a yakuman(役满) counts, see [crate::scoring::base_points].
+pub const YAKUMAN_FAN: u16 = 13;
+
+/// Checks if the case or the pair contains a terminal or an honor(幺九牌).
+fn has_yao_jiu(case: CaseType) -> bool {
+    case.cards().iter().any(CardType::is_yao_jiu)
+}
+
+/// Returns the number of pairs of the same 顺子 in the concealed cases, known as 杯口 in Chinese.
+fn pei_kou(decomposition: &Decomposition) -> usize {
+    let mut shuns: Vec<_> = decomposition
+        .cases
+        .iter()
+        .filter(|c| matches!(c, CaseType::Shun(_)))
+        .collect();
+    shuns.sort_unstable();
+    let mut res = 0;
+    let mut i = 0;
+    while i + 1 < shuns.len() {
+        if shuns[i] == shuns[i + 1] {
+            res += 1;
+            i += 2;
+        } else {
+            i += 1;
+        }
+    }
+    res
+}
+
+/// Checks if there are cases of the kind in all the three number suits with the same rank.
+fn san_se(cases: &[CaseType], kind: fn(CaseType) -> Option<CardType>) -> bool {
+    let cards: Vec<_> = cases.iter().filter_map(|&c| kind(c)).collect();
+    cards.iter().any(|card| {
+        [SuitType::Wan, SuitType::Tiao, SuitType::Tong]
+            .iter()
+            .all(|&suit| {
+                cards
+                    .iter()
+                    .any(|c| c.suit() == suit && c.rank() == card.rank())
+            })
+    })
+}
+
+/// Returns the starting card of a 顺子.
+fn shun(case: CaseType) -> Option<CardType> {
+    match case {
+        CaseType::Shun(start) => Some(start),
+        _ => None,
+    }
+}
+
+/// Returns the card of a 刻子 or a 杠子.
+fn ke(case: CaseType) -> Option<CardType> {
+    match case {
+        CaseType::Ke(c) | CaseType::Gang(c) | CaseType::AnGang(c) if c.rank().is_some() => Some(c),
+        _ => None,
+    }
+}
+
+/// 立直: a drawing hand indicator is in the river.
+fn li_zhi(_: &Hand, river: &River, _: &Open, _: CardType, _: &WinContext) -> bool {
+    river.iter().any(RiverCard::is_drawing)
+}
+
+/// Checks if the decomposition is 平和 with the wait shape, given the open of the player
+/// and the winds counted as 役牌, which decide the fu(符) of the pair, see [pair_fu].
+fn is_ping_he(
+    decomposition: &Decomposition,
+    wait: WaitType,
+    open: &Open,
+    winds: &[FengType],
+) -> bool {
+    open.is_empty()
+        && pair_fu(decomposition.pair, winds) == 0
+        && decomposition
+            .cases
+            .iter()
+            .all(|c| matches!(c, CaseType::Shun(_)))
+        && wait == WaitType::LiangMian
+}
+
+/// 平和: concealed 顺子 only, a pair which is not a 役牌, and a 两面 wait.
+///
+/// A pair of 风牌 is allowed unless the wind is counted as 役牌, see [WinContext::winds].
+fn ping_he(hand: &Hand, _: &River, open: &Open, draw: CardType, context: &WinContext) -> bool {
+    winning_decompositions(hand, open, draw, context)
+        .iter()
+        .any(|d| {
+            winning_waits(d, draw, context)
+                .into_iter()
+                .any(|wait| is_ping_he(d, wait, open, &context.winds))
+        })
+}
+
+/// 一杯口: two same concealed 顺子.
+fn yi_bei_kou(
+    hand: &Hand,
+    river: &River,
+    open: &Open,
+    draw: CardType,
+    context: &WinContext,
+) -> bool {
+    situations::men_qian_qing(hand, river, open, draw, context)
+        && winning_decompositions(hand, open, draw, context)
+            .iter()
+            .any(|d| pei_kou(d) >= 1)
+}
+
+/// 二杯口: two pairs of same concealed 顺子.
+fn er_bei_kou(
+    hand: &Hand,
+    river: &River,
+    open: &Open,
+    draw: CardType,
+    context: &WinContext,
+) -> bool {
+    situations::men_qian_qing(hand, river, open, draw, context)
+        && winning_decompositions(hand, open, draw, context)
+            .iter()
+            .any(|d| pei_kou(d) >= 2)
+}
+
+/// 役牌: a triplet of the card, e.g. 白.
+fn yi_pai(card: CardType) -> SituationChecker {
+    Box::new(
+        move |hand: &Hand, _: &River, open: &Open, draw: CardType, _: &WinContext| {
+            has_triplet(hand, open, draw, card)
+        },
+    )
+}
+
+/// 场风: a triplet of the wind of the round, the first of [WinContext::winds].
+fn chang_feng(hand: &Hand, _: &River, open: &Open, draw: CardType, context: &WinContext) -> bool {
+    context
+        .winds
+        .first()
+        .is_some_and(|&feng| has_triplet(hand, open, draw, CardType::Zi(ZiType::Feng(feng))))
+}
+
+/// 自风: a triplet of the wind of the seat, the second of [WinContext::winds].
+fn zi_feng(hand: &Hand, _: &River, open: &Open, draw: CardType, context: &WinContext) -> bool {
+    context
+        .winds
+        .get(1)
+        .is_some_and(|&feng| has_triplet(hand, open, draw, CardType::Zi(ZiType::Feng(feng))))
+}
+
+/// 三色同顺: 顺子 of the same rank in all the three number suits.
+fn san_se_tong_shun(
+    hand: &Hand,
+    _: &River,
+    open: &Open,
+    draw: CardType,
+    context: &WinContext,
+) -> bool {
+    winning_decompositions(hand, open, draw, context)
+        .iter()
+        .any(|d| san_se(&all_cases(d, open), shun))
+}
+
+/// 三色同刻: 刻子 of the same rank in all the three number suits.
+fn san_se_tong_ke(
+    hand: &Hand,
+    _: &River,
+    open: &Open,
+    draw: CardType,
+    context: &WinContext,
+) -> bool {
+    winning_decompositions(hand, open, draw, context)
+        .iter()
+        .any(|d| san_se(&all_cases(d, open), ke))
+}
+
+/// 一气通贯: 123, 456 and 789 of the same suit.
+fn yi_qi_tong_guan(
+    hand: &Hand,
+    _: &River,
+    open: &Open,
+    draw: CardType,
+    context: &WinContext,
+) -> bool {
+    winning_decompositions(hand, open, draw, context)
+        .iter()
+        .any(|d| {
+            let cases = all_cases(d, open);
+            cases.iter().filter_map(|&c| shun(c)).any(|start| {
+                start.rank() == Some(RankType::One)
+                    && [3, 6].iter().all(|&n| {
+                        CardType::from_index(start.index() + n)
+                            .is_some_and(|c| cases.contains(&CaseType::Shun(c)))
+                    })
+            })
+        })
+}
+
+/// 混全带幺九: every case and the pair contain a terminal or an honor.
+fn hun_quan_dai_yao_jiu(
+    hand: &Hand,
+    _: &River,
+    open: &Open,
+    draw: CardType,
+    context: &WinContext,
+) -> bool {
+    winning_decompositions(hand, open, draw, context)
+        .iter()
+        .any(|d| d.pair.is_yao_jiu() && all_cases(d, open).into_iter().all(has_yao_jiu))
+}
+
+/// 纯全带幺九: every case and the pair contain a terminal, and there is no honor.
+fn chun_quan_dai_yao_jiu(
+    hand: &Hand,
+    river: &River,
+    open: &Open,
+    draw: CardType,
+    context: &WinContext,
+) -> bool {
+    all_cards(hand, open, draw)
+        .iter()
+        .all(|c| c.rank().is_some())
+        && hun_quan_dai_yao_jiu(hand, river, open, draw, context)
+}
+
+/// 三暗刻: three concealed 刻子 or 暗杠.
+///
+/// A 刻子 completed by 荣和 is not concealed.
+fn san_an_ke(hand: &Hand, _: &River, open: &Open, draw: CardType, context: &WinContext) -> bool {
+    let (_, tsumo) = complete_hand(hand, open, draw);
+    winning_decompositions(hand, open, draw, context)
+        .iter()
+        .any(|d| winning_concealed_triplets(d, open, draw, tsumo, context) >= 3)
+}
+
+/// 三杠子: three 杠子.
+fn san_gang_zi(_: &Hand, _: &River, open: &Open, _: CardType, _: &WinContext) -> bool {
+    open.iter()
+        .filter(|c| matches!(c, CaseType::Gang(_) | CaseType::AnGang(_)))
+        .count()
+        == 3
+}
+
+/// 小三元: two triplets of 三元牌 and a pair of the other.
+fn xiao_san_yuan(hand: &Hand, _: &River, open: &Open, draw: CardType, _: &WinContext) -> bool {
+    let (complete, _) = complete_hand(hand, open, draw);
+    let triplets = JIANS
+        .iter()
+        .filter(|&&c| has_triplet(hand, open, draw, c))
+        .count();
+    let pair = JIANS
+        .iter()
+        .any(|c| complete.get(c).copied().unwrap_or(0) == 2);
+    triplets == 2 && pair
+}
+
+/// 国士无双: one of each terminal and honor(幺九牌), and a pair of one of them.
+fn guo_shi_wu_shuang(
+    hand: &Hand,
+    _: &River,
+    open: &Open,
+    draw: CardType,
+    context: &WinContext,
+) -> bool {
+    winning_forms(hand, open, draw, context)
+        .iter()
+        .any(|f| matches!(f, FormType::GuoShi(_)))
+}
+
+/// 国士无双十三面: 国士无双 completed by the pair, i.e. waiting for all the thirteen cards.
+fn guo_shi_shi_san_mian(
+    hand: &Hand,
+    _: &River,
+    open: &Open,
+    draw: CardType,
+    context: &WinContext,
+) -> bool {
+    winning_forms(hand, open, draw, context)
+        .iter()
+        .any(|f| matches!(f, FormType::GuoShi(pair) if *pair == draw))
+}
+
+/// 四暗刻: four concealed 刻子 or 暗杠.
+///
+/// A 刻子 completed by 荣和 is not concealed.
+fn si_an_ke(hand: &Hand, _: &River, open: &Open, draw: CardType, context: &WinContext) -> bool {
+    let (_, tsumo) = complete_hand(hand, open, draw);
+    winning_decompositions(hand, open, draw, context)
+        .iter()
+        .any(|d| winning_concealed_triplets(d, open, draw, tsumo, context) == 4)
+}
+
+/// 四暗刻单骑: 四暗刻 completed by the pair(单骑).
+fn si_an_ke_dan_qi(
+    hand: &Hand,
+    _: &River,
+    open: &Open,
+    draw: CardType,
+    context: &WinContext,
+) -> bool {
+    let (_, tsumo) = complete_hand(hand, open, draw);
+    winning_decompositions(hand, open, draw, context)
+        .iter()
+        .any(|d| d.pair == draw && winning_concealed_triplets(d, open, draw, tsumo, context) == 4)
+}
+
+/// 大三元: triplets of all the three 三元牌.
+fn da_san_yuan(hand: &Hand, _: &River, open: &Open, draw: CardType, _: &WinContext) -> bool {
+    JIANS.iter().all(|&c| has_triplet(hand, open, draw, c))
+}
+
+/// 小四喜: three triplets of 风牌 and a pair of the other.
+fn xiao_si_xi(hand: &Hand, _: &River, open: &Open, draw: CardType, _: &WinContext) -> bool {
+    let (complete, _) = complete_hand(hand, open, draw);
+    let triplets = FENGS
+        .iter()
+        .filter(|&&c| has_triplet(hand, open, draw, c))
+        .count();
+    let pair = FENGS
+        .iter()
+        .any(|c| complete.get(c).copied().unwrap_or(0) == 2);
+    triplets == 3 && pair
+}
+
+/// 大四喜: triplets of all the four 风牌.
+fn da_si_xi(hand: &Hand, _: &River, open: &Open, draw: CardType, _: &WinContext) -> bool {
+    FENGS.iter().all(|&c| has_triplet(hand, open, draw, c))
+}
+
+/// 绿一色: 2, 3, 4, 6, 8 of 条 and 发 only.
+fn lv_yi_se(hand: &Hand, _: &River, open: &Open, draw: CardType, _: &WinContext) -> bool {
+    all_cards(hand, open, draw).iter().all(|c| {
+        matches!(
+            c,
+            CardType::Tiao(
+                RankType::Two | RankType::Three | RankType::Four | RankType::Six | RankType::Eight
+            ) | CardType::Zi(ZiType::Jian(JianType::Fa))
+        )
+    })
+}
+
+/// Returns how many cards of each rank the concealed hand holds
+/// if it is all of the suit of the last card and there is no call action, 暗杠 included.
+fn jiu_lian_ranks(hand: &Hand, open: &Open, draw: CardType) -> Option<[u8; 9]> {
+    let suit = draw.suit();
+    if !open.is_empty() || suit == SuitType::Zi || hand.keys().any(|c| c.suit() != suit) {
+        return None;
+    }
+    let mut res = [0; 9];
+    for (c, &n) in hand {
+        res[c.rank()? as usize] += n;
+    }
+    Some(res)
+}
+
+/// The ranks of 九莲宝灯 before the last card, i.e. 1112345678999 of a suit.
+const JIU_LIAN: [u8; 9] = [3, 1, 1, 1, 1, 1, 1, 1, 3];
+
+/// 九莲宝灯: concealed 1112345678999 of a suit, and another card of the suit.
+fn jiu_lian_bao_deng(hand: &Hand, _: &River, open: &Open, draw: CardType, _: &WinContext) -> bool {
+    let (hand, _) = complete_hand(hand, open, draw);
+    jiu_lian_ranks(&hand, open, draw)
+        .is_some_and(|ranks| ranks.iter().zip(JIU_LIAN).all(|(&n, base)| n >= base))
+}
+
+/// 纯正九莲宝灯: 九莲宝灯 completed by the other card, i.e. waiting for all the nine cards of the suit.
+fn chun_zheng_jiu_lian(
+    hand: &Hand,
+    _: &River,
+    open: &Open,
+    draw: CardType,
+    _: &WinContext,
+) -> bool {
+    let (mut hand, _) = complete_hand(hand, open, draw);
+    if let Some(n) = hand.get_mut(&draw) {
+        *n -= 1;
+    }
+    jiu_lian_ranks(&hand, open, draw) == Some(JIU_LIAN)
+}
+
+/// 四杠子: four 杠子.
+fn si_gang_zi(_: &Hand, _: &River, open: &Open, _: CardType, _: &WinContext) -> bool {
+    open.iter()
+        .filter(|c| matches!(c, CaseType::Gang(_) | CaseType::AnGang(_)))
+        .count()
+        == 4
+}
+
+/// Returns the fu(符) of a case, given whether it is concealed.
+fn case_fu(case: CaseType, concealed: bool) -> u8 {
+    let (card, base) = match case {
+        CaseType::Shun(_) => return 0,
+        CaseType::Ke(card) => (card, 2),
+        CaseType::Gang(card) | CaseType::AnGang(card) => (card, 8),
+    };
+    base * if card.is_yao_jiu() { 2 } else { 1 } * if concealed { 2 } else { 1 }
+}
+
+/// Returns the fu(符) of a pair, given the winds counted as 役牌.
+///
+/// A wind listed twice, i.e. both the wind of the round and the seat, counts twice.
+fn pair_fu(pair: CardType, winds: &[FengType]) -> u8 {
+    match pair {
+        CardType::Zi(ZiType::Jian(_)) => 2,
+        CardType::Zi(ZiType::Feng(feng)) => 2 * winds.iter().filter(|&&f| f == feng).count() as u8,
+        _ => 0,
+    }
+}
+
+/// Returns the fu(符) of a standard decomposition with the wait shape.
+fn decomposition_fu(
+    decomposition: &Decomposition,
+    wait: WaitType,
+    open: &Open,
+    winning: CardType,
+    is_tsumo: bool,
+    winds: &[FengType],
+) -> u8 {
+    if is_ping_he(decomposition, wait, open, winds) {
+        return if is_tsumo { 20 } else { 30 };
+    }
+    let concealed = !open.iter().any(CaseType::is_open);
+    let mut fu = 20 + pair_fu(decomposition.pair, winds);
+    fu += open.iter().map(|&c| case_fu(c, !c.is_open())).sum::<u8>();
+    let mut ron_ke = !is_tsumo && wait == WaitType::ShuangPeng;
+    for &case in &decomposition.cases {
+        if ron_ke && case == CaseType::Ke(winning) {
+            ron_ke = false;
+            fu += case_fu(case, false);
+        } else {
+            fu += case_fu(case, true);
+        }
+    }
+    if matches!(
+        wait,
+        WaitType::QianZhang | WaitType::BianZhang | WaitType::DanQi
+    ) {
+        fu += 2;
+    }
+    if is_tsumo {
+        fu += 2;
+    } else if concealed {
+        fu += 10;
+    }
+    // An open hand without any fu counts 30.
+    if fu == 20 {
+        30
+    } else {
+        fu.div_ceil(10) * 10
+    }
+}
+
+/// Returns the fu(符) of an interpretation of a complete hand, rounded up to tens,
+/// see [Cards::interpret] for the interpretation chosen.
+///
+/// `winds` are the winds counted as 役牌 for the pair, i.e. the wind of the round and the seat.
+/// A 自摸 includes a replacement card(岭上牌), see [WinContext::is_tsumo].
+///
+/// 七对子 counts 25, and 平和 counts 20 for 自摸 and 30 for 荣和.
+/// Other forms, such as 国士无双, do not depend on fu, and count 30.
+pub fn fu(
+    interpretation: &Interpretation,
+    open: &Open,
+    winning: CardType,
+    is_tsumo: bool,
+    winds: &[FengType],
+) -> u8 {
+    match (&interpretation.form, interpretation.wait) {
+        (FormType::Standard(d), Some(wait)) => {
+            decomposition_fu(d, wait, open, winning, is_tsumo, winds)
+        }
+        (FormType::QiDui(_), _) => 25,
+        _ => 30,
+    }
+}
+
+/// The `DoraBreakdown` type. Represents the dora(宝牌) of a complete hand, each counting 1 fan.
+#[derive(PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DoraBreakdown {
+    /// The number of dora indicated by the dora indicators(宝牌指示牌).
+    pub dora: u16,
+    /// The number of dora indicated by the ura dora indicators(里宝牌指示牌),
+    /// which only count after declaring 立直.
+    pub ura_dora: u16,
+    /// The number of red fives(赤宝牌).
+    pub aka: u16,
+    /// The number of dora of the 北 set aside in 三麻(拔北宝牌),
+    /// each counting 1 and once more for each indicator pointing to 北.
+    pub nuki: u16,
+}
+
+impl DoraBreakdown {
+    /// Returns the total number of dora, i.e. the fan they add.
+    pub fn total(&self) -> u16 {
+        self.dora + self.ura_dora + self.aka + self.nuki
+    }
+}
+
+/// Returns the number of dora(宝牌) in the hand and the open given the dora indicators(宝牌指示牌).
+///
+/// The hand should contain the winning card, see [Cards::winning_hand].
+/// A card counts once for each indicator pointing to it.
+pub fn dora_count(hand: &Hand, open: &Open, indicators: &[CardType]) -> u16 {
+    let cards = hand
+        .iter()
+        .flat_map(|(&c, &n)| std::iter::repeat_n(c, n as usize))
+        .chain(open.iter().flat_map(CaseType::cards));
+    cards
+        .map(|c| indicators.iter().filter(|i| i.dora() == c).count() as u16)
+        .sum()
+}
+
+/// Returns the situation checkers of riichi mahjong, named in Chinese,
+/// i.e. the standard ones, see [situations::standard_checkers], and those of the yaku.
+pub fn situation_checkers() -> HashMap<&'static str, SituationChecker> {
+    let checkers: [(&'static str, SituationFn); 25] = [
+        ("立直", li_zhi),
+        ("平和", ping_he),
+        ("一杯口", yi_bei_kou),
+        ("二杯口", er_bei_kou),
+        ("场风", chang_feng),
+        ("自风", zi_feng),
+        ("三色同顺", san_se_tong_shun),
+        ("三色同刻", san_se_tong_ke),
+        ("一气通贯", yi_qi_tong_guan),
+        ("混全带幺九", hun_quan_dai_yao_jiu),
+        ("纯全带幺九", chun_quan_dai_yao_jiu),
+        ("三暗刻", san_an_ke),
+        ("三杠子", san_gang_zi),
+        ("小三元", xiao_san_yuan),
+        ("国士无双", guo_shi_wu_shuang),
+        ("国士无双十三面", guo_shi_shi_san_mian),
+        ("四暗刻", si_an_ke),
+        ("四暗刻单骑", si_an_ke_dan_qi),
+        ("大三元", da_san_yuan),
+        ("小四喜", xiao_si_xi),
+        ("大四喜", da_si_xi),
+        ("绿一色", lv_yi_se),
+        ("九莲宝灯", jiu_lian_bao_deng),
+        ("纯正九莲宝灯", chun_zheng_jiu_lian),
+        ("四杠子", si_gang_zi),
+    ];
+    let mut res = situations::standard_checkers();
+    res.extend(situations::collect_checkers(checkers));
+    for (name, jian) in [
+        ("役牌白", JianType::Bai),
+        ("役牌发", JianType::Fa),
+        ("役牌中", JianType::Zhong),
+    ] {
+        res.insert(name, yi_pai(CardType::Zi(ZiType::Jian(jian))));
+    }
+    res
+}
+
+/// The yakuman(役满) and how many times [YAKUMAN_FAN] each of them counts,
+/// the double yakuman(双倍役满) first, each forbidding the yakuman it contains.
+const YAKUMAN: [(&str, u16, &[&str]); 15] = [
+    ("国士无双十三面", 2, &[]),
+    ("四暗刻单骑", 2, &[]),
+    ("大四喜", 2, &[]),
+    ("纯正九莲宝灯", 2, &[]),
+    ("天和", 1, &[]),
+    ("地和", 1, &[]),
+    ("国士无双", 1, &["国士无双十三面"]),
+    ("四暗刻", 1, &["四暗刻单骑"]),
+    ("大三元", 1, &[]),
+    ("小四喜", 1, &[]),
+    ("字一色", 1, &[]),
+    ("绿一色", 1, &[]),
+    ("清老头", 1, &[]),
+    ("九莲宝灯", 1, &["纯正九莲宝灯"]),
+    ("四杠子", 1, &[]),
+];
+
+/// Returns the completions of riichi mahjong, i.e. the yaku(役) and their fan(番数),
+/// following the options of the rules given.
+///
+/// Yaku worth less after a call action(食下) come in two completions,
+/// one requiring `门前清` and the other requiring `副露`.
+/// 断幺九 requires `门前清` unless [RuleConfig::kuitan] is enabled.
+///
+/// A yakuman(役满) counts [YAKUMAN_FAN] and forbids the other yaku,
+/// a double yakuman(双倍役满) twice as much unless [RuleConfig::double_yakuman] is disabled.
+/// Yakuman met together add up(复合役满) unless [RuleConfig::yakuman_stacking] is disabled,
+/// in which case only the first of them counts, the double yakuman first.
+pub fn completions(config: &RuleConfig) -> Vec<Completion> {
+    let mut res = vec![
+        completion!("立直", 1, requires ["立直"], forbids ["两立直", "开立直"]),
+        completion!("两立直", 2, requires["两立直"], forbids["开立直"]),
+        completion!("开立直", 2, requires["开立直"], forbids["两立直"]),
+        completion!("开两立直", 3, requires ["开立直", "两立直"]),
+        completion!("一发", 1, requires["一发"]),
+        completion!("抢杠", 1, requires["抢杠和"]),
+        completion!("岭上开花", 1, requires["岭上开花"]),
+        completion!("海底摸月", 1, requires["海底摸月"]),
+        completion!("河底捞鱼", 1, requires["河底捞鱼"]),
+        completion!("门前清自摸和", 1, requires ["门前清", "自摸"]),
+        completion!("平和", 1, requires["平和"]),
+        completion!("一杯口", 1, requires["一杯口"], forbids["二杯口"]),
+        completion!("役牌 白", 1, requires["役牌白"]),
+        completion!("役牌 发", 1, requires["役牌发"]),
+        completion!("役牌 中", 1, requires["役牌中"]),
+        completion!("役牌 场风", 1, requires["场风"]),
+        completion!("役牌 自风", 1, requires["自风"]),
+        completion!("三色同刻", 2, requires["三色同刻"]),
+        completion!("对对和", 2, requires["对对和"]),
+        completion!("三暗刻", 2, requires["三暗刻"]),
+        completion!("三杠子", 2, requires["三杠子"]),
+        completion!("小三元", 2, requires["小三元"]),
+        completion!("混老头", 2, requires["混老头"]),
+        completion!("七对子", 2, requires["七对子"], forbids["二杯口"]),
+        completion!("二杯口", 3, requires["二杯口"]),
+    ];
+    for (name, fan, forbidden) in [
+        ("三色同顺", 2, vec![]),
+        ("一气通贯", 2, vec![]),
+        ("混全带幺九", 2, vec!["纯全带幺九", "混老头"]),
+        ("纯全带幺九", 3, vec![]),
+        ("混一色", 3, vec!["清一色"]),
+        ("清一色", 6, vec![]),
+    ] {
+        for (situation, fan) in [("门前清", fan), ("副露", fan - 1)] {
+            res.push(
+                Completion::builder(name)
+                    .require_all([name, situation])
+                    .forbid_all(forbidden.iter().copied())
+                    .fan(fan)
+                    .build(),
+            );
+        }
+    }
+    if config.kuitan {
+        res.push(completion!("断幺九", 1, requires["断幺九"]));
+    } else {
+        res.push(completion!("断幺九", 1, requires ["断幺九", "门前清"]));
+    }
+    let names = YAKUMAN.map(|(name, _, _)| name);
+    for c in &mut res {
+        c.forbidden.extend(names);
+    }
+    for (i, (name, times, contained)) in YAKUMAN.into_iter().enumerate() {
+        let mut forbidden = contained.to_vec();
+        if !config.yakuman_stacking {
+            forbidden.extend(&names[..i]);
+        }
+        let times = if config.double_yakuman { times } else { 1 };
+        res.push(
+            Completion::builder(name)
+                .require(name)
+                .forbid_all(forbidden)
+                .fan(YAKUMAN_FAN * times)
+                .build(),
+        );
+    }
+    res
+}
+
+/// Registers the situations and completions of riichi mahjong into the cards,
+/// following the options of the cards, see [Cards::config].
+pub fn register(cards: &mut Cards) {
+    cards.situation_checkers.extend(situation_checkers());
+    cards.completion_checkers.extend(completions(&cards.config));
+}
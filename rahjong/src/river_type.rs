@@ -1,11 +1,17 @@
 //! River types and their associated methods if any.
-//! 
-//! River cards are cards that being played in the game, stored in the river array.
+//!
+//! River cards are cards that being played in the game, stored in the river array as [RiverCard].
+//!
+//! A river card is displayed as its card, e.g. `3m`,
+//! followed by `*` if it is a drawing hand indicator, e.g. `3m*`.
+
+use std::fmt;
 
 use crate::card_type::CardType;
 
 /// The `RiverType` type. See [the module level documentation](self) for more.
 #[derive(PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RiverType {
     /// The card being played normally.
     /// Also used to indicate that the card is being played when checking completion.
@@ -14,3 +20,84 @@ pub enum RiverType {
     /// Also used to indicate that the card is being drawn when checking completion.
     Drawing(CardType),
 }
+
+impl RiverType {
+    /// Returns the card being played.
+    pub fn card(&self) -> CardType {
+        match *self {
+            Self::Normal(card) | Self::Drawing(card) => card,
+        }
+    }
+}
+
+/// The struct of a card in a river, see [crate::cards::River].
+#[derive(PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RiverCard {
+    /// The card played, and whether as the drawing hand indicator.
+    pub kind: RiverType,
+    /// Whether the card is taken by a call action of another player,
+    /// i.e. moved out of the river to the open of the caller at the table.
+    ///
+    /// The card is kept in the river for 振听 and 流局满贯, see [crate::cards::Cards::is_nagashi].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub claimed: bool,
+    /// Whether the card is the one just drawn, known as 摸切 in Chinese,
+    /// or else played from the hand(手切), as seen at the table.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub tsumogiri: bool,
+    /// The turn the card is played in, counting the cards played by all players in the hand from 0,
+    /// see [crate::cards::Cards::turn].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub turn: u16,
+}
+
+impl RiverCard {
+    /// Returns the card being played.
+    pub fn card(&self) -> CardType {
+        self.kind.card()
+    }
+
+    /// Checks if the card is played as the drawing hand indicator, i.e. declaring 立直.
+    pub fn is_drawing(&self) -> bool {
+        matches!(self.kind, RiverType::Drawing(_))
+    }
+}
+
+impl From<RiverType> for RiverCard {
+    fn from(kind: RiverType) -> Self {
+        Self {
+            kind,
+            claimed: false,
+            tsumogiri: false,
+            turn: 0,
+        }
+    }
+}
+
+impl fmt::Display for RiverCard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.kind.fmt(f)
+    }
+}
+
+impl fmt::Debug for RiverCard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for RiverType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Normal(card) => write!(f, "{card}"),
+            Self::Drawing(card) => write!(f, "{card}*"),
+        }
+    }
+}
+
+impl fmt::Debug for RiverType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
@@ -0,0 +1,172 @@
+//! The options of the rules a table can turn on or off, known as 规则 or ルール in Japanese.
+//!
+//! The core of this module is the [RuleConfig] struct,
+//! kept by the cards as [crate::cards::Cards::config] and read when the calls, the plays and the completions are checked,
+//! and when the points are paid, see [crate::ruleset::Ruleset::payment].
+//! It is built by chaining its setters on the default, e.g.
+//! `RuleConfig::default().kuitan(false).aka([1, 1, 1])`,
+//! and given to the cards by [crate::cards::Cards::with_config].
+
+use crate::scoring;
+
+/// The `KanDoraTiming` type. Represents when the dora indicator(宝牌指示牌) of a 杠 is revealed,
+/// known as 杠宝牌 or 杠ドラ, see [crate::cards::Cards::reveal_dora].
+#[derive(PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum KanDoraTiming {
+    /// Revealed as soon as the 杠 is done, before the replacement card(岭上牌) is drawn,
+    /// so it counts for 岭上开花 and 抢杠.
+    #[default]
+    Immediate,
+    /// Revealed when the player doing the 杠 plays the next card, known as 打牌后翻 in Chinese,
+    /// so it does not count for 岭上开花 or 抢杠, but does for 荣和 on the card played,
+    /// see [crate::cards::Cards::pending_dora].
+    AfterDiscard,
+}
+
+/// The options of the rules, see the [module](self) docs.
+///
+/// The default is common in riichi mahjong:
+/// 食断 and 后付 allowed, 食替 forbidden, one winner of a card played(头跳), no 切上满贯,
+/// double yakuman(双倍役满) and 复合役满 counted, the dora of 暗杠 revealed at once
+/// and of other 杠 after the next card played, no 开立直 and no red fives.
+#[derive(PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RuleConfig {
+    /// Whether an open hand can be all simples(断幺九), known as 食断 in Chinese.
+    ///
+    /// Takes effect when the completions are registered, see [crate::riichi::register].
+    pub kuitan: bool,
+    /// Whether a hand can complete with a card only some of its waits give a completion,
+    /// known as 后付 in Chinese, or 片和了 for the hand.
+    ///
+    /// If not, every wait of the hand must give a completion, see [crate::cards::Cards::is_kata_agari].
+    pub atozuke: bool,
+    /// Whether a player can play the card just called or its other side right after a 吃 or 碰,
+    /// known as 食替 in Chinese, see [crate::cards::Cards::kuikae].
+    pub kuikae: bool,
+    /// Whether more than one player can complete with the card played(一炮多响),
+    /// see [crate::claim::ClaimArbitrator::multiple_ron], which is always the case in 血战到底.
+    pub multiple_ron: bool,
+    /// Whether 4 fan 30 fu and 3 fan 60 fu are rounded up to 满贯, known as 切上满贯 in Chinese,
+    /// see [RuleConfig::base_points].
+    pub kiriage: bool,
+    /// Whether a double yakuman(双倍役满), e.g. 四暗刻单骑, counts twice as much as a yakuman(役满).
+    ///
+    /// Takes effect when the completions are registered, see [crate::riichi::completions].
+    pub double_yakuman: bool,
+    /// Whether the yakuman(役满) met together add up, known as 复合役满 in Chinese.
+    ///
+    /// Takes effect when the completions are registered, see [crate::riichi::completions].
+    pub yakuman_stacking: bool,
+    /// When the dora indicator of an 暗杠 is revealed.
+    pub ankan_dora: KanDoraTiming,
+    /// When the dora indicator of a 大明杠 or an 加杠 is revealed.
+    pub minkan_dora: KanDoraTiming,
+    /// Whether a player can declare 立直 openly, known as 开立直 in Chinese,
+    /// see [crate::cards::Cards::declare_open_riichi].
+    ///
+    /// 开立直 counts 2 fan, or 3 fan with 两立直,
+    /// and a player not in 立直 playing the winning card to it pays a yakuman(役满),
+    /// see [crate::cards::Cards::score_win].
+    pub open_riichi: bool,
+    /// The number of red fives(赤宝牌) of 万, 条 and 筒 in the wall, from 0 to 4 each.
+    ///
+    /// Takes effect when the wall is built, see [crate::aka::Aka::mark].
+    pub aka: [u8; 3],
+}
+
+impl Default for RuleConfig {
+    fn default() -> Self {
+        Self {
+            kuitan: true,
+            atozuke: true,
+            kuikae: false,
+            multiple_ron: false,
+            kiriage: false,
+            double_yakuman: true,
+            yakuman_stacking: true,
+            ankan_dora: KanDoraTiming::Immediate,
+            minkan_dora: KanDoraTiming::AfterDiscard,
+            open_riichi: false,
+            aka: [0; 3],
+        }
+    }
+}
+
+impl RuleConfig {
+    /// Sets whether an open hand can be all simples(食断), see [RuleConfig::kuitan].
+    pub fn kuitan(mut self, kuitan: bool) -> Self {
+        self.kuitan = kuitan;
+        self
+    }
+
+    /// Sets whether a hand can complete by 后付, see [RuleConfig::atozuke].
+    pub fn atozuke(mut self, atozuke: bool) -> Self {
+        self.atozuke = atozuke;
+        self
+    }
+
+    /// Sets whether 食替 is allowed, see [RuleConfig::kuikae].
+    pub fn kuikae(mut self, kuikae: bool) -> Self {
+        self.kuikae = kuikae;
+        self
+    }
+
+    /// Sets whether more than one player can complete with the card played, see [RuleConfig::multiple_ron].
+    pub fn multiple_ron(mut self, multiple_ron: bool) -> Self {
+        self.multiple_ron = multiple_ron;
+        self
+    }
+
+    /// Sets whether 切上满贯 is counted, see [RuleConfig::kiriage].
+    pub fn kiriage(mut self, kiriage: bool) -> Self {
+        self.kiriage = kiriage;
+        self
+    }
+
+    /// Sets whether a double yakuman counts twice, see [RuleConfig::double_yakuman].
+    pub fn double_yakuman(mut self, double_yakuman: bool) -> Self {
+        self.double_yakuman = double_yakuman;
+        self
+    }
+
+    /// Sets whether the yakuman add up, see [RuleConfig::yakuman_stacking].
+    pub fn yakuman_stacking(mut self, yakuman_stacking: bool) -> Self {
+        self.yakuman_stacking = yakuman_stacking;
+        self
+    }
+
+    /// Sets when the dora indicator of an 暗杠 is revealed, see [RuleConfig::ankan_dora].
+    pub fn ankan_dora(mut self, timing: KanDoraTiming) -> Self {
+        self.ankan_dora = timing;
+        self
+    }
+
+    /// Sets when the dora indicator of a 大明杠 or an 加杠 is revealed, see [RuleConfig::minkan_dora].
+    pub fn minkan_dora(mut self, timing: KanDoraTiming) -> Self {
+        self.minkan_dora = timing;
+        self
+    }
+
+    /// Sets whether 开立直 is allowed, see [RuleConfig::open_riichi].
+    pub fn open_riichi(mut self, open_riichi: bool) -> Self {
+        self.open_riichi = open_riichi;
+        self
+    }
+
+    /// Sets the number of red fives of 万, 条 and 筒, see [RuleConfig::aka].
+    pub fn aka(mut self, aka: [u8; 3]) -> Self {
+        self.aka = aka;
+        self
+    }
+
+    /// Returns the base points(基本点) of riichi mahjong given fan(番数) and fu(符),
+    /// see [scoring::base_points], rounded up to 满贯 by [RuleConfig::kiriage].
+    pub fn base_points(&self, fan: u16, fu: u8) -> u32 {
+        match scoring::base_points(fan, fu) {
+            1920 if self.kiriage => 2000,
+            base => base,
+        }
+    }
+}
@@ -0,0 +1,562 @@
+//! Rule sets, i.e. what tells one variant of mahjong from another, and the built-in ones.
+//!
+//! The core of this module is the [Ruleset] trait:
+//! the composition of the wall, the players and their hand size, the call actions allowed,
+//! the completions and payments, and how a hand can be drawn.
+//! [Cards] and the [crate::engine::Engine] running on it ask [Cards::rules] for all of these,
+//! so a variant or a set of house rules is an implementation rather than a fork,
+//! see [Cards::with_ruleset].
+//!
+//! The built-in rule sets are unit structs, each told by its [RulesetId].
+//! [Cards] always carries one, riichi mahjong by default, see [Cards::ruleset].
+
+use std::sync::Arc;
+
+use crate::{
+    card_type::{CardType, FengType, RankType},
+    cards::Cards,
+    rule_config::RuleConfig,
+    scoring::{self, Payment},
+};
+
+/// The trait of rule sets, see the [module](self) docs.
+///
+/// Every method but [Ruleset::register] and [Ruleset::payment] defaults to riichi mahjong.
+pub trait Ruleset: Send + Sync {
+    /// Returns the built-in rule set these rules are, or are based on,
+    /// by which the rules are rebuilt from a [crate::view::PlayerView], see [Cards::sample_from_view].
+    fn id(&self) -> RulesetId {
+        RulesetId::Riichi
+    }
+
+    /// Returns the number of copies of the card type in the wall, at most 4.
+    fn copies(&self, _card: CardType) -> u8 {
+        4
+    }
+
+    /// Returns the players of the game in the order of playing.
+    fn players(&self) -> &'static [FengType] {
+        &FengType::ALL
+    }
+
+    /// Returns the number of cards a player holds while waiting for a card, see [Cards::hand_size].
+    fn hand_size(&self) -> usize {
+        13
+    }
+
+    /// Returns whether the next player can call 吃 on the card played.
+    fn allows_chi(&self) -> bool {
+        true
+    }
+
+    /// Returns whether the players can declare 立直, see [Cards::declare_riichi].
+    fn allows_riichi(&self) -> bool {
+        true
+    }
+
+    /// Returns whether a hand can end in an abortive draw(途中流局), see [Cards::check_abortive_draw].
+    fn has_abortive_draws(&self) -> bool {
+        true
+    }
+
+    /// Returns whether 流局满贯 is paid at the end of a drawn hand, see [Cards::is_nagashi].
+    fn has_nagashi(&self) -> bool {
+        true
+    }
+
+    /// Returns whether the dora(宝牌) add to the fan of a completion, see [Cards::score_win].
+    fn has_dora(&self) -> bool {
+        true
+    }
+
+    /// Returns whether a completion is also paid the counters(本场) and the riichi sticks(供托),
+    /// see [crate::game::Game::settle_win].
+    fn has_honba(&self) -> bool {
+        true
+    }
+
+    /// Returns the points every player starts with.
+    fn starting_score(&self) -> i32 {
+        25000
+    }
+
+    /// Returns the minimum total fan(番数) of the completions to complete, known as 起和番 in Chinese.
+    ///
+    /// Zero means any completion is enough.
+    fn min_fan(&self) -> u16 {
+        0
+    }
+
+    /// Returns whether a 北 can be set aside as a dora, as in 三麻, see [Cards::nuki_dora].
+    fn has_nuki_dora(&self) -> bool {
+        false
+    }
+
+    /// Returns whether the flower cards(花牌) are always in the wall, see [crate::flower::Flowers::enabled].
+    fn has_flowers(&self) -> bool {
+        false
+    }
+
+    /// Returns whether a hand can complete in the knitted forms of MCR, i.e. 全不靠 and 组合龙,
+    /// see [crate::analysis::knitted_forms].
+    fn has_knitted_forms(&self) -> bool {
+        false
+    }
+
+    /// Returns whether the hand goes on after a player completes until three of them have,
+    /// known as 血战到底 in Chinese, see [Cards::finished].
+    ///
+    /// Each player declares a void suit before the first draw, see [Cards::declare_void].
+    fn continues_after_completion(&self) -> bool {
+        false
+    }
+
+    /// Registers the situations and completions of the rules into the cards.
+    fn register(&self, cards: &mut Cards);
+
+    /// Returns the payment of a completion given its fan(番数) and fu(符),
+    /// where `from` is the player who played the winning card, or `None` for 自摸,
+    /// following the options of the rules, e.g. [RuleConfig::kiriage].
+    fn payment(
+        &self,
+        config: &RuleConfig,
+        fan: u16,
+        fu: u8,
+        winner: FengType,
+        dealer: FengType,
+        from: Option<FengType>,
+    ) -> Payment;
+
+    /// Returns the points each player gains or loses at the end of a drawn hand(荒牌流局),
+    /// given the players in drawing hand(听牌), see [scoring::noten_payments].
+    fn noten_payments(&self, tenpai: &[FengType]) -> Vec<(FengType, i32)> {
+        scoring::noten_payments(tenpai)
+    }
+}
+
+/// The `RulesetId` type. Represents one of the built-in rule sets, see [Ruleset::id].
+#[derive(PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RulesetId {
+    /// [RiichiRules].
+    #[default]
+    Riichi,
+    /// [SanmaRules].
+    Sanma,
+    /// [TaiwaneseRules].
+    Taiwanese,
+    /// [HkosRules].
+    Hkos,
+    /// [McrRules].
+    Mcr,
+    /// [SichuanRules].
+    Sichuan,
+    /// [ZungJungRules].
+    ZungJung,
+}
+
+impl RulesetId {
+    /// Returns the built-in rule set of the id.
+    pub fn ruleset(self) -> Arc<dyn Ruleset> {
+        match self {
+            Self::Riichi => Arc::new(RiichiRules),
+            Self::Sanma => Arc::new(SanmaRules),
+            Self::Taiwanese => Arc::new(TaiwaneseRules),
+            Self::Hkos => Arc::new(HkosRules),
+            Self::Mcr => Arc::new(McrRules),
+            Self::Sichuan => Arc::new(SichuanRules),
+            Self::ZungJung => Arc::new(ZungJungRules),
+        }
+    }
+}
+
+/// The rules carried by [Cards], see [Cards::ruleset], riichi mahjong by default.
+#[derive(Clone)]
+pub struct SharedRuleset(pub Arc<dyn Ruleset>);
+
+impl Default for SharedRuleset {
+    fn default() -> Self {
+        Self(Arc::new(RiichiRules))
+    }
+}
+
+/// The rules of riichi mahjong, see [crate::riichi].
+#[derive(Default, Clone, Copy)]
+pub struct RiichiRules;
+
+impl Ruleset for RiichiRules {
+    fn register(&self, cards: &mut Cards) {
+        crate::riichi::register(cards);
+    }
+
+    fn payment(
+        &self,
+        config: &RuleConfig,
+        fan: u16,
+        fu: u8,
+        winner: FengType,
+        dealer: FengType,
+        from: Option<FengType>,
+    ) -> Payment {
+        scoring::riichi_payment(config.base_points(fan, fu), winner, dealer, from)
+    }
+}
+
+/// The rules of riichi mahjong for three players, known as 三人麻将 or 三麻 in Chinese.
+///
+/// There is no 北 player, the wall has no 2 to 8 of 万,
+/// no player can call 吃, and a 北 can be set aside as a dora, see [Cards::nuki_dora].
+#[derive(Default, Clone, Copy)]
+pub struct SanmaRules;
+
+impl Ruleset for SanmaRules {
+    fn id(&self) -> RulesetId {
+        RulesetId::Sanma
+    }
+
+    fn copies(&self, card: CardType) -> u8 {
+        match card {
+            CardType::Wan(rank) if rank != RankType::One && rank != RankType::Nine => 0,
+            _ => 4,
+        }
+    }
+
+    fn players(&self) -> &'static [FengType] {
+        &[FengType::Dong, FengType::Nan, FengType::Xi]
+    }
+
+    fn allows_chi(&self) -> bool {
+        false
+    }
+
+    fn starting_score(&self) -> i32 {
+        35000
+    }
+
+    fn has_nuki_dora(&self) -> bool {
+        true
+    }
+
+    fn register(&self, cards: &mut Cards) {
+        crate::riichi::register(cards);
+    }
+
+    fn payment(
+        &self,
+        config: &RuleConfig,
+        fan: u16,
+        fu: u8,
+        winner: FengType,
+        dealer: FengType,
+        from: Option<FengType>,
+    ) -> Payment {
+        scoring::sanma_payment(config.base_points(fan, fu), winner, dealer, from)
+    }
+
+    fn noten_payments(&self, tenpai: &[FengType]) -> Vec<(FengType, i32)> {
+        scoring::sanma_noten_payments(tenpai)
+    }
+}
+
+/// The rules of 台湾麻将 with 16 cards in hand, known as 台湾十六张 in Chinese.
+///
+/// A complete hand needs five cases(面子) and a pair(雀头), see [crate::analysis::cases_needed],
+/// and the flower cards(花牌) are always in the wall.
+/// The 台 are paid as the fan of Chinese official mahjong, see [scoring::mcr_payment].
+#[derive(Default, Clone, Copy)]
+pub struct TaiwaneseRules;
+
+impl Ruleset for TaiwaneseRules {
+    fn id(&self) -> RulesetId {
+        RulesetId::Taiwanese
+    }
+
+    fn hand_size(&self) -> usize {
+        16
+    }
+
+    fn allows_riichi(&self) -> bool {
+        false
+    }
+
+    fn has_abortive_draws(&self) -> bool {
+        false
+    }
+
+    fn has_nagashi(&self) -> bool {
+        false
+    }
+
+    fn has_dora(&self) -> bool {
+        false
+    }
+
+    fn has_honba(&self) -> bool {
+        false
+    }
+
+    fn has_flowers(&self) -> bool {
+        true
+    }
+
+    fn register(&self, cards: &mut Cards) {
+        crate::taiwanese::register(cards);
+    }
+
+    fn payment(
+        &self,
+        _config: &RuleConfig,
+        fan: u16,
+        _fu: u8,
+        winner: FengType,
+        _dealer: FengType,
+        from: Option<FengType>,
+    ) -> Payment {
+        scoring::mcr_payment(fan, winner, from)
+    }
+
+    fn noten_payments(&self, _tenpai: &[FengType]) -> Vec<(FengType, i32)> {
+        Vec::new()
+    }
+}
+
+/// The rules of Hong Kong Old Style mahjong, see [crate::hkos].
+#[derive(Default, Clone, Copy)]
+pub struct HkosRules;
+
+impl Ruleset for HkosRules {
+    fn id(&self) -> RulesetId {
+        RulesetId::Hkos
+    }
+
+    fn allows_riichi(&self) -> bool {
+        false
+    }
+
+    fn has_abortive_draws(&self) -> bool {
+        false
+    }
+
+    fn has_nagashi(&self) -> bool {
+        false
+    }
+
+    fn has_dora(&self) -> bool {
+        false
+    }
+
+    fn has_honba(&self) -> bool {
+        false
+    }
+
+    fn starting_score(&self) -> i32 {
+        0
+    }
+
+    fn min_fan(&self) -> u16 {
+        3
+    }
+
+    fn register(&self, cards: &mut Cards) {
+        crate::hkos::register(cards);
+    }
+
+    fn payment(
+        &self,
+        _config: &RuleConfig,
+        fan: u16,
+        _fu: u8,
+        winner: FengType,
+        _dealer: FengType,
+        from: Option<FengType>,
+    ) -> Payment {
+        scoring::hkos_payment(fan, winner, from)
+    }
+
+    fn noten_payments(&self, _tenpai: &[FengType]) -> Vec<(FengType, i32)> {
+        Vec::new()
+    }
+}
+
+/// The rules of Chinese official mahjong, see [crate::mcr].
+#[derive(Default, Clone, Copy)]
+pub struct McrRules;
+
+impl Ruleset for McrRules {
+    fn id(&self) -> RulesetId {
+        RulesetId::Mcr
+    }
+
+    fn allows_riichi(&self) -> bool {
+        false
+    }
+
+    fn has_abortive_draws(&self) -> bool {
+        false
+    }
+
+    fn has_nagashi(&self) -> bool {
+        false
+    }
+
+    fn has_dora(&self) -> bool {
+        false
+    }
+
+    fn has_honba(&self) -> bool {
+        false
+    }
+
+    fn starting_score(&self) -> i32 {
+        0
+    }
+
+    fn min_fan(&self) -> u16 {
+        8
+    }
+
+    fn has_knitted_forms(&self) -> bool {
+        true
+    }
+
+    fn register(&self, cards: &mut Cards) {
+        crate::mcr::register(cards);
+    }
+
+    fn payment(
+        &self,
+        _config: &RuleConfig,
+        fan: u16,
+        _fu: u8,
+        winner: FengType,
+        _dealer: FengType,
+        from: Option<FengType>,
+    ) -> Payment {
+        scoring::mcr_payment(fan, winner, from)
+    }
+
+    fn noten_payments(&self, _tenpai: &[FengType]) -> Vec<(FengType, i32)> {
+        Vec::new()
+    }
+}
+
+/// The rules of 四川麻将, known as 血战到底 in Chinese.
+///
+/// The wall has no honors(字牌), and no player can call 吃.
+#[derive(Default, Clone, Copy)]
+pub struct SichuanRules;
+
+impl Ruleset for SichuanRules {
+    fn id(&self) -> RulesetId {
+        RulesetId::Sichuan
+    }
+
+    fn copies(&self, card: CardType) -> u8 {
+        match card {
+            CardType::Zi(_) => 0,
+            _ => 4,
+        }
+    }
+
+    fn allows_chi(&self) -> bool {
+        false
+    }
+
+    fn allows_riichi(&self) -> bool {
+        false
+    }
+
+    fn has_abortive_draws(&self) -> bool {
+        false
+    }
+
+    fn has_nagashi(&self) -> bool {
+        false
+    }
+
+    fn has_dora(&self) -> bool {
+        false
+    }
+
+    fn has_honba(&self) -> bool {
+        false
+    }
+
+    fn continues_after_completion(&self) -> bool {
+        true
+    }
+
+    fn register(&self, cards: &mut Cards) {
+        crate::sichuan::register(cards);
+    }
+
+    fn payment(
+        &self,
+        _config: &RuleConfig,
+        fan: u16,
+        _fu: u8,
+        winner: FengType,
+        _dealer: FengType,
+        from: Option<FengType>,
+    ) -> Payment {
+        scoring::sichuan_payment(fan, winner, from)
+    }
+
+    fn noten_payments(&self, _tenpai: &[FengType]) -> Vec<(FengType, i32)> {
+        Vec::new()
+    }
+}
+
+/// The rules of Zung Jung, see [crate::zung_jung].
+#[derive(Default, Clone, Copy)]
+pub struct ZungJungRules;
+
+impl Ruleset for ZungJungRules {
+    fn id(&self) -> RulesetId {
+        RulesetId::ZungJung
+    }
+
+    fn allows_riichi(&self) -> bool {
+        false
+    }
+
+    fn has_abortive_draws(&self) -> bool {
+        false
+    }
+
+    fn has_nagashi(&self) -> bool {
+        false
+    }
+
+    fn has_dora(&self) -> bool {
+        false
+    }
+
+    fn has_honba(&self) -> bool {
+        false
+    }
+
+    fn starting_score(&self) -> i32 {
+        0
+    }
+
+    fn register(&self, cards: &mut Cards) {
+        crate::zung_jung::register(cards);
+    }
+
+    fn payment(
+        &self,
+        _config: &RuleConfig,
+        fan: u16,
+        _fu: u8,
+        winner: FengType,
+        _dealer: FengType,
+        from: Option<FengType>,
+    ) -> Payment {
+        scoring::zung_jung_payment(fan, winner, from)
+    }
+
+    fn noten_payments(&self, _tenpai: &[FengType]) -> Vec<(FengType, i32)> {
+        Vec::new()
+    }
+}
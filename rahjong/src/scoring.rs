@@ -0,0 +1,341 @@
+//! Point payments of a completion(和牌), and their associated functions.
+//!
+//! Riichi mahjong pays by fan(番数) and fu(符), see [riichi_payment],
+//! while Chinese official mahjong pays by fan only, see [mcr_payment],
+//! and Hong Kong mahjong doubles the points by each faan(番), see [hkos_payment].
+//! Zung Jung adds up the points of the patterns up to a limit, see [zung_jung_points],
+//! and 血战到底 doubles the points by each fan, see [sichuan_payment].
+//! The payment is applied to the scores of players by [crate::cards::Cards::apply_payment],
+//! and comes with the completions, the fan and the fu in an [AgariResult], see [crate::cards::Cards::score_win].
+//! A drawn hand(荒牌流局) pays by drawing hand(听牌) instead, see [noten_payments].
+//! 三麻 adjusts both, see [sanma_payment] and [sanma_noten_payments].
+//! A player who let another complete 大三元 or 大四喜 by a call action pays for it, see [pao_payment].
+
+use crate::{
+    card_type::{FengType, Next},
+    hkos::HKOS_LIMIT_FAAN,
+    riichi::{DoraBreakdown, YAKUMAN_FAN},
+    zung_jung::ZUNG_JUNG_LIMIT,
+};
+
+/// The `Payment` type. Represents the points transferred from the payers to the winner.
+#[derive(PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Payment {
+    /// The player who completes.
+    pub winner: FengType,
+    /// The players paying and the points each of them pays.
+    pub payers: Vec<(FengType, u32)>,
+    /// The points of the riichi sticks(供托) the winner takes from the table.
+    pub deposit: u32,
+}
+
+impl Payment {
+    /// Returns the total points the winner receives, including the deposit.
+    pub fn total(&self) -> u32 {
+        self.payers.iter().map(|(_, p)| p).sum::<u32>() + self.deposit
+    }
+
+    /// Adds the bonus of the counters(本场) to the payment,
+    /// 300 points for each counter paid by the player who played the winning card,
+    /// or 100 points for each counter paid by every payer for 自摸.
+    pub fn add_honba(&mut self, honba: u8) {
+        let each = if self.payers.len() == 1 { 300 } else { 100 };
+        for (_, points) in &mut self.payers {
+            *points += each * honba as u32;
+        }
+    }
+}
+
+/// The `AgariResult` type. Represents the score of a completion(和牌), see [crate::cards::Cards::score_win].
+#[derive(PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AgariResult {
+    /// The player who completes.
+    pub winner: FengType,
+    /// The player who played the winning card(放铳), or `None` for 自摸.
+    pub from: Option<FengType>,
+    /// The names and the fan(番数) of the completions met, e.g. the yaku(役) of riichi mahjong.
+    pub completions: Vec<(String, u16)>,
+    /// The total fan of the completions, without dora.
+    pub fan: u16,
+    /// The dora(宝牌) of the hand, none in rules without, see [crate::ruleset::Ruleset::has_dora].
+    pub dora: DoraBreakdown,
+    /// The fu(符) of the hand, see [crate::riichi::fu], or zero if not counted.
+    pub fu: u8,
+    /// The payment of the completion without the counters(本场) and the riichi sticks(供托),
+    /// see [crate::game::Game::settle_win].
+    pub payment: Payment,
+}
+
+impl AgariResult {
+    /// Returns the total fan of the completion, i.e. the fan of the completions and the dora.
+    pub fn total_fan(&self) -> u16 {
+        self.fan + self.dora.total()
+    }
+
+    /// Returns the points the winner receives, see [Payment::total].
+    pub fn points(&self) -> u32 {
+        self.payment.total()
+    }
+}
+
+/// Returns the other players of the side, in the order of playing.
+fn others(side: FengType) -> [FengType; 3] {
+    [side.next(), side.next().next(), side.next().next().next()]
+}
+
+/// Rounds the points up to hundreds.
+fn round_up(points: u32) -> u32 {
+    points.div_ceil(100) * 100
+}
+
+/// Returns the base points(基本点) of riichi mahjong given fan(番数) and fu(符).
+///
+/// The base points are `fu * 2 ^ (fan + 2)`, limited as follows:
+/// 满贯 2000 from 5 fan or over 2000,
+/// 跳满 3000 from 6 fan,
+/// 倍满 4000 from 8 fan,
+/// 三倍满 6000 from 11 fan,
+/// and 役满 8000 from 13 fan, see [crate::riichi::YAKUMAN_FAN],
+/// once more for each other 13 fan, e.g. 16000 for 双倍役满 of 26 fan.
+pub fn base_points(fan: u16, fu: u8) -> u32 {
+    match fan {
+        0 => 0,
+        1..=4 => (fu as u32 * 2u32.pow(fan as u32 + 2)).min(2000),
+        5 => 2000,
+        6..=7 => 3000,
+        8..=10 => 4000,
+        11..=12 => 6000,
+        _ => 8000 * (fan / YAKUMAN_FAN) as u32,
+    }
+}
+
+/// Returns the payment of riichi mahjong given the base points, see [base_points].
+///
+/// For 荣和, `from` is the player who played the winning card(放铳) and pays all,
+/// 6 times the base points if the winner is the dealer(庄家), otherwise 4 times.
+/// For 自摸, `from` is `None` and every other player pays,
+/// 2 times the base points if either the payer or the winner is the dealer, otherwise 1 time.
+///
+/// Each payment is rounded up to hundreds.
+pub fn riichi_payment(
+    base: u32,
+    winner: FengType,
+    dealer: FengType,
+    from: Option<FengType>,
+) -> Payment {
+    let payers = match from {
+        Some(from) => {
+            let times = if winner == dealer { 6 } else { 4 };
+            vec![(from, round_up(base * times))]
+        }
+        None => others(winner)
+            .into_iter()
+            .map(|side| {
+                let times = if winner == dealer || side == dealer {
+                    2
+                } else {
+                    1
+                };
+                (side, round_up(base * times))
+            })
+            .collect(),
+    };
+    Payment {
+        winner,
+        payers,
+        deposit: 0,
+    }
+}
+
+/// Returns the payment of riichi mahjong in 三麻 given the base points, see [riichi_payment].
+///
+/// There is no 北 player to pay for 自摸, so the winner takes only the payments of the other two players,
+/// known as 自摸损 in Chinese.
+pub fn sanma_payment(
+    base: u32,
+    winner: FengType,
+    dealer: FengType,
+    from: Option<FengType>,
+) -> Payment {
+    let mut res = riichi_payment(base, winner, dealer, from);
+    res.payers.retain(|&(side, _)| side != FengType::Bei);
+    res
+}
+
+/// The completions a player can be liable for, known as 包牌 or pao in Chinese,
+/// see [crate::cards::Cards::pao].
+pub const PAO_COMPLETIONS: [&str; 2] = ["大三元", "大四喜"];
+
+/// Returns the payment of a completion with a player liable for it(包牌), see [PAO_COMPLETIONS],
+/// given the payment as if the liable player played the winning card,
+/// and the player who did, or `None` for 自摸.
+///
+/// For 自摸 the liable player pays all,
+/// while for 荣和 by another player the liable player and the player who played the winning card
+/// pay half each, rounded up to hundreds for the liable player.
+/// Either way the liable player pays for the whole completion, including any other completions met.
+pub fn pao_payment(payment: &Payment, from: Option<FengType>) -> Payment {
+    let mut res = payment.clone();
+    if let (Some(from), [(liable, points)]) = (from, payment.payers.as_slice()) {
+        if from != *liable {
+            let half = round_up(points / 2);
+            res.payers = vec![(*liable, half), (from, points - half)];
+        }
+    }
+    res
+}
+
+/// Returns the points each player gains or loses at the end of a drawn hand(荒牌流局),
+/// given the players in drawing hand(听牌) state, known as 不听罚符 in Chinese.
+///
+/// The players not in drawing hand pay 3000 points in total,
+/// shared equally by the players in drawing hand.
+/// Nothing is paid if all or none of the players are in drawing hand.
+pub fn noten_payments(tenpai: &[FengType]) -> Vec<(FengType, i32)> {
+    let n = FengType::ALL.iter().filter(|s| tenpai.contains(s)).count() as i32;
+    if n == 0 || n == 4 {
+        return Vec::new();
+    }
+    FengType::ALL
+        .into_iter()
+        .map(|side| {
+            let points = if tenpai.contains(&side) {
+                3000 / n
+            } else {
+                -3000 / (4 - n)
+            };
+            (side, points)
+        })
+        .collect()
+}
+
+/// Returns the points each player gains or loses at the end of a drawn hand(荒牌流局) in 三麻,
+/// given the players in drawing hand(听牌) state, see [noten_payments].
+///
+/// The players not in drawing hand pay 2000 points in total,
+/// shared equally by the players in drawing hand.
+/// Nothing is paid if all or none of the three players are in drawing hand.
+pub fn sanma_noten_payments(tenpai: &[FengType]) -> Vec<(FengType, i32)> {
+    let players = [FengType::Dong, FengType::Nan, FengType::Xi];
+    let n = players.iter().filter(|s| tenpai.contains(s)).count() as i32;
+    if n == 0 || n == 3 {
+        return Vec::new();
+    }
+    players
+        .into_iter()
+        .map(|side| {
+            let points = if tenpai.contains(&side) {
+                2000 / n
+            } else {
+                -2000 / (3 - n)
+            };
+            (side, points)
+        })
+        .collect()
+}
+
+/// Returns the payment of Chinese official mahjong given the fan(番数).
+///
+/// For 荣和, `from` is the player who played the winning card(放铳) and pays `8 + fan`,
+/// while the other players pay 8 each.
+/// For 自摸, `from` is `None` and every other player pays `8 + fan`.
+pub fn mcr_payment(fan: u16, winner: FengType, from: Option<FengType>) -> Payment {
+    let payers = others(winner)
+        .into_iter()
+        .map(|side| {
+            let points = if from.is_none_or(|from| from == side) {
+                8 + fan as u32
+            } else {
+                8
+            };
+            (side, points)
+        })
+        .collect();
+    Payment {
+        winner,
+        payers,
+        deposit: 0,
+    }
+}
+
+/// Returns the points of Hong Kong mahjong given the faan(番), known as 番数表 in Chinese,
+/// which double with each faan up to the limit(爆棚), see [HKOS_LIMIT_FAAN].
+///
+/// It is the half amount(半铳) of the payment, see [hkos_payment].
+pub fn hkos_points(faan: u16) -> u32 {
+    1 << faan.min(HKOS_LIMIT_FAAN)
+}
+
+/// Returns the payment of Hong Kong mahjong given the faan(番), known as 半铳 in Chinese.
+///
+/// For 荣和, `from` is the player who played the winning card(出铳) and pays twice the points,
+/// see [hkos_points], while the other players pay the points each.
+/// For 自摸, `from` is `None` and every other player pays twice the points.
+pub fn hkos_payment(faan: u16, winner: FengType, from: Option<FengType>) -> Payment {
+    let points = hkos_points(faan);
+    let payers = others(winner)
+        .into_iter()
+        .map(|side| {
+            let times = if from.is_none_or(|from| from == side) {
+                2
+            } else {
+                1
+            };
+            (side, points * times)
+        })
+        .collect();
+    Payment {
+        winner,
+        payers,
+        deposit: 0,
+    }
+}
+
+/// Returns the points of a Zung Jung hand given the total of its patterns,
+/// i.e. the fan(番数) of the completions, limited to [ZUNG_JUNG_LIMIT].
+pub fn zung_jung_points(total: u16) -> u32 {
+    total.min(ZUNG_JUNG_LIMIT) as u32
+}
+
+/// Returns the payment of a Zung Jung hand given the total of its patterns, see [zung_jung_points].
+///
+/// For 荣和, `from` is the player who played the winning card and pays the points alone,
+/// while for 自摸, `from` is `None` and every other player pays the points.
+pub fn zung_jung_payment(total: u16, winner: FengType, from: Option<FengType>) -> Payment {
+    let points = zung_jung_points(total);
+    let payers = match from {
+        Some(from) => vec![(from, points)],
+        None => others(winner)
+            .into_iter()
+            .map(|side| (side, points))
+            .collect(),
+    };
+    Payment {
+        winner,
+        payers,
+        deposit: 0,
+    }
+}
+
+/// Returns the payment of 血战到底 given the fan(番数), doubling the points with each fan.
+///
+/// For 荣和, `from` is the player who played the winning card(点炮) and pays the points alone,
+/// while for 自摸, `from` is `None` and every other player pays the points,
+/// of whom [crate::cards::Cards::score_win] excludes the players who have completed before.
+pub fn sichuan_payment(fan: u16, winner: FengType, from: Option<FengType>) -> Payment {
+    let points = 1 << fan.min(31);
+    let payers = match from {
+        Some(from) => vec![(from, points)],
+        None => others(winner)
+            .into_iter()
+            .map(|side| (side, points))
+            .collect(),
+    };
+    Payment {
+        winner,
+        payers,
+        deposit: 0,
+    }
+}
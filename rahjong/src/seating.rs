@@ -0,0 +1,89 @@
+//! Seating the players before the game and choosing the first dealer(起家),
+//! known as 定座 and 定庄 in Chinese.
+//!
+//! The core of this module is the [Seating] struct, chosen by [Seating::choose]
+//! and given to the game session by [crate::game::Game::with_seating].
+//! The players are numbered from 0 in the order they join,
+//! while the sides of the table are named after the winds, see [FengType].
+
+use std::collections::BTreeMap;
+
+use rand::{seq::SliceRandom, Rng};
+
+use crate::{card_type::FengType, dice::Dice};
+
+/// The `SeatingMethod` type. Represents how the players are seated.
+#[derive(PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SeatingMethod {
+    /// The players are seated at random and the 东 side is the first dealer, e.g. online.
+    #[default]
+    Random,
+    /// Each player draws a wind card face down and sits at the side of the wind, known as 抓位 in Chinese.
+    ///
+    /// The player at 东 then rolls the dice, and counting from 东 as one,
+    /// the player at the side the total reaches is the first dealer.
+    TileDraw,
+}
+
+/// The struct of the seats of the players and the first dealer(起家) of a game.
+#[derive(PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Seating {
+    /// The player sitting at each side, numbered in the order of joining.
+    pub seats: BTreeMap<FengType, usize>,
+    /// The dealer of the first hand, known as 起家 in Chinese.
+    pub first_dealer: FengType,
+    /// The dice rolled to choose the first dealer, or `None` if not rolled, see [SeatingMethod::TileDraw].
+    pub dice: Option<Dice>,
+}
+
+impl Seating {
+    /// Returns the seating of the players in the order of joining at the sides in the order of playing,
+    /// where the 东 side is the first dealer.
+    pub fn fixed(players: &[FengType]) -> Self {
+        Self {
+            seats: players
+                .iter()
+                .enumerate()
+                .map(|(i, &side)| (side, i))
+                .collect(),
+            first_dealer: FengType::Dong,
+            dice: None,
+        }
+    }
+
+    /// Seats the players at the sides given in the order of playing by the method,
+    /// using the random number generator given.
+    pub fn choose<R: Rng + ?Sized>(
+        method: SeatingMethod,
+        players: &[FengType],
+        rng: &mut R,
+    ) -> Self {
+        let mut sides = players.to_vec();
+        sides.shuffle(rng);
+        let mut res = Self {
+            seats: sides
+                .into_iter()
+                .enumerate()
+                .map(|(i, side)| (side, i))
+                .collect(),
+            first_dealer: FengType::Dong,
+            dice: None,
+        };
+        if method == SeatingMethod::TileDraw && !players.is_empty() {
+            let dice = Dice::roll(rng);
+            res.first_dealer = players[(dice.total() as usize - 1) % players.len()];
+            res.dice = Some(dice);
+        }
+        res
+    }
+
+    /// Returns the side the player sits at, or `None` if not seated.
+    pub fn side_of(&self, player: usize) -> Option<FengType> {
+        self.seats
+            .iter()
+            .find(|&(_, &p)| p == player)
+            .map(|(&side, _)| side)
+    }
+}
@@ -0,0 +1,235 @@
+//! Lookup tables of suit shapes, making the standard shanten number(向听数) table-driven.
+//!
+//! The cases(面子), partial cases(搭子) and pairs(雀头) never span two suits,
+//! so a standard hand is scored suit by suit: each suit is looked up by its counts,
+//! and the four results, i.e. 万, 条, 筒 and the honors(字牌) in the order of [crate::card_type::CardType::index], are combined.
+//! A shape is searched only the first time it is seen and kept for the whole process,
+//! and far fewer shapes of a suit turn up than hands,
+//! so a few lookups soon replace the search over the whole hand.
+//!
+//! [crate::analysis] works on these tables, e.g. [crate::analysis::shanten],
+//! as does [crate::packed_hand::PackedHand].
+
+use std::{
+    collections::HashMap,
+    sync::{OnceLock, PoisonError, RwLock},
+};
+
+use crate::{analysis, tile_counts::TileCounts};
+
+/// The most cases(面子) of a complete hand, i.e. five in 台湾麻将, see [analysis::cases_needed].
+const MAX_CASES: usize = 5;
+
+/// The most partial cases(搭子) of a suit, indexed by whether a pair(雀头) is taken
+/// and then by the number of cases, or `-1` if the suit can not be split so.
+#[derive(Clone, Copy)]
+struct Shape([[i8; MAX_CASES + 1]; 2]);
+
+impl Shape {
+    /// The shape no split has been found for.
+    const NONE: Shape = Shape([[-1; MAX_CASES + 1]; 2]);
+
+    /// Combines the shapes of two disjoint sets of cards.
+    fn merge(&self, other: &Shape) -> Shape {
+        let mut res = Shape::NONE;
+        for (p, q) in [(0, 0), (0, 1), (1, 0)] {
+            for m in 0..=MAX_CASES {
+                for n in 0..=MAX_CASES - m {
+                    let (a, b) = (self.0[p][m], other.0[q][n]);
+                    if a >= 0 && b >= 0 {
+                        let best = &mut res.0[p + q][m + n];
+                        *best = (*best).max(a + b);
+                    }
+                }
+            }
+        }
+        res
+    }
+}
+
+/// The table of the shapes searched, keyed by [key].
+static TABLE: OnceLock<RwLock<HashMap<u32, Shape>>> = OnceLock::new();
+
+/// Returns the key of the counts of a suit, 3 bits a card,
+/// with the highest bit telling whether the suit forms 顺子, i.e. is not the honors.
+fn key(counts: &[u8], shun: bool) -> u32 {
+    counts
+        .iter()
+        .fold(shun as u32, |key, &n| key << 3 | n as u32)
+}
+
+/// Returns the shape of the counts of a suit, searching it if not in the table yet.
+fn shape(counts: &[u8], shun: bool) -> Shape {
+    let key = key(counts, shun);
+    let table = TABLE.get_or_init(Default::default);
+    if let Some(&shape) = table
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(&key)
+    {
+        return shape;
+    }
+    let mut search = SuitSearch {
+        counts: [0; 9],
+        len: counts.len(),
+        shun,
+        cases: 0,
+        partials: 0,
+        pair: false,
+        shape: Shape::NONE,
+    };
+    search.counts[..counts.len()].copy_from_slice(counts);
+    search.search(0);
+    table
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(key, search.shape);
+    search.shape
+}
+
+/// Returns the shapes of 万, 条, 筒 and the honors(字牌) in the counts, see [crate::card_type::CardType::index].
+fn shapes(counts: &TileCounts) -> [Shape; 4] {
+    [
+        shape(&counts.0[..9], true),
+        shape(&counts.0[9..18], true),
+        shape(&counts.0[18..27], true),
+        shape(&counts.0[27..], false),
+    ]
+}
+
+/// The state of searching the shape of a suit.
+struct SuitSearch {
+    /// The counts of the suit, of which the first `len` are used.
+    counts: [u8; 9],
+    /// The number of cards in the suit, 9 for numbers and 7 for honors.
+    len: usize,
+    /// Whether the cards form 顺子.
+    shun: bool,
+    /// The number of cases(面子) taken.
+    cases: usize,
+    /// The number of partial cases(搭子) taken.
+    partials: i8,
+    /// Whether a pair(雀头) has been taken.
+    pair: bool,
+    /// The shape found.
+    shape: Shape,
+}
+
+impl SuitSearch {
+    /// Searches from the index, taking cases, pairs and partial cases in turn.
+    fn search(&mut self, mut i: usize) {
+        while i < self.len && self.counts[i] == 0 {
+            i += 1;
+        }
+        if i == self.len {
+            if self.cases <= MAX_CASES {
+                let best = &mut self.shape.0[self.pair as usize][self.cases];
+                *best = (*best).max(self.partials);
+            }
+            return;
+        }
+        let shun = self.shun && i + 2 < self.len;
+        let near = self.shun && i + 1 < self.len;
+        if self.counts[i] >= 3 {
+            self.counts[i] -= 3;
+            self.cases += 1;
+            self.search(i);
+            self.cases -= 1;
+            self.counts[i] += 3;
+        }
+        if shun && self.counts[i + 1] > 0 && self.counts[i + 2] > 0 {
+            self.take(&[i, i + 1, i + 2]);
+            self.cases += 1;
+            self.search(i);
+            self.cases -= 1;
+            self.give(&[i, i + 1, i + 2]);
+        }
+        if self.counts[i] >= 2 {
+            self.counts[i] -= 2;
+            if !self.pair {
+                self.pair = true;
+                self.search(i);
+                self.pair = false;
+            }
+            self.partials += 1;
+            self.search(i);
+            self.partials -= 1;
+            self.counts[i] += 2;
+        }
+        if near && self.counts[i + 1] > 0 {
+            self.take(&[i, i + 1]);
+            self.partials += 1;
+            self.search(i);
+            self.partials -= 1;
+            self.give(&[i, i + 1]);
+        }
+        if shun && self.counts[i + 2] > 0 {
+            self.take(&[i, i + 2]);
+            self.partials += 1;
+            self.search(i);
+            self.partials -= 1;
+            self.give(&[i, i + 2]);
+        }
+        self.counts[i] -= 1;
+        self.search(i);
+        self.counts[i] += 1;
+    }
+
+    /// Takes one card of each index.
+    fn take(&mut self, indices: &[usize]) {
+        for &i in indices {
+            self.counts[i] -= 1;
+        }
+    }
+
+    /// Gives back one card of each index.
+    fn give(&mut self, indices: &[usize]) {
+        for &i in indices {
+            self.counts[i] += 1;
+        }
+    }
+}
+
+/// Returns the shanten number(向听数) of standard hands, i.e. four cases(面子) and a pair(雀头),
+/// of the counts with the number of cases in open,
+/// or five cases if the counts and the open hold 16 or 17 cards, see [analysis::cases_needed].
+///
+/// Unlike [crate::analysis::shanten_breakdown],
+/// a hand only waiting for a card of which it contains all four(空听) is counted as in drawing hand(听牌).
+pub fn standard_shanten(counts: &TileCounts, melds: usize) -> i8 {
+    let [a, b, c, d] = shapes(counts);
+    let shape = a.merge(&b).merge(&c).merge(&d);
+    let needed = analysis::cases_needed(counts.total() + 3 * melds) as i8;
+    let mut best = 2 * needed;
+    for (pair, row) in shape.0.iter().enumerate() {
+        for (cases, &partials) in row.iter().enumerate() {
+            let cases = (cases + melds) as i8;
+            if partials < 0 || cases > needed {
+                continue;
+            }
+            best = best.min(2 * needed - 2 * cases - partials.min(needed - cases) - pair as i8);
+        }
+    }
+    best
+}
+
+/// Checks if the counts split into cases(面子) and exactly one pair(雀头), leaving no card.
+pub fn is_standard_complete(counts: &TileCounts) -> bool {
+    let mut pairs = 0;
+    for (i, shape) in shapes(counts).iter().enumerate() {
+        let total: usize = counts.0[9 * i..(9 * i + 9).min(counts.0.len())]
+            .iter()
+            .map(|&n| n as usize)
+            .sum();
+        let pair = match total % 3 {
+            0 => 0,
+            2 => 1,
+            _ => return false,
+        };
+        pairs += pair;
+        if total / 3 > MAX_CASES || shape.0[pair][total / 3] < 0 {
+            return false;
+        }
+    }
+    pairs == 1
+}
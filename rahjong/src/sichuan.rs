@@ -0,0 +1,92 @@
+//! Built-in situations and completions of Sichuan mahjong(四川麻将), known as 血战到底.
+//!
+//! The core of this module is [register], which fills the checkers of a [Cards]
+//! with the common 番 of 血战到底, see also [Cards::with_sichuan_rules].
+//! The wall has only the three number suits, no player can call 吃,
+//! and each player gives up a suit before the first draw, see [Cards::declare_void].
+//! The hand goes on after a player completes until three of them have, see [Cards::finished].
+//!
+//! Every complete hand can win, counting 平胡 as a completion of no 番,
+//! so [crate::ruleset::Ruleset::min_fan] stays zero. Each 番 doubles the points in common rules.
+//!
+//! Situation checkers see the hand, the river and the open of a player
+//! together with the last card and where it comes from, see [WinContext],
+//! so the 番 depending on the course of the hand, e.g. 杠上炮, 天胡 or 地胡, are not included.
+
+use std::collections::HashMap;
+
+use crate::{
+    analysis::{all_cards, all_cases, winning_decompositions},
+    card_type::CardType,
+    cards::{Cards, Completion, Hand, Open, River, SituationChecker, SituationFn, WinContext},
+    completion, situations,
+};
+
+/// 带幺九: every case and the pair contain a 1 or a 9.
+fn dai_yao_jiu(hand: &Hand, _: &River, open: &Open, draw: CardType, context: &WinContext) -> bool {
+    winning_decompositions(hand, open, draw, context)
+        .iter()
+        .any(|d| {
+            d.pair.is_yao_jiu()
+                && all_cases(d, open)
+                    .iter()
+                    .all(|c| c.cards().iter().any(CardType::is_yao_jiu))
+        })
+}
+
+/// 金钩钓: four cases in open, completing with the pair.
+fn jin_gou_diao(_: &Hand, _: &River, open: &Open, _: CardType, _: &WinContext) -> bool {
+    open.len() == 4
+}
+
+/// 根: four of a card in the winning hand, in hand or in open.
+fn gen(hand: &Hand, _: &River, open: &Open, draw: CardType, _: &WinContext) -> bool {
+    let mut counts = Hand::new();
+    for card in all_cards(hand, open, draw) {
+        *counts.entry(card).or_default() += 1;
+    }
+    counts.values().any(|&n| n == 4)
+}
+
+/// Returns the situation checkers of 血战到底, named after the 番 in Chinese.
+pub fn situation_checkers() -> HashMap<&'static str, SituationChecker> {
+    let checkers: [(&'static str, SituationFn); 10] = [
+        ("对对胡", situations::dui_dui_he),
+        ("清一色", situations::qing_yi_se),
+        ("七对", situations::qi_dui_zi),
+        ("带幺九", dai_yao_jiu),
+        ("金钩钓", jin_gou_diao),
+        ("根", gen),
+        ("杠上开花", situations::ling_shang_kai_hua),
+        ("抢杠", situations::qiang_gang),
+        ("海底捞月", situations::hai_di_mo_yue),
+        ("海底炮", situations::he_di_lao_yu),
+    ];
+    situations::collect_checkers(checkers)
+}
+
+/// Returns the completions of 血战到底, i.e. the 番 as the fan(番数).
+///
+/// 平胡 requires no situation, so any complete hand meets it.
+/// 根 counts once however many fours the hand has.
+pub fn completions() -> Vec<Completion> {
+    vec![
+        completion!("平胡", 0),
+        completion!("对对胡", 1, requires["对对胡"]),
+        completion!("清一色", 2, requires["清一色"]),
+        completion!("七对", 2, requires["七对"]),
+        completion!("带幺九", 2, requires["带幺九"]),
+        completion!("金钩钓", 1, requires["金钩钓"]),
+        completion!("根", 1, requires["根"]),
+        completion!("杠上开花", 1, requires["杠上开花"]),
+        completion!("抢杠", 1, requires["抢杠"]),
+        completion!("海底捞月", 1, requires["海底捞月"]),
+        completion!("海底炮", 1, requires["海底炮"]),
+    ]
+}
+
+/// Registers the situations and completions of 血战到底 into the cards.
+pub fn register(cards: &mut Cards) {
+    cards.situation_checkers.extend(situation_checkers());
+    cards.completion_checkers.extend(completions());
+}
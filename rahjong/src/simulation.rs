@@ -0,0 +1,219 @@
+//! Monte Carlo simulation(蒙特卡洛模拟) of the rest of a hand(局) from the state shown to a player.
+//!
+//! Each game of [simulate] deals the cards not shown at random, see [Cards::sample_from_view],
+//! and plays the hand to its end by the policy for every player,
+//! so the results estimate how likely the player completes or deals in(放铳) from here.
+//!
+//! With the `rayon` feature, [self_play_batch] plays many whole hands by the same policy in parallel,
+//! e.g. to balance a rule set or evaluate a bot.
+
+use rand::Rng;
+
+use crate::{
+    bot::Bot,
+    card_type::FengType,
+    cards::Cards,
+    engine::{Engine, HandEnd},
+    game::{Game, GameLength},
+    view::PlayerView,
+};
+
+/// The maximum number of actions in a game, in case the policy never ends the hand.
+pub const MAX_ACTIONS: usize = 2000;
+
+/// The struct counting the results of the games simulated for a player.
+#[derive(Default, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Estimate {
+    /// The number of games simulated.
+    pub games: usize,
+    /// The number of games the player completes.
+    pub wins: usize,
+    /// The number of games the player deals in(放铳), i.e. plays the card another player completes with.
+    pub deal_ins: usize,
+    /// The number of games another player completes by 自摸.
+    pub tsumo_losses: usize,
+    /// The number of games drawn(流局).
+    pub draws: usize,
+}
+
+impl Estimate {
+    /// Returns the rate of the games the player completes.
+    pub fn win_rate(&self) -> f64 {
+        self.rate(self.wins)
+    }
+
+    /// Returns the rate of the games the player deals in(放铳).
+    pub fn deal_in_rate(&self) -> f64 {
+        self.rate(self.deal_ins)
+    }
+
+    /// Returns the rate of the games drawn(流局).
+    pub fn draw_rate(&self) -> f64 {
+        self.rate(self.draws)
+    }
+
+    /// Returns the rate of the number of games, or zero if no game is simulated.
+    fn rate(&self, n: usize) -> f64 {
+        if self.games == 0 {
+            0.0
+        } else {
+            n as f64 / self.games as f64
+        }
+    }
+
+    /// Adds the results counted by another estimate.
+    pub fn merge(&mut self, other: &Estimate) {
+        self.games += other.games;
+        self.wins += other.wins;
+        self.deal_ins += other.deal_ins;
+        self.tsumo_losses += other.tsumo_losses;
+        self.draws += other.draws;
+    }
+
+    /// Counts the result of a game for the player.
+    pub fn record(&mut self, side: FengType, result: Option<&HandEnd>) {
+        self.games += 1;
+        match result {
+            Some(HandEnd::Tsumo(winner)) if *winner == side => self.wins += 1,
+            Some(HandEnd::Tsumo(_)) => self.tsumo_losses += 1,
+            Some(HandEnd::Ron { winners, .. }) if winners.contains(&side) => self.wins += 1,
+            Some(HandEnd::Ron { from, .. }) if *from == side => self.deal_ins += 1,
+            Some(HandEnd::Ron { .. }) => {}
+            Some(HandEnd::Draw(_)) | None => self.draws += 1,
+        }
+    }
+}
+
+/// Simulates `n` games from the state shown to the player, see [simulate_with_rng],
+/// with the cards not shown dealt by [rand::thread_rng].
+pub fn simulate<P: Bot>(view: &PlayerView, policy: &mut P, n: usize) -> Estimate {
+    simulate_with_rng(view, policy, n, &mut rand::thread_rng())
+}
+
+/// Simulates `n` games from the state shown to the player,
+/// each with the cards not shown dealt at random, see [Cards::sample_from_view],
+/// and played to the end by the policy for every player under the rules of the view,
+/// see [PlayerView::rules] and [crate::ruleset::Ruleset::register].
+///
+/// Returns the results counted for the player.
+pub fn simulate_with_rng<P: Bot, R: Rng + ?Sized>(
+    view: &PlayerView,
+    policy: &mut P,
+    n: usize,
+    rng: &mut R,
+) -> Estimate {
+    let mut res = Estimate::default();
+    for _ in 0..n {
+        let mut cards = Cards::sample_from_view(view, rng);
+        view.rules.ruleset().register(&mut cards);
+        cards.config.multiple_ron = true;
+        let mut engine = Engine::new(Game::new(cards, GameLength::default()));
+        play_out(&mut engine, policy);
+        res.record(view.side, engine.result.as_ref());
+    }
+    res
+}
+
+/// Plays the hand to its end by the policy for every player.
+///
+/// The hand is left going on if it is still after [MAX_ACTIONS] actions.
+pub fn play_out<P: Bot>(engine: &mut Engine, policy: &mut P) {
+    for _ in 0..MAX_ACTIONS {
+        if engine.result.is_some() {
+            return;
+        }
+        let acting = match &engine.claims {
+            Some(_) => FengType::ALL
+                .into_iter()
+                .find(|side| !engine.responded.contains(side)),
+            None => Some(engine.cards().active_player),
+        };
+        let Some(side) = acting else {
+            return;
+        };
+        let actions = engine.legal_actions(side);
+        if actions.is_empty() {
+            return;
+        }
+        let action = policy.choose_action(&engine.cards().view(side), &actions);
+        if !engine.apply(side, action) {
+            engine.apply(side, actions[0]);
+        }
+    }
+}
+
+/// The struct aggregating the results of hands played by [self_play_batch].
+#[derive(Default, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BatchStats {
+    /// The number of hands played.
+    pub games: usize,
+    /// The results counted for each player, in the order of players.
+    pub seats: [Estimate; 4],
+    /// The number of hands won by 自摸.
+    pub tsumo: usize,
+    /// The number of hands won by 荣和.
+    pub ron: usize,
+    /// The number of hands drawn(流局), including those cut at [MAX_ACTIONS].
+    pub draws: usize,
+    /// The total number of actions applied, passes included.
+    pub actions: usize,
+}
+
+impl BatchStats {
+    /// Counts the hand the engine has run.
+    pub fn record(&mut self, engine: &Engine) {
+        self.games += 1;
+        for (seat, side) in self.seats.iter_mut().zip(FengType::ALL) {
+            seat.record(side, engine.result.as_ref());
+        }
+        match engine.result {
+            Some(HandEnd::Tsumo(_)) => self.tsumo += 1,
+            Some(HandEnd::Ron { .. }) => self.ron += 1,
+            Some(HandEnd::Draw(_)) | None => self.draws += 1,
+        }
+        self.actions += engine
+            .events
+            .iter()
+            .filter(|e| e.action().is_some())
+            .count();
+    }
+
+    /// Adds the results aggregated by another batch.
+    pub fn merge(mut self, other: BatchStats) -> BatchStats {
+        self.games += other.games;
+        for (seat, other) in self.seats.iter_mut().zip(&other.seats) {
+            seat.merge(other);
+        }
+        self.tsumo += other.tsumo;
+        self.ron += other.ron;
+        self.draws += other.draws;
+        self.actions += other.actions;
+        self
+    }
+}
+
+/// Plays `games` hands of riichi mahjong in parallel, each by a clone of the policy for every player,
+/// and aggregates the results, behind the `rayon` feature.
+///
+/// The wall of the hand numbered `i` is built from the seed `seed + i`, see [Cards::new_with_seed],
+/// so a batch is reproducible whatever the number of threads is.
+#[cfg(feature = "rayon")]
+pub fn self_play_batch<P: Bot + Clone + Sync>(games: usize, seed: u64, policy: &P) -> BatchStats {
+    use rayon::prelude::*;
+
+    (0..games as u64)
+        .into_par_iter()
+        .map(|i| {
+            let mut cards = Cards::new_with_seed(seed.wrapping_add(i));
+            crate::riichi::register(&mut cards);
+            cards.config.multiple_ron = true;
+            let mut engine = Engine::new(Game::new(cards, GameLength::default()));
+            play_out(&mut engine, &mut policy.clone());
+            let mut stats = BatchStats::default();
+            stats.record(&engine);
+            stats
+        })
+        .reduce(BatchStats::default, BatchStats::merge)
+}
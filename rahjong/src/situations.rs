@@ -0,0 +1,231 @@
+//! Named situation checkers shared by the rules, and the standard set of them.
+//!
+//! The checkers here only look at the cards and where the last card comes from, see [WinContext],
+//! so they mean the same in every variant of mahjong,
+//! and the rule modules use them under their own names, e.g. 门清 of [crate::taiwanese] is [men_qian_qing].
+//! [standard_checkers] names them in Chinese,
+//! and [register_standard] adds them all into [Cards::situation_checkers] at once,
+//! so a custom set of completions only needs to add the situations of its own.
+//!
+//! Like every situation checker, each checks if there is an interpretation of the hand meeting it.
+//! The rule modules also share the helpers here, such as `has_triplet` and the 三元牌 and 风牌 in order.
+
+use std::collections::HashMap;
+
+use crate::{
+    analysis::{
+        all_cards, all_cases, complete_hand, winning_decompositions, winning_forms, FormType,
+    },
+    card_type::{CardType, FengType, JianType, RankType, SuitType, ZiType},
+    cards::{CardSource, Cards, Hand, Open, River, SituationChecker, SituationFn, WinContext},
+    case_type::CaseType,
+};
+
+/// The 三元牌.
+pub(crate) const JIANS: [CardType; 3] = [
+    CardType::Zi(ZiType::Jian(JianType::Bai)),
+    CardType::Zi(ZiType::Jian(JianType::Fa)),
+    CardType::Zi(ZiType::Jian(JianType::Zhong)),
+];
+
+/// The 风牌.
+pub(crate) const FENGS: [CardType; 4] = [
+    CardType::Zi(ZiType::Feng(FengType::Dong)),
+    CardType::Zi(ZiType::Feng(FengType::Nan)),
+    CardType::Zi(ZiType::Feng(FengType::Xi)),
+    CardType::Zi(ZiType::Feng(FengType::Bei)),
+];
+
+/// Checks if the player holds a triplet of the card, in hand or in open.
+pub(crate) fn has_triplet(hand: &Hand, open: &Open, draw: CardType, card: CardType) -> bool {
+    let (hand, _) = complete_hand(hand, open, draw);
+    hand.get(&card).copied().unwrap_or(0) >= 3
+        || open.iter().any(|&case| {
+            matches!(case, CaseType::Ke(c) | CaseType::Gang(c) | CaseType::AnGang(c) if c == card)
+        })
+}
+
+/// Returns the number of triplets and pairs of the cards the player holds,
+/// given the cards, in hand or in open.
+pub(crate) fn triplets_and_pairs(
+    hand: &Hand,
+    open: &Open,
+    draw: CardType,
+    cards: &[CardType],
+) -> (usize, usize) {
+    let (complete, _) = complete_hand(hand, open, draw);
+    let triplets = cards
+        .iter()
+        .filter(|&&c| has_triplet(hand, open, draw, c))
+        .count();
+    let pairs = cards
+        .iter()
+        .filter(|c| complete.get(c).copied().unwrap_or(0) == 2)
+        .count();
+    (triplets, pairs)
+}
+
+/// Returns the suits in the winning hand, sorted.
+pub(crate) fn suits(hand: &Hand, open: &Open, draw: CardType) -> Vec<SuitType> {
+    let mut res: Vec<_> = all_cards(hand, open, draw)
+        .iter()
+        .map(CardType::suit)
+        .collect();
+    res.sort_unstable();
+    res.dedup();
+    res
+}
+
+/// 门前清: no call action has been done, 暗杠 excluded.
+pub fn men_qian_qing(_: &Hand, _: &River, open: &Open, _: CardType, _: &WinContext) -> bool {
+    !open.iter().any(CaseType::is_open)
+}
+
+/// 副露: any call action has been done, 暗杠 excluded.
+pub fn fu_lu(_: &Hand, _: &River, open: &Open, _: CardType, _: &WinContext) -> bool {
+    open.iter().any(CaseType::is_open)
+}
+
+/// 自摸: the last card has been drawn.
+pub fn zi_mo(hand: &Hand, _: &River, open: &Open, draw: CardType, _: &WinContext) -> bool {
+    complete_hand(hand, open, draw).1
+}
+
+/// 荣和: the last card has been played or added to an 加杠 by another player.
+pub fn rong_he(hand: &Hand, _: &River, open: &Open, draw: CardType, _: &WinContext) -> bool {
+    !complete_hand(hand, open, draw).1
+}
+
+/// 断幺九: simples only.
+pub fn duan_yao_jiu(hand: &Hand, _: &River, open: &Open, draw: CardType, _: &WinContext) -> bool {
+    all_cards(hand, open, draw).iter().all(CardType::is_simple)
+}
+
+/// 混老头: terminals and honors only, with both of them.
+pub fn hun_lao_tou(hand: &Hand, _: &River, open: &Open, draw: CardType, _: &WinContext) -> bool {
+    let cards = all_cards(hand, open, draw);
+    cards.iter().all(CardType::is_yao_jiu)
+        && cards.iter().any(|c| c.rank().is_some())
+        && cards.iter().any(|c| c.rank().is_none())
+}
+
+/// 清老头: terminals only, without honors.
+pub fn qing_lao_tou(hand: &Hand, _: &River, open: &Open, draw: CardType, _: &WinContext) -> bool {
+    all_cards(hand, open, draw)
+        .iter()
+        .all(|c| matches!(c.rank(), Some(RankType::One | RankType::Nine)))
+}
+
+/// 字一色: honors only.
+pub fn zi_yi_se(hand: &Hand, _: &River, open: &Open, draw: CardType, _: &WinContext) -> bool {
+    suits(hand, open, draw) == [SuitType::Zi]
+}
+
+/// 混一色: one number suit with honors.
+pub fn hun_yi_se(hand: &Hand, _: &River, open: &Open, draw: CardType, _: &WinContext) -> bool {
+    let suits = suits(hand, open, draw);
+    suits.len() == 2 && suits.contains(&SuitType::Zi)
+}
+
+/// 清一色: one number suit without honors.
+pub fn qing_yi_se(hand: &Hand, _: &River, open: &Open, draw: CardType, _: &WinContext) -> bool {
+    let suits = suits(hand, open, draw);
+    suits.len() == 1 && suits[0] != SuitType::Zi
+}
+
+/// 对对和: 刻子 and 杠子 only.
+pub fn dui_dui_he(
+    hand: &Hand,
+    _: &River,
+    open: &Open,
+    draw: CardType,
+    context: &WinContext,
+) -> bool {
+    winning_decompositions(hand, open, draw, context)
+        .iter()
+        .any(|d| {
+            all_cases(d, open)
+                .iter()
+                .all(|c| !matches!(c, CaseType::Shun(_)))
+        })
+}
+
+/// 七对子: seven different pairs.
+pub fn qi_dui_zi(
+    hand: &Hand,
+    _: &River,
+    open: &Open,
+    draw: CardType,
+    context: &WinContext,
+) -> bool {
+    winning_forms(hand, open, draw, context)
+        .iter()
+        .any(|f| matches!(f, FormType::QiDui(_)))
+}
+
+/// 岭上开花: the last card is a replacement card(岭上牌) drawn after a 杠.
+pub fn ling_shang_kai_hua(
+    _: &Hand,
+    _: &River,
+    _: &Open,
+    _: CardType,
+    context: &WinContext,
+) -> bool {
+    context.source == CardSource::Replacement
+}
+
+/// 海底摸月: the last card is the last one drawn from the mountain.
+pub fn hai_di_mo_yue(_: &Hand, _: &River, _: &Open, _: CardType, context: &WinContext) -> bool {
+    context.source == CardSource::Mountain && context.is_last
+}
+
+/// 河底捞鱼: the last card is the last one played in the hand.
+pub fn he_di_lao_yu(_: &Hand, _: &River, _: &Open, _: CardType, context: &WinContext) -> bool {
+    context.source == CardSource::River && context.is_last
+}
+
+/// 抢杠和: the last card is added to an 加杠 by another player.
+pub fn qiang_gang(_: &Hand, _: &River, _: &Open, _: CardType, context: &WinContext) -> bool {
+    context.source == CardSource::Robbed
+}
+
+/// Collects the plain functions given into situation checkers by their names.
+pub fn collect_checkers(
+    checkers: impl IntoIterator<Item = (&'static str, SituationFn)>,
+) -> HashMap<&'static str, SituationChecker> {
+    checkers
+        .into_iter()
+        .map(|(name, f)| (name, Box::new(f) as SituationChecker))
+        .collect()
+}
+
+/// Returns the standard situation checkers, named in Chinese.
+pub fn standard_checkers() -> HashMap<&'static str, SituationChecker> {
+    let checkers: [(&'static str, SituationFn); 16] = [
+        ("门前清", men_qian_qing),
+        ("副露", fu_lu),
+        ("自摸", zi_mo),
+        ("荣和", rong_he),
+        ("断幺九", duan_yao_jiu),
+        ("混老头", hun_lao_tou),
+        ("清老头", qing_lao_tou),
+        ("字一色", zi_yi_se),
+        ("混一色", hun_yi_se),
+        ("清一色", qing_yi_se),
+        ("对对和", dui_dui_he),
+        ("七对子", qi_dui_zi),
+        ("岭上开花", ling_shang_kai_hua),
+        ("海底摸月", hai_di_mo_yue),
+        ("河底捞鱼", he_di_lao_yu),
+        ("抢杠和", qiang_gang),
+    ];
+    collect_checkers(checkers)
+}
+
+/// Registers the standard situation checkers into the cards, see [standard_checkers],
+/// keeping the checkers already registered under the same names.
+pub fn register_standard(cards: &mut Cards) {
+    for (name, checker) in standard_checkers() {
+        cards.situation_checkers.entry(name).or_insert(checker);
+    }
+}
@@ -0,0 +1,128 @@
+//! The final standings of a game session, with uma(顺位马) and oka(头名奖), and their associated functions.
+//!
+//! The core of this module is [crate::game::Game::final_result],
+//! which ranks the players by points, breaking ties by the seats from the first dealer(起家),
+//! and adjusts the points by the [UmaOka] given, e.g. for tournaments and leagues.
+
+use crate::{card_type::FengType, game::RIICHI_DEPOSIT, scoring::Payment, seating::Seating};
+
+/// The struct of the adjustments of the final points by placement.
+#[derive(PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UmaOka {
+    /// The points each player starts with, known as 配给原点 in Chinese.
+    pub starting: i32,
+    /// The points the final points are counted from, known as 原点 or 返点 in Chinese.
+    ///
+    /// The players pay the difference from [UmaOka::starting] to the first place, known as 头名奖 or oka.
+    pub returning: i32,
+    /// The points added to each place from the first, known as 顺位马 or uma, usually adding up to zero.
+    ///
+    /// Places without a value get nothing.
+    pub uma: Vec<i32>,
+    /// Whether the riichi sticks(供托) left on the table go to the first place.
+    pub sticks_to_first: bool,
+}
+
+impl Default for UmaOka {
+    /// Returns the common rules of 25000 points returned at 30000, with the uma of 10 and 20 thousand.
+    fn default() -> Self {
+        Self {
+            starting: 25000,
+            returning: 30000,
+            uma: vec![20000, 10000, -10000, -20000],
+            sticks_to_first: true,
+        }
+    }
+}
+
+impl UmaOka {
+    /// Returns the oka(头名奖), i.e. the points the first place takes given the number of players.
+    pub fn oka(&self, players: usize) -> i32 {
+        (self.returning - self.starting) * players as i32
+    }
+}
+
+/// The struct of the final standing of a player.
+#[derive(PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Standing {
+    /// The side the player sits at.
+    pub side: FengType,
+    /// The player sitting at the side, see [crate::seating::Seating::seats].
+    pub player: Option<usize>,
+    /// The place of the player, from 1.
+    pub place: u8,
+    /// The points of the player at the end of the game, with the riichi sticks taken if any.
+    pub score: i32,
+    /// The final points of the player, i.e. the points over [UmaOka::returning]
+    /// with the uma and the oka added.
+    pub points: i32,
+}
+
+/// The struct of a game session ended by points below the threshold, known as 击飞 or tobi,
+/// see [crate::game::Game::tobi].
+#[derive(PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Tobi {
+    /// The players whose points are below the threshold.
+    pub sides: Vec<FengType>,
+    /// The payment taking the points below the threshold,
+    /// or `None` for the payments of a drawn hand(荒牌流局), see [crate::scoring::noten_payments].
+    pub payment: Option<Payment>,
+}
+
+/// The struct of the final result of a game session.
+#[derive(PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FinalResult {
+    /// The standings of the players, from the first place.
+    pub standings: Vec<Standing>,
+    /// How the game ended by points below the threshold, if it did.
+    pub tobi: Option<Tobi>,
+}
+
+impl FinalResult {
+    /// Ranks the players given their points in the order of seats from the first dealer,
+    /// so the earlier seat ranks higher in a tie,
+    /// the seating, the riichi sticks left on the table and the adjustments.
+    pub fn rank(
+        scores: &[(FengType, i32)],
+        seating: &Seating,
+        riichi_sticks: u8,
+        uma_oka: &UmaOka,
+    ) -> Self {
+        let mut order: Vec<_> = scores.iter().copied().enumerate().collect();
+        order.sort_by_key(|&(seat, (_, score))| (-score, seat));
+        let standings = order
+            .into_iter()
+            .enumerate()
+            .map(|(i, (_, (side, mut score)))| {
+                let mut points = score - uma_oka.returning + uma_oka.uma.get(i).unwrap_or(&0);
+                if i == 0 {
+                    if uma_oka.sticks_to_first {
+                        score += riichi_sticks as i32 * RIICHI_DEPOSIT;
+                        points += riichi_sticks as i32 * RIICHI_DEPOSIT;
+                    }
+                    points += uma_oka.oka(scores.len());
+                }
+                Standing {
+                    side,
+                    player: seating.seats.get(&side).copied(),
+                    place: i as u8 + 1,
+                    score,
+                    points,
+                }
+            })
+            .collect();
+        Self {
+            standings,
+            tobi: None,
+        }
+    }
+
+    /// Returns the standing of the player at the side, if any.
+    pub fn standing(&self, side: FengType) -> Option<&Standing> {
+        self.standings.iter().find(|s| s.side == side)
+    }
+}
@@ -0,0 +1,148 @@
+//! Built-in situations and completions of Taiwanese mahjong(台湾麻将), known as 台湾十六张.
+//!
+//! The core of this module is [register], which fills the checkers of a [Cards]
+//! with common 台 of Taiwanese mahjong, see also [Cards::with_taiwanese_rules].
+//! Players hold 16 cards, so a complete hand is five cases(面子) and a pair(雀头),
+//! and there are no special forms such as 七对子.
+//!
+//! Every complete hand can win, counting the base(底) as a completion of no 台,
+//! so the 台 are the fan(番数) of the completions and [crate::ruleset::Ruleset::min_fan] stays zero.
+//!
+//! Situation checkers see the hand, the river and the open of a player
+//! together with the last card and where it comes from, see [WinContext],
+//! so the 台 depending on the dealer, the winds of the round and the seat,
+//! or the flower cards(花牌) are not included.
+
+use std::collections::HashMap;
+
+use crate::{
+    analysis::{
+        all_cards, all_cases, complete_hand, winning_concealed_triplets, winning_decompositions,
+        winning_waits, WaitType,
+    },
+    card_type::CardType,
+    cards::{Cards, Completion, Hand, Open, River, SituationChecker, SituationFn, WinContext},
+    case_type::CaseType,
+    completion,
+    situations::{self, has_triplet, triplets_and_pairs, FENGS, JIANS},
+};
+
+/// 平胡: 顺子 only, no honor, and a 两面 wait by 荣和.
+fn ping_hu(hand: &Hand, _: &River, open: &Open, draw: CardType, context: &WinContext) -> bool {
+    let (_, tsumo) = complete_hand(hand, open, draw);
+    !tsumo
+        && all_cards(hand, open, draw)
+            .iter()
+            .all(|c| c.rank().is_some())
+        && winning_decompositions(hand, open, draw, context)
+            .iter()
+            .any(|d| {
+                all_cases(d, open)
+                    .iter()
+                    .all(|c| matches!(c, CaseType::Shun(_)))
+                    && winning_waits(d, draw, context).contains(&WaitType::LiangMian)
+            })
+}
+
+/// 全求人: every case is called, and the pair is completed by 荣和.
+fn quan_qiu_ren(hand: &Hand, _: &River, open: &Open, draw: CardType, _: &WinContext) -> bool {
+    let (complete, tsumo) = complete_hand(hand, open, draw);
+    !tsumo
+        && open.iter().all(CaseType::is_open)
+        && complete.values().sum::<u8>() == 2
+        && complete.get(&draw) == Some(&2)
+}
+
+/// Returns the most concealed 刻子 and 暗杠 of the winning hand, known as 暗刻 in Chinese.
+///
+/// A 刻子 completed by 荣和 is not concealed.
+fn an_ke(hand: &Hand, open: &Open, draw: CardType, context: &WinContext) -> usize {
+    let (_, tsumo) = complete_hand(hand, open, draw);
+    winning_decompositions(hand, open, draw, context)
+        .iter()
+        .map(|d| winning_concealed_triplets(d, open, draw, tsumo, context))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Returns the situation checkers of Taiwanese mahjong, named in Chinese.
+pub fn situation_checkers() -> HashMap<&'static str, SituationChecker> {
+    let checkers: [(&'static str, SituationFn); 22] = [
+        ("门清", situations::men_qian_qing),
+        ("自摸", situations::zi_mo),
+        ("杠上开花", situations::ling_shang_kai_hua),
+        ("海底捞月", situations::hai_di_mo_yue),
+        ("河底捞鱼", situations::he_di_lao_yu),
+        ("抢杠", situations::qiang_gang),
+        ("平胡", ping_hu),
+        ("全求人", quan_qiu_ren),
+        ("碰碰胡", situations::dui_dui_he),
+        ("三暗刻", |h, _, o, d, ctx| an_ke(h, o, d, ctx) == 3),
+        ("四暗刻", |h, _, o, d, ctx| an_ke(h, o, d, ctx) == 4),
+        ("五暗刻", |h, _, o, d, ctx| an_ke(h, o, d, ctx) == 5),
+        ("混一色", situations::hun_yi_se),
+        ("清一色", situations::qing_yi_se),
+        ("字一色", situations::zi_yi_se),
+        ("三元牌白", |h, _, o, d, _| {
+            has_triplet(h, o, d, JIANS[0])
+        }),
+        ("三元牌发", |h, _, o, d, _| {
+            has_triplet(h, o, d, JIANS[1])
+        }),
+        ("三元牌中", |h, _, o, d, _| {
+            has_triplet(h, o, d, JIANS[2])
+        }),
+        ("小三元", |h, _, o, d, _| {
+            triplets_and_pairs(h, o, d, &JIANS) == (2, 1)
+        }),
+        ("大三元", |h, _, o, d, _| {
+            triplets_and_pairs(h, o, d, &JIANS).0 == 3
+        }),
+        ("小四喜", |h, _, o, d, _| {
+            triplets_and_pairs(h, o, d, &FENGS) == (3, 1)
+        }),
+        ("大四喜", |h, _, o, d, _| {
+            triplets_and_pairs(h, o, d, &FENGS).0 == 4
+        }),
+    ];
+    situations::collect_checkers(checkers)
+}
+
+/// Returns the completions of Taiwanese mahjong, i.e. the 台 and their number as the fan(番数).
+///
+/// The base(底) requires no situation, so any complete hand meets it.
+/// 门清 and 自摸 together count as 门清自摸 instead.
+pub fn completions() -> Vec<Completion> {
+    vec![
+        completion!("底", 0),
+        completion!("门清", 1, requires["门清"], forbids["自摸"]),
+        completion!("自摸", 1, requires["自摸"], forbids["门清"]),
+        completion!("门清自摸", 3, requires ["门清", "自摸"]),
+        completion!("杠上开花", 1, requires["杠上开花"]),
+        completion!("海底捞月", 1, requires["海底捞月"]),
+        completion!("河底捞鱼", 1, requires["河底捞鱼"]),
+        completion!("抢杠", 1, requires["抢杠"]),
+        completion!("三元牌 白", 1, requires ["三元牌白"], forbids ["小三元", "大三元"]),
+        completion!("三元牌 发", 1, requires ["三元牌发"], forbids ["小三元", "大三元"]),
+        completion!("三元牌 中", 1, requires ["三元牌中"], forbids ["小三元", "大三元"]),
+        completion!("平胡", 2, requires["平胡"]),
+        completion!("全求人", 2, requires["全求人"]),
+        completion!("三暗刻", 2, requires["三暗刻"]),
+        completion!("碰碰胡", 4, requires["碰碰胡"], forbids["字一色"]),
+        completion!("混一色", 4, requires["混一色"]),
+        completion!("小三元", 4, requires["小三元"]),
+        completion!("四暗刻", 5, requires["四暗刻"]),
+        completion!("清一色", 8, requires["清一色"]),
+        completion!("大三元", 8, requires["大三元"]),
+        completion!("小四喜", 8, requires["小四喜"]),
+        completion!("五暗刻", 8, requires["五暗刻"]),
+        completion!("字一色", 16, requires["字一色"]),
+        completion!("大四喜", 16, requires["大四喜"]),
+    ]
+}
+
+/// Registers the situations and completions of Taiwanese mahjong into the cards.
+pub fn register(cards: &mut Cards) {
+    cards.situation_checkers.extend(situation_checkers());
+    cards.completion_checkers.extend(completions());
+}
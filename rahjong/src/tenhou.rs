@@ -0,0 +1,815 @@
+//! Import and export of the game logs of Tenhou(天凤), behind the `tenhou` feature.
+//!
+//! The core of this module is the [TenhouLog] struct, parsed from a log in the JSON format
+//! used by the web replay viewer of Tenhou, see [parse],
+//! or recorded from the engines of a game, see [TenhouHand::from_engine],
+//! and written back by [TenhouLog::to_json].
+//! Logs in the XML format(mjlog) should be converted to the JSON format first.
+//!
+//! Each hand(局) of the log is turned into the wall it was dealt from and the actions of players,
+//! so that it can be replayed by an [Engine], see [TenhouHand::replay].
+//! Since a log only tells the cards drawn and revealed,
+//! the other cards of the wall are filled in arbitrarily.
+//!
+//! Players are the seats of the first hand, i.e. the first player of the log is 东.
+
+use serde_json::{json, Value};
+
+use crate::{
+    aka,
+    card_type::{CardType, FengType, Next},
+    cards::Cards,
+    case_type::CaseType,
+    draw_reason::DrawReason,
+    engine::{Action, Engine, HandEnd},
+    error::LogError,
+    event::GameEvent,
+    game::{Game, GameLength},
+    tile_counts::TileCounts,
+};
+
+/// A card of the log, and whether it is a red five(赤宝牌).
+type Tile = (CardType, bool);
+
+/// The code of a discard played right after being drawn(摸切).
+const TSUMOGIRI: i64 = 60;
+
+/// The struct containing a game log of Tenhou.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TenhouLog {
+    /// The names of players, in the order of players.
+    pub names: Vec<String>,
+    /// The hands(局) of the game, in order.
+    pub hands: Vec<TenhouHand>,
+}
+
+/// The struct containing a hand(局) of a game log of Tenhou.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TenhouHand {
+    /// The wind of the round, known as 场风 in Chinese.
+    pub round_wind: FengType,
+    /// The index of the hand in the round, from 0 to 3, see [Game::kyoku].
+    pub kyoku: u8,
+    /// The counter of hands the dealer repeated or drawn in a row, known as 本场 in Chinese.
+    pub honba: u8,
+    /// The number of riichi sticks(立直棒) on the table when the hand starts.
+    pub riichi_sticks: u8,
+    /// The points of players when the hand starts.
+    pub scores: [i32; 4],
+    /// The wall the hand was dealt from, laid out as [Cards::from_wall] takes it.
+    pub wall: Vec<CardType>,
+    /// Whether each card of the wall is a red five(赤宝牌), in the same order as the wall.
+    pub reds: Vec<bool>,
+    /// The actions of players in order, which can be applied by [Engine::apply].
+    ///
+    /// Passes on the cards played are left out, see [TenhouHand::replay].
+    pub actions: Vec<(FengType, Action)>,
+    /// How the hand ended, or `None` if unknown to this crate.
+    pub end: Option<HandEnd>,
+    /// The points each player gained or lost in the hand.
+    pub deltas: [i32; 4],
+}
+
+/// The `Take` type. Represents a card a player takes in the log.
+enum Take {
+    /// A card drawn from mountain or the dead wall(王牌).
+    Draw(Tile),
+    /// A call action on a card played, with its marker, the index of the marker and the cards.
+    Call(char, usize, Vec<Tile>),
+}
+
+/// The `Discard` type. Represents what a player does after taking a card in the log.
+enum Discard {
+    /// Playing the card, or the card just drawn if `None`, and whether to declare 立直.
+    Play(Option<Tile>, bool),
+    /// Doing an 暗杠 or an 加杠 with the card, in the player's own turn.
+    Kan(bool, CardType),
+    /// Nothing, following a 大明杠.
+    Skip,
+}
+
+/// The cards and actions of a player in a hand of the log.
+struct Seat {
+    takes: Vec<Take>,
+    discards: Vec<Discard>,
+}
+
+/// Parses a game log of Tenhou in the JSON format.
+///
+/// Returns an error telling why if the log can not be read.
+pub fn parse(json: &str) -> Result<TenhouLog, LogError> {
+    let value: Value = serde_json::from_str(json).map_err(|_| LogError::InvalidFormat)?;
+    let names = value
+        .get("name")
+        .and_then(Value::as_array)
+        .map(|names| {
+            names
+                .iter()
+                .filter_map(|n| n.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default();
+    let hands = array(value.get("log"), "log")?
+        .iter()
+        .map(parse_hand)
+        .collect::<Result<_, _>>()?;
+    Ok(TenhouLog { names, hands })
+}
+
+/// Returns the array of the value, or an error on the field if it is not an array.
+fn array<'a>(value: Option<&'a Value>, field: &'static str) -> Result<&'a [Value], LogError> {
+    value
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .ok_or(LogError::InvalidField(field))
+}
+
+/// Returns the integer of the value, or an error on the field if it is not an integer.
+fn int(value: Option<&Value>, field: &'static str) -> Result<i64, LogError> {
+    value
+        .and_then(Value::as_i64)
+        .ok_or(LogError::InvalidField(field))
+}
+
+/// Returns the code of the card, the reverse of [tile].
+fn code(card: CardType, red: bool) -> i64 {
+    let index = card.index() as i64;
+    match index {
+        4 | 13 | 22 if red => [51, 53, 52][index as usize / 9],
+        0..=8 => 11 + index,
+        9..=17 => 22 + index,
+        18..=26 => 3 + index,
+        27..=29 => 18 + index,
+        _ => 11 + index,
+    }
+}
+
+/// Returns the card of the code, i.e. the suit(1 for 万, 2 for 筒, 3 for 条 and 4 for 字) followed by the rank,
+/// or 51, 52 and 53 for the red fives of 万, 筒 and 条.
+fn tile(code: i64) -> Result<Tile, LogError> {
+    let (suit, rank, red) = match code {
+        51..=53 => (code - 50, 5, true),
+        11..=47 => (code / 10, code % 10, false),
+        _ => return Err(LogError::InvalidCard(code)),
+    };
+    let suit = match suit {
+        1 => 'm',
+        2 => 'p',
+        3 => 's',
+        _ => 'z',
+    };
+    let card = format!("{rank}{suit}")
+        .parse()
+        .map_err(|_| LogError::InvalidCard(code))?;
+    Ok((card, red))
+}
+
+/// Parses the cards of a call action, e.g. `c275226` or `393939a39`,
+/// returning the marker, the index of the marker and the cards.
+fn meld(s: &str) -> Result<(char, usize, Vec<Tile>), LogError> {
+    let mut marker = None;
+    let mut tiles = Vec::new();
+    let mut rest = s;
+    while let Some(c) = rest.chars().next() {
+        if c.is_ascii_alphabetic() {
+            marker = Some((c, tiles.len()));
+            rest = &rest[1..];
+        } else {
+            let code = rest
+                .get(..2)
+                .and_then(|code| code.parse().ok())
+                .ok_or(LogError::InvalidCall)?;
+            tiles.push(tile(code)?);
+            rest = &rest[2..];
+        }
+    }
+    let (marker, index) = marker.ok_or(LogError::InvalidCall)?;
+    Ok((marker, index, tiles))
+}
+
+/// Parses a card taken by a player.
+fn take(value: &Value) -> Result<Take, LogError> {
+    match value {
+        Value::String(s) => {
+            let (marker, index, tiles) = meld(s)?;
+            Ok(Take::Call(marker, index, tiles))
+        }
+        _ => Ok(Take::Draw(tile(int(Some(value), "takes")?)?)),
+    }
+}
+
+/// Parses what a player does after taking a card.
+fn discard(value: &Value) -> Result<Discard, LogError> {
+    let play = |code| match code {
+        TSUMOGIRI => Ok(None),
+        _ => tile(code).map(Some),
+    };
+    match value {
+        Value::String(s) if s.starts_with('r') => {
+            let code = s[1..]
+                .parse()
+                .map_err(|_| LogError::InvalidField("discards"))?;
+            Ok(Discard::Play(play(code)?, true))
+        }
+        Value::String(s) => {
+            let (marker, _, tiles) = meld(s)?;
+            let &(card, _) = tiles.first().ok_or(LogError::InvalidCall)?;
+            match marker {
+                'a' => Ok(Discard::Kan(true, card)),
+                'k' => Ok(Discard::Kan(false, card)),
+                _ => Err(LogError::InvalidCall),
+            }
+        }
+        _ => match int(Some(value), "discards")? {
+            0 => Ok(Discard::Skip),
+            code => Ok(Discard::Play(play(code)?, false)),
+        },
+    }
+}
+
+/// Parses a hand(局) of the log, rebuilding its wall and the actions of players.
+fn parse_hand(value: &Value) -> Result<TenhouHand, LogError> {
+    let parts = array(Some(value), "log")?;
+    let info = array(parts.first(), "log")?;
+    let round = int(info.first(), "log")?;
+    let scores = array(parts.get(1), "log")?;
+    let mut hand = TenhouHand {
+        round_wind: FengType::ALL[(round / 4 % 4) as usize],
+        kyoku: (round % 4) as u8,
+        honba: int(info.get(1), "log")? as u8,
+        riichi_sticks: int(info.get(2), "log")? as u8,
+        scores: [0; 4],
+        wall: Vec::new(),
+        reds: Vec::new(),
+        actions: Vec::new(),
+        end: None,
+        deltas: [0; 4],
+    };
+    for (score, value) in hand.scores.iter_mut().zip(scores) {
+        *score = int(Some(value), "log")? as i32;
+    }
+    let tiles = |index: usize| -> Result<Vec<Tile>, LogError> {
+        array(parts.get(index), "log")?
+            .iter()
+            .map(|v| tile(int(Some(v), "log")?))
+            .collect()
+    };
+    let dora = tiles(2)?;
+    let ura = tiles(3)?;
+    let mut haipai = Vec::new();
+    let mut seats = Vec::new();
+    for i in 0..4 {
+        haipai.push(tiles(4 + 3 * i)?);
+        seats.push(Seat {
+            takes: array(parts.get(5 + 3 * i), "log")?
+                .iter()
+                .map(take)
+                .collect::<Result<_, _>>()?,
+            discards: array(parts.get(6 + 3 * i), "log")?
+                .iter()
+                .map(discard)
+                .collect::<Result<_, _>>()?,
+        });
+    }
+    let (draws, replacements) = follow(&mut hand, &seats)?;
+    if let Some(result) = parts.get(16).and_then(Value::as_array) {
+        end(&mut hand, result)?;
+    }
+    build_wall(&mut hand, &haipai, &draws, &replacements, &dora, &ura)?;
+    Ok(hand)
+}
+
+/// Follows the turns of the hand, recording the actions of players.
+///
+/// Returns the cards drawn from mountain and the replacement cards(岭上牌), in the order of being drawn.
+fn follow(hand: &mut TenhouHand, seats: &[Seat]) -> Result<(Vec<Tile>, Vec<Tile>), LogError> {
+    let mut draws = Vec::new();
+    let mut replacements = Vec::new();
+    let mut takes = [0; 4];
+    let mut discards = [0; 4];
+    let mut cur = hand.kyoku as usize;
+    let mut after_kan = false;
+    'draw: while let Some(Take::Draw(drawn)) = seats[cur].takes.get(takes[cur]) {
+        takes[cur] += 1;
+        hand.actions.push((FengType::ALL[cur], Action::Draw));
+        if after_kan {
+            replacements.push(*drawn);
+        } else {
+            draws.push(*drawn);
+        }
+        after_kan = false;
+        let mut drawn = Some(*drawn);
+        loop {
+            let side = FengType::ALL[cur];
+            let Some(discard) = seats[cur].discards.get(discards[cur]) else {
+                break 'draw;
+            };
+            discards[cur] += 1;
+            let (card, riichi) = match *discard {
+                Discard::Kan(concealed, card) => {
+                    let action = if concealed {
+                        Action::AnKan(card)
+                    } else {
+                        Action::Kan(card)
+                    };
+                    hand.actions.push((side, action));
+                    after_kan = true;
+                    continue 'draw;
+                }
+                Discard::Skip => return Err(LogError::Inconsistent),
+                Discard::Play(card, riichi) => {
+                    let (card, _) = card.or(drawn).ok_or(LogError::Inconsistent)?;
+                    (card, riichi)
+                }
+            };
+            hand.actions.push((
+                side,
+                if riichi {
+                    Action::Riichi(card)
+                } else {
+                    Action::Discard(card)
+                },
+            ));
+            let Some((caller, marker, tiles)) = caller(seats, &takes, cur, card) else {
+                cur = (cur + 1) % 4;
+                continue 'draw;
+            };
+            takes[caller] += 1;
+            cur = caller;
+            drawn = None;
+            let side = FengType::ALL[cur];
+            match marker {
+                'c' => {
+                    let start = tiles.iter().map(|&(c, _)| c).min().unwrap_or(card);
+                    hand.actions.push((side, Action::Chi(start)));
+                }
+                'p' => hand.actions.push((side, Action::Pon)),
+                _ => {
+                    hand.actions.push((side, Action::Kan(card)));
+                    if let Some(Discard::Skip) = seats[cur].discards.get(discards[cur]) {
+                        discards[cur] += 1;
+                    }
+                    after_kan = true;
+                    continue 'draw;
+                }
+            }
+        }
+    }
+    Ok((draws, replacements))
+}
+
+/// Returns the player calling the card played by the player given,
+/// with the marker and the cards of the call action.
+///
+/// A player calls the card if the next card it takes is a call action on the card from the player,
+/// where 碰 and 杠 come before 吃.
+fn caller<'a>(
+    seats: &'a [Seat],
+    takes: &[usize; 4],
+    discarder: usize,
+    card: CardType,
+) -> Option<(usize, char, &'a [Tile])> {
+    let mut res = None;
+    for distance in 1..4 {
+        let caller = (discarder + distance) % 4;
+        let Some(Take::Call(marker, index, tiles)) = seats[caller].takes.get(takes[caller]) else {
+            continue;
+        };
+        let from = match (marker, index) {
+            ('c', _) => 1,
+            ('p' | 'm', 0) => 1,
+            ('p' | 'm', 1) => 2,
+            ('p', 2) | ('m', 3) => 3,
+            _ => continue,
+        };
+        if from != distance || !tiles.iter().any(|&(c, _)| c == card) {
+            continue;
+        }
+        if *marker != 'c' {
+            return Some((caller, *marker, tiles.as_slice()));
+        }
+        res = Some((caller, *marker, tiles.as_slice()));
+    }
+    res
+}
+
+/// Reads the result of the hand, adding the final actions of the winners.
+fn end(hand: &mut TenhouHand, result: &[Value]) -> Result<(), LogError> {
+    let name = result.first().and_then(Value::as_str).unwrap_or_default();
+    let mut deltas = Vec::new();
+    let mut winners = Vec::new();
+    let mut from = None;
+    if name == "和了" {
+        for pair in result[1..].chunks(2) {
+            deltas.push(&pair[0]);
+            let info = array(pair.get(1), "result")?;
+            let who = FengType::ALL[int(info.first(), "result")? as usize % 4];
+            from = Some(FengType::ALL[int(info.get(1), "result")? as usize % 4]);
+            winners.push(who);
+        }
+    } else {
+        deltas.extend(result.get(1));
+    }
+    for delta in deltas {
+        for (total, value) in hand.deltas.iter_mut().zip(array(Some(delta), "result")?) {
+            *total += int(Some(value), "result")? as i32;
+        }
+    }
+    hand.end = match (name, from) {
+        ("和了", Some(from)) if winners == [from] => {
+            hand.actions.push((from, Action::Tsumo));
+            Some(HandEnd::Tsumo(from))
+        }
+        ("和了", Some(from)) => {
+            hand.actions
+                .extend(winners.iter().map(|&w| (w, Action::Ron)));
+            Some(HandEnd::Ron { winners, from })
+        }
+        ("流局" | "全員聴牌" | "全員不聴" | "流し満貫", _) => {
+            Some(HandEnd::Draw(DrawReason::HuangPai))
+        }
+        ("九種九牌", _) => Some(HandEnd::Draw(DrawReason::JiuZhongJiuPai)),
+        ("四風連打", _) => Some(HandEnd::Draw(DrawReason::SiFengLianDa)),
+        ("四家立直", _) => Some(HandEnd::Draw(DrawReason::SiJiaLiZhi)),
+        ("四槓散了", _) => Some(HandEnd::Draw(DrawReason::SiGangSanLe)),
+        ("三家和了", _) => Some(HandEnd::Draw(DrawReason::SanJiaHeLe)),
+        _ => None,
+    };
+    Ok(())
+}
+
+/// Rebuilds the wall of the hand from the cards known,
+/// filling in the rest with the cards left.
+fn build_wall(
+    hand: &mut TenhouHand,
+    haipai: &[Vec<Tile>],
+    draws: &[Tile],
+    replacements: &[Tile],
+    dora: &[Tile],
+    ura: &[Tile],
+) -> Result<(), LogError> {
+    const DEAD_WALL: usize = 14;
+    const MOUNTAIN: usize = 70;
+    let mut slots: Vec<Option<Tile>> = vec![None; DEAD_WALL + MOUNTAIN];
+    let known = [
+        (0, replacements.iter().take(4)),
+        (4, dora.iter().take(5)),
+        (9, ura.iter().take(5)),
+    ];
+    for (start, tiles) in known {
+        for (slot, &t) in slots[start..].iter_mut().zip(tiles) {
+            *slot = Some(t);
+        }
+    }
+    if draws.len() > MOUNTAIN || haipai.iter().any(|h| h.len() != 13) {
+        return Err(LogError::Inconsistent);
+    }
+    for (slot, &t) in slots.iter_mut().rev().zip(draws) {
+        *slot = Some(t);
+    }
+    for dealt in haipai.iter().rev() {
+        slots.extend(dealt.iter().map(|&t| Some(t)));
+    }
+
+    let mut left = TileCounts::new();
+    (0..CardType::COUNT)
+        .filter_map(CardType::from_index)
+        .for_each(|card| left[card] = 4);
+    for &(card, _) in slots.iter().flatten() {
+        if left[card] == 0 {
+            return Err(LogError::Inconsistent);
+        }
+        left[card] -= 1;
+    }
+    let mut rest = left
+        .iter()
+        .flat_map(|(card, n)| std::iter::repeat_n(card, n as usize));
+    for slot in slots {
+        let (card, red) = match slot {
+            Some(t) => t,
+            None => (rest.next().ok_or(LogError::Inconsistent)?, false),
+        };
+        hand.wall.push(card);
+        hand.reds.push(red && aka::fives().contains(&card));
+    }
+    Ok(())
+}
+
+impl TenhouHand {
+    /// Returns the dealer of the hand, known as 庄家 in Chinese.
+    pub fn dealer(&self) -> FengType {
+        FengType::ALL[self.kyoku as usize % 4]
+    }
+
+    /// Returns the cards of the hand when it starts,
+    /// with the built-in situations and completions of riichi mahjong, see [crate::riichi::register].
+    ///
+    /// Returns `None` if the wall is not valid, see [Cards::from_wall].
+    pub fn cards(&self) -> Option<Cards> {
+        let mut cards = Cards::from_wall_with_reds(self.wall.clone(), self.reds.clone())?;
+        crate::riichi::register(&mut cards);
+        for (side, &score) in FengType::ALL.into_iter().zip(&self.scores) {
+            *cards.score_mut(side) = score;
+        }
+        cards.round_wind = self.round_wind;
+        cards.dealer = self.dealer();
+        cards.active_player = self.dealer();
+        Some(cards)
+    }
+
+    /// Replays the actions of the hand by an [Engine],
+    /// where players who do not claim a card played pass on it.
+    ///
+    /// Returns the engine after the last action,
+    /// or the index of the first action the engine rejects.
+    pub fn replay(&self) -> Result<Engine, usize> {
+        self.replay_with(|engine, side, action| engine.apply(side, action))
+    }
+
+    /// Replays the actions of the hand like [TenhouHand::replay],
+    /// applying each action, passes included, by the function given.
+    fn replay_with(
+        &self,
+        mut apply: impl FnMut(&mut Engine, FengType, Action) -> bool,
+    ) -> Result<Engine, usize> {
+        let mut cards = self.cards().ok_or(0_usize)?;
+        cards.config.multiple_ron = true;
+        let mut game = Game::new(cards, GameLength::default());
+        game.kyoku = self.kyoku;
+        game.honba = self.honba;
+        game.riichi_sticks = self.riichi_sticks;
+        let mut engine = Engine::new(game);
+        for (i, &(side, action)) in self.actions.iter().enumerate() {
+            let claiming = matches!(
+                action,
+                Action::Chi(_) | Action::Pon | Action::Kan(_) | Action::Ron
+            ) && engine.claims.as_ref().is_some_and(|c| c.discarder != side);
+            if !claiming {
+                pass_all(&mut engine, &mut apply);
+            }
+            if !apply(&mut engine, side, action) {
+                return Err(i);
+            }
+        }
+        pass_all(&mut engine, &mut apply);
+        Ok(engine)
+    }
+}
+
+/// Passes on the card waiting for claims by every player who has not responded,
+/// applying the passes by the function given.
+fn pass_all(engine: &mut Engine, apply: &mut impl FnMut(&mut Engine, FengType, Action) -> bool) {
+    if engine.claims.is_some() {
+        for side in FengType::ALL {
+            apply(engine, side, Action::Pass);
+        }
+    }
+}
+
+impl TenhouHand {
+    /// Creates a hand of the log from the engine which has run the hand, see [Engine::events].
+    ///
+    /// The points gained or lost are not known to the engine, and left as zeros.
+    ///
+    /// Returns `None` if the events do not start with [GameEvent::Start],
+    /// or the hand is of 三麻, 台湾麻将 or 血战到底, which are not supported,
+    /// see [crate::ruleset::Ruleset::has_nuki_dora], [crate::ruleset::Ruleset::has_flowers]
+    /// and [crate::ruleset::Ruleset::continues_after_completion].
+    pub fn from_engine(engine: &Engine) -> Option<Self> {
+        let cards = engine.cards();
+        let rules = cards.rules();
+        if rules.has_nuki_dora() || rules.has_flowers() || rules.continues_after_completion() {
+            return None;
+        }
+        let Some(GameEvent::Start {
+            round_wind,
+            kyoku,
+            honba,
+            riichi_sticks,
+            scores,
+            wall,
+            reds,
+            ..
+        }) = engine.events.first()
+        else {
+            return None;
+        };
+        Some(Self {
+            round_wind: *round_wind,
+            kyoku: *kyoku,
+            honba: *honba,
+            riichi_sticks: *riichi_sticks,
+            scores: *scores,
+            wall: wall.clone(),
+            reds: reds.clone(),
+            actions: engine
+                .events
+                .iter()
+                .filter_map(GameEvent::action)
+                .filter(|(_, a)| *a != Action::Pass)
+                .collect(),
+            end: engine.result.clone(),
+            deltas: [0; 4],
+        })
+    }
+
+    /// Returns the hand in the JSON format of Tenhou, see [TenhouLog::to_json].
+    ///
+    /// Returns [LogError::Inconsistent] if the actions can not be replayed, see [TenhouHand::replay].
+    pub fn to_json(&self) -> Result<Value, LogError> {
+        let cards = self.cards().ok_or(LogError::Inconsistent)?;
+        let haipai: Vec<Vec<i64>> = FengType::ALL
+            .into_iter()
+            .map(|side| {
+                let reds = cards.aka.in_hand.get(&side);
+                let mut res = Vec::new();
+                for (&card, &n) in cards.hand(side) {
+                    let red = reds.map_or(0, |v| v.iter().filter(|&&c| c == card).count());
+                    res.extend((0..n as usize).map(|i| code(card, i < red)));
+                }
+                res
+            })
+            .collect();
+        let mut recorder = Recorder::default();
+        let engine = self
+            .replay_with(|engine, side, action| recorder.apply(engine, side, action))
+            .map_err(|_| LogError::Inconsistent)?;
+
+        let revealed = engine.cards().dora_revealed as usize;
+        let indicators = |start: usize| -> Vec<i64> {
+            (start..start + revealed)
+                .filter_map(|i| Some(code(*self.wall.get(i)?, self.reds[i])))
+                .collect()
+        };
+        let won = matches!(self.end, Some(HandEnd::Tsumo(_) | HandEnd::Ron { .. }));
+        let index = |side: FengType| FengType::ALL.iter().position(|&s| s == side).unwrap_or(0);
+        let result = match &self.end {
+            Some(HandEnd::Tsumo(winner)) => {
+                let w = index(*winner);
+                json!(["和了", self.deltas, [w, w, w, ""]])
+            }
+            Some(HandEnd::Ron { winners, from }) => {
+                let mut res = vec![json!("和了")];
+                for (i, &winner) in winners.iter().enumerate() {
+                    let deltas = if i == 0 { self.deltas } else { [0; 4] };
+                    res.push(json!(deltas));
+                    res.push(json!([index(winner), index(*from), index(winner), ""]));
+                }
+                Value::Array(res)
+            }
+            Some(HandEnd::Draw(reason)) => {
+                let name = match reason {
+                    DrawReason::HuangPai => "流局",
+                    DrawReason::JiuZhongJiuPai => "九種九牌",
+                    DrawReason::SiFengLianDa => "四風連打",
+                    DrawReason::SiJiaLiZhi => "四家立直",
+                    DrawReason::SiGangSanLe => "四槓散了",
+                    DrawReason::SanJiaHeLe => "三家和了",
+                };
+                json!([name, self.deltas])
+            }
+            None => json!([]),
+        };
+
+        let round = FengType::ALL
+            .iter()
+            .position(|&s| s == self.round_wind)
+            .unwrap_or(0)
+            * 4;
+        let mut res = vec![
+            json!([round + self.kyoku as usize, self.honba, self.riichi_sticks]),
+            json!(self.scores),
+            json!(indicators(4)),
+            json!(if won { indicators(9) } else { Vec::new() }),
+        ];
+        for (i, haipai) in haipai.into_iter().enumerate() {
+            res.push(json!(haipai));
+            res.push(Value::Array(std::mem::take(&mut recorder.takes[i])));
+            res.push(Value::Array(std::mem::take(&mut recorder.discards[i])));
+        }
+        res.push(result);
+        Ok(Value::Array(res))
+    }
+}
+
+impl TenhouLog {
+    /// Returns the log in the JSON format of Tenhou, which can be opened by its web replay viewer.
+    ///
+    /// Returns an error if a hand can not be written, see [TenhouHand::to_json].
+    pub fn to_json(&self) -> Result<Value, LogError> {
+        let hands = self
+            .hands
+            .iter()
+            .map(TenhouHand::to_json)
+            .collect::<Result<Vec<_>, _>>()?;
+        let aka = self.hands.iter().any(|h| h.reds.contains(&true));
+        Ok(json!({
+            "title": ["", ""],
+            "name": self.names,
+            "rule": { "disp": "", "aka": u8::from(aka) },
+            "log": hands,
+        }))
+    }
+}
+
+/// The struct recording the cards players take and what they do after, as the log of Tenhou does.
+#[derive(Default)]
+struct Recorder {
+    takes: [Vec<Value>; 4],
+    discards: [Vec<Value>; 4],
+    /// The 碰 done by each player, with the card and the call action written.
+    pons: Vec<(usize, CardType, String)>,
+}
+
+impl Recorder {
+    /// Applies the action by the engine, recording it if applied.
+    fn apply(&mut self, engine: &mut Engine, side: FengType, action: Action) -> bool {
+        let index = |side: FengType| FengType::ALL.iter().position(|&s| s == side).unwrap_or(0);
+        let cards = engine.cards();
+        let opens = FengType::ALL.map(|s| cards.open(s).len());
+        let claim = engine
+            .claims
+            .as_ref()
+            .map(|c| (index(c.discarder), c.card, cards.aka.last_played));
+        let reds = cards.aka.count(side);
+        if !engine.apply(side, action) {
+            return false;
+        }
+        let cards = engine.cards();
+        let i = index(side);
+        match action {
+            Action::Draw => {
+                if let Some(card) = cards.drawn {
+                    self.takes[i].push(json!(code(card, cards.aka.count(side) > reds)));
+                }
+            }
+            // The log has no 开立直, so it is recorded as a 立直.
+            Action::Discard(card) | Action::Riichi(card) | Action::OpenRiichi(card) => {
+                let played = match cards.river(side).last() {
+                    Some(r) if r.tsumogiri => TSUMOGIRI,
+                    _ => code(card, cards.aka.last_played),
+                };
+                self.discards[i].push(match action {
+                    Action::Riichi(_) | Action::OpenRiichi(_) => json!(format!("r{played}")),
+                    _ => json!(played),
+                });
+            }
+            Action::AnKan(card) => {
+                let c = code(card, false);
+                self.discards[i].push(json!(format!("{c}{c}{c}a{c}")));
+            }
+            Action::Kan(card) if claim.is_none() => {
+                let c = code(card, false);
+                let pon = self
+                    .pons
+                    .iter()
+                    .find(|&&(s, p, _)| s == i && p == card)
+                    .map_or_else(|| format!("p{c}{c}{c}"), |(_, _, pon)| pon.clone());
+                self.discards[i].push(json!(pon.replacen('p', &format!("k{c}"), 1)));
+            }
+            _ => {}
+        }
+        let Some((discarder, card, red)) = claim else {
+            return true;
+        };
+        for (caller, side) in FengType::ALL.into_iter().enumerate() {
+            if cards.open(side).len() <= opens[caller] {
+                continue;
+            }
+            let called = code(card, red);
+            let c = code(card, false);
+            let distance = (caller + 4 - discarder) % 4;
+            let call = match cards.open(side).last() {
+                Some(&CaseType::Shun(start)) => {
+                    let mut res = format!("c{called}");
+                    let mut cur = start;
+                    for _ in 0..3 {
+                        if cur != card {
+                            res += &code(cur, false).to_string();
+                        }
+                        cur = cur.next();
+                    }
+                    res
+                }
+                Some(CaseType::Ke(_)) => {
+                    let mut res = [c, c, c].map(|c| c.to_string());
+                    res[distance - 1] = format!("p{called}");
+                    let res = res.concat();
+                    self.pons.push((caller, card, res.clone()));
+                    res
+                }
+                Some(CaseType::Gang(_)) => {
+                    let mut res = [c, c, c, c].map(|c| c.to_string());
+                    res[[0, 1, 3][distance - 1]] = format!("m{called}");
+                    self.discards[caller].push(json!(0));
+                    res.concat()
+                }
+                _ => continue,
+            };
+            self.takes[caller].push(json!(call));
+        }
+        true
+    }
+}
@@ -0,0 +1,127 @@
+//! Tile counts and their associated methods.
+//!
+//! The core of this module is the [TileCounts] struct,
+//! a compact multiset of cards counting each card type.
+
+use std::ops::{Add, AddAssign, Index, IndexMut, Sub, SubAssign};
+
+use crate::{card_type::CardType, cards::Hand};
+
+/// The `TileCounts` type, containing the count of each card type,
+/// indexed by [CardType::index].
+///
+/// Arithmetic on counts saturates instead of overflowing,
+/// e.g. subtracting more cards than there are leaves zero.
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub struct TileCounts(pub [u8; CardType::COUNT]);
+
+impl TileCounts {
+    /// Creates a new [TileCounts] containing no card.
+    pub fn new() -> Self {
+        Self([0; CardType::COUNT])
+    }
+
+    /// Returns the total number of cards.
+    pub fn total(&self) -> usize {
+        self.0.iter().map(|&n| n as usize).sum()
+    }
+
+    /// Returns each card type together with its count, in the order of card types.
+    ///
+    /// Card types of zero count are included.
+    pub fn iter(&self) -> impl Iterator<Item = (CardType, u8)> + '_ {
+        self.0
+            .iter()
+            .enumerate()
+            .filter_map(|(i, &n)| Some((CardType::from_index(i)?, n)))
+    }
+}
+
+impl Default for TileCounts {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for TileCounts {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for TileCounts {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let counts: Vec<u8> = serde::Deserialize::deserialize(deserializer)?;
+        let len = counts.len();
+        counts
+            .try_into()
+            .map(Self)
+            .map_err(|_| serde::de::Error::invalid_length(len, &"a count for each card type"))
+    }
+}
+
+impl Index<CardType> for TileCounts {
+    type Output = u8;
+
+    fn index(&self, card: CardType) -> &u8 {
+        &self.0[card.index()]
+    }
+}
+
+impl IndexMut<CardType> for TileCounts {
+    fn index_mut(&mut self, card: CardType) -> &mut u8 {
+        &mut self.0[card.index()]
+    }
+}
+
+impl AddAssign for TileCounts {
+    fn add_assign(&mut self, rhs: Self) {
+        for (l, r) in self.0.iter_mut().zip(rhs.0) {
+            *l = l.saturating_add(r);
+        }
+    }
+}
+
+impl Add for TileCounts {
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self {
+        self += rhs;
+        self
+    }
+}
+
+impl SubAssign for TileCounts {
+    fn sub_assign(&mut self, rhs: Self) {
+        for (l, r) in self.0.iter_mut().zip(rhs.0) {
+            *l = l.saturating_sub(r);
+        }
+    }
+}
+
+impl Sub for TileCounts {
+    type Output = Self;
+
+    fn sub(mut self, rhs: Self) -> Self {
+        self -= rhs;
+        self
+    }
+}
+
+impl From<&Hand> for TileCounts {
+    fn from(hand: &Hand) -> Self {
+        let mut res = Self::new();
+        for (&card, &num) in hand {
+            res[card] = res[card].saturating_add(num);
+        }
+        res
+    }
+}
+
+impl From<TileCounts> for Hand {
+    fn from(counts: TileCounts) -> Self {
+        counts.iter().filter(|&(_, n)| n > 0).collect()
+    }
+}
@@ -0,0 +1,97 @@
+//! The state of a hand(局) as seen by a player, see [crate::cards::Cards::view].
+//!
+//! A [PlayerView] keeps everything shown to the player,
+//! i.e. the player's own hand, the rivers, opens and points of all players and the dora indicators,
+//! while the hands of other players and the wall only show their numbers of cards.
+//! So a server can send it to the player's client without leaking the cards not shown,
+//! and then only the changes of it, see [crate::delta].
+
+use crate::{
+    card_type::{CardType, FengType, HuaType, SuitType},
+    cards::{CalledCard, Hand, Open, River},
+    ruleset::RulesetId,
+    tile_counts::TileCounts,
+};
+
+/// The struct containing the state of a hand(局) shown to a player.
+#[derive(PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlayerView {
+    /// The player seeing the state.
+    pub side: FengType,
+    /// The cards in the player's hand, known as 手牌 in Chinese.
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "crate::cards::serialize_hand",
+            deserialize_with = "crate::cards::deserialize_hand"
+        )
+    )]
+    pub hand: Hand,
+    /// The red fives(赤宝牌) in the player's hand.
+    pub reds: Vec<CardType>,
+    /// The card the player has just drawn, if the player is active.
+    pub drawn: Option<CardType>,
+    /// The states of all players shown to everyone, in the order of players.
+    pub seats: Vec<SeatView>,
+    /// The built-in rules the game is played by, see [crate::ruleset::Ruleset::id].
+    pub rules: RulesetId,
+    /// The players who have completed in 血战到底, see [crate::cards::Cards::finished].
+    pub finished: Vec<(FengType, Option<FengType>)>,
+    /// The player who should play a card.
+    pub active_player: FengType,
+    /// The wind of the current round, see [crate::cards::Cards::round_wind].
+    pub round_wind: FengType,
+    /// The dealer of the current hand, see [crate::cards::Cards::dealer].
+    pub dealer: FengType,
+    /// The number of cards left in mountain.
+    pub mountain: usize,
+    /// The dora indicators(宝牌指示牌) revealed.
+    pub dora_indicators: Vec<CardType>,
+    /// The card last played and the player who played it, see [crate::cards::Cards::last_discard].
+    pub last_discard: Option<(FengType, CardType)>,
+    /// The 加杠 just done and the player who did it, see [crate::cards::Cards::jia_gang].
+    pub jia_gang: Option<(FengType, CardType)>,
+    /// The number of each card type shown to all players, see [crate::cards::Cards::seen_tiles].
+    pub seen_tiles: TileCounts,
+}
+
+/// The struct containing the state of a player shown to everyone.
+#[derive(PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SeatView {
+    /// The player.
+    pub side: FengType,
+    /// The number of cards in the player's hand, the cards themselves not shown.
+    pub hand_count: usize,
+    /// The cards in the player's river, known as 牌河 in Chinese.
+    pub river: River,
+    /// The cards in the player's open, known as 副露 in Chinese.
+    pub open: Open,
+    /// The number of red fives(赤宝牌) in the player's open.
+    pub reds_in_open: u8,
+    /// The flower cards(花牌) set aside by the player.
+    pub flowers: Vec<HuaType>,
+    /// The points of the player, known as 点数 in Chinese.
+    pub score: i32,
+    /// Whether the player has declared 立直.
+    pub riichi: bool,
+    /// The waits of the player shown by 开立直, or empty if not declared openly,
+    /// see [crate::cards::Cards::open_riichi_waits].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub waits: Vec<CardType>,
+    /// The cards safe to play against the player, known as 现物 in Chinese,
+    /// see [crate::cards::Cards::safe_tiles].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub safe_tiles: Vec<CardType>,
+    /// The card called from a river into each case(面子) in the player's open,
+    /// see [crate::cards::Player::called].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub called: Vec<Option<CalledCard>>,
+    /// The number of 北 the player has set aside as dora(拔北宝牌) in 三麻,
+    /// see [crate::cards::Cards::nuki_dora].
+    pub nuki: u8,
+    /// The suit the player has declared void in 血战到底, if any,
+    /// see [crate::cards::Cards::declare_void].
+    pub void: Option<SuitType>,
+}
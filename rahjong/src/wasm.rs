@@ -0,0 +1,108 @@
+//! Bindings for JavaScript by `wasm-bindgen`, behind the `wasm-bindgen` feature.
+//!
+//! The core of this module is the [WasmGame] class, an [Engine] of riichi mahjong
+//! which browser clients can run locally, without a server deciding the rules.
+//!
+//! Players are the numbers `0` to `3` in the order of players, i.e. 东 is `0`.
+//! Actions, views and results are plain JavaScript objects
+//! in the same shape as the serde representation of [Action], [PlayerView] and [HandEnd],
+//! e.g. `"Draw"` or `{ "Discard": { "Wan": "One" } }` for an action.
+//!
+//! The crate is built as a library for Rust, so the module is built as a `cdylib` on demand, e.g.
+//!
+//! ```text
+//! cargo rustc -p rahjong --release --target wasm32-unknown-unknown --features wasm-bindgen --crate-type cdylib
+//! wasm-bindgen --target web --out-dir pkg target/wasm32-unknown-unknown/release/rahjong.wasm
+//! ```
+//!
+//! [PlayerView]: crate::view::PlayerView
+
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    card_type::FengType,
+    cards::Cards,
+    engine::{Action, Engine, HandEnd},
+    game::{Game, GameLength},
+};
+
+/// A hand(局) of riichi mahjong driven by the actions of players, see [Engine].
+#[wasm_bindgen]
+pub struct WasmGame {
+    engine: Engine,
+}
+
+#[wasm_bindgen]
+impl WasmGame {
+    /// Creates a game of riichi mahjong whose first hand has just started,
+    /// see [Cards::with_riichi_rules].
+    ///
+    /// The walls are built from the seed if given, see [Cards::with_riichi_rules_seeded].
+    #[wasm_bindgen(constructor)]
+    pub fn new(seed: Option<u64>) -> WasmGame {
+        let cards = match seed {
+            Some(seed) => Cards::with_riichi_rules_seeded(seed),
+            None => Cards::with_riichi_rules(),
+        };
+        Self {
+            engine: Engine::new(Game::new(cards, GameLength::default())),
+        }
+    }
+
+    /// Returns the player who should play a card.
+    #[wasm_bindgen(js_name = activePlayer)]
+    pub fn active_player(&self) -> u8 {
+        index_of(self.engine.cards().active_player)
+    }
+
+    /// Applies the action of the player if it is legal at the moment, see [Engine::apply].
+    ///
+    /// Returns whether the action was applied, or an error if the action is malformed.
+    pub fn apply(&mut self, side: u8, action: JsValue) -> Result<bool, JsValue> {
+        let side = side_of(side)?;
+        let action: Action = serde_wasm_bindgen::from_value(action)?;
+        Ok(self.engine.apply(side, action))
+    }
+
+    /// Returns the actions the player can do at the moment, see [Engine::legal_actions].
+    #[wasm_bindgen(js_name = legalActions)]
+    pub fn legal_actions(&self, side: u8) -> Result<JsValue, JsValue> {
+        let actions = self.engine.legal_actions(side_of(side)?);
+        Ok(serde_wasm_bindgen::to_value(&actions)?)
+    }
+
+    /// Returns the state of the hand shown to the player, see [Cards::view].
+    pub fn view(&self, side: u8) -> Result<JsValue, JsValue> {
+        let view = self.engine.cards().view(side_of(side)?);
+        Ok(serde_wasm_bindgen::to_value(&view)?)
+    }
+
+    /// Returns how the hand ended, or `null` if it is still going on, see [HandEnd].
+    pub fn result(&self) -> Result<JsValue, JsValue> {
+        let result: &Option<HandEnd> = &self.engine.result;
+        Ok(serde_wasm_bindgen::to_value(result)?)
+    }
+
+    /// Takes back the last action applied, see [Engine::undo].
+    pub fn undo(&mut self) -> bool {
+        self.engine.undo()
+    }
+
+    /// Applies again the last action taken back, see [Engine::redo].
+    pub fn redo(&mut self) -> bool {
+        self.engine.redo()
+    }
+}
+
+/// Returns the player of the number, where 东 is `0`.
+fn side_of(index: u8) -> Result<FengType, JsValue> {
+    FengType::ALL
+        .get(index as usize)
+        .copied()
+        .ok_or_else(|| JsValue::from_str("player out of range"))
+}
+
+/// Returns the number of the player, where 东 is `0`.
+fn index_of(side: FengType) -> u8 {
+    FengType::ALL.iter().position(|&s| s == side).unwrap_or(0) as u8
+}
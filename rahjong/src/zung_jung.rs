@@ -0,0 +1,435 @@
+//! Built-in situations and completions of Zung Jung(中庸麻雀), a scoring system of Chinese mahjong.
+//!
+//! The core of this module is [register], which fills the checkers of a [Cards]
+//! with the patterns of the Zung Jung point table, see also [Cards::with_zung_jung_rules].
+//! The points of the patterns are simply added up, so they are the fan(番数) of the completions,
+//! and a hand with no pattern is a chicken hand(鸡和) of 1 point.
+//! The total is limited to [ZUNG_JUNG_LIMIT], see [crate::scoring::zung_jung_points].
+//!
+//! Situation checkers see the hand, the river and the open of a player
+//! together with the last card and where it comes from, see [WinContext],
+//! so the patterns depending on the winds of the round and the seat,
+//! or on the course of the hand, e.g. 天和 and 地和, are not included.
+
+use std::collections::HashMap;
+
+use crate::{
+    analysis::{
+        all_cards, all_cases, complete_hand, winning_concealed_triplets, winning_decompositions,
+        winning_forms, Decomposition, FormType,
+    },
+    card_type::{CardType, Next, RankType, SuitType},
+    cards::{
+        CardSource, Cards, Completion, Hand, Open, River, SituationChecker, SituationFn, WinContext,
+    },
+    case_type::CaseType,
+    situations::{self, has_triplet, suits, triplets_and_pairs, FENGS, JIANS},
+};
+
+/// The most points a hand counts in Zung Jung, known as 封顶 in Chinese.
+pub const ZUNG_JUNG_LIMIT: u16 = 320;
+
+/// Checks if the winning hand is of the complete form.
+fn is_form(
+    hand: &Hand,
+    open: &Open,
+    draw: CardType,
+    context: &WinContext,
+    form: fn(&FormType) -> bool,
+) -> bool {
+    winning_forms(hand, open, draw, context).iter().any(form)
+}
+
+/// Checks if any decomposition of the winning hand, with all its cases, meets the condition.
+fn any_decomposition(
+    hand: &Hand,
+    open: &Open,
+    draw: CardType,
+    context: &WinContext,
+    condition: impl Fn(&Decomposition, &[CaseType]) -> bool,
+) -> bool {
+    winning_decompositions(hand, open, draw, context)
+        .iter()
+        .any(|d| condition(d, &all_cases(d, open)))
+}
+
+/// Returns the starting cards of the 顺子 in the cases.
+fn sequences(cases: &[CaseType]) -> Vec<CardType> {
+    cases
+        .iter()
+        .filter_map(|c| match c {
+            CaseType::Shun(start) => Some(*start),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns the cards of the 刻子 and 杠子 in the cases, sorted.
+fn triplets(cases: &[CaseType]) -> Vec<CardType> {
+    let mut res: Vec<_> = cases
+        .iter()
+        .filter_map(|c| match c {
+            CaseType::Ke(card) | CaseType::Gang(card) | CaseType::AnGang(card) => Some(*card),
+            CaseType::Shun(_) => None,
+        })
+        .collect();
+    res.sort_unstable();
+    res
+}
+
+/// Returns the most concealed 刻子 and 暗杠 of the winning hand, known as 暗刻 in Chinese.
+///
+/// A 刻子 completed by 荣和 is not concealed.
+fn an_ke(hand: &Hand, open: &Open, draw: CardType, context: &WinContext) -> usize {
+    let (_, tsumo) = complete_hand(hand, open, draw);
+    winning_decompositions(hand, open, draw, context)
+        .iter()
+        .map(|d| winning_concealed_triplets(d, open, draw, tsumo, context))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Returns the number of 杠子 in the open.
+fn gangs(open: &Open) -> usize {
+    open.iter()
+        .filter(|c| matches!(c, CaseType::Gang(_) | CaseType::AnGang(_)))
+        .count()
+}
+
+/// Checks if any decomposition has identical 顺子 as given,
+/// i.e. the most copies of a 顺子 and the number of 顺子 with more than one copy.
+fn identical_sequences(
+    hand: &Hand,
+    open: &Open,
+    draw: CardType,
+    context: &WinContext,
+    most: usize,
+    kinds: usize,
+) -> bool {
+    any_decomposition(hand, open, draw, context, |_, cases| {
+        let mut starts = sequences(cases);
+        starts.sort_unstable();
+        let copies: Vec<_> = starts.chunk_by(|a, b| a == b).map(<[_]>::len).collect();
+        copies.iter().copied().max() == Some(most)
+            && copies.iter().filter(|&&n| n > 1).count() == kinds
+    })
+}
+
+/// Checks if the cards are of the same rank in three different number suits.
+fn three_suits(cards: &[CardType]) -> bool {
+    cards.iter().any(|a| {
+        let suits: Vec<_> = cards
+            .iter()
+            .filter(|b| b.rank().is_some() && b.rank() == a.rank())
+            .map(CardType::suit)
+            .collect();
+        [SuitType::Wan, SuitType::Tiao, SuitType::Tong]
+            .iter()
+            .all(|s| suits.contains(s))
+    })
+}
+
+/// Returns the longest run of 刻子 of consecutive ranks in one suit.
+fn consecutive_triplets(cases: &[CaseType]) -> usize {
+    let triplets = triplets(cases);
+    let mut res = 0;
+    let mut run = 0;
+    let mut last: Option<CardType> = None;
+    for card in triplets {
+        let follows = last.is_some_and(|l| {
+            l.rank().is_some() && l.suit() == card.suit() && l.index() + 1 == card.index()
+        });
+        run = if follows { run + 1 } else { 1 };
+        res = res.max(run);
+        last = Some(card);
+    }
+    res
+}
+
+/// 平和: 顺子 only.
+fn ping_he(hand: &Hand, _: &River, open: &Open, draw: CardType, context: &WinContext) -> bool {
+    any_decomposition(hand, open, draw, context, |_, cases| {
+        cases.iter().all(|c| matches!(c, CaseType::Shun(_)))
+    })
+}
+
+/// 九莲宝灯: a concealed 1112345678999 of one suit with any card of the suit.
+fn jiu_lian_bao_deng(hand: &Hand, _: &River, open: &Open, draw: CardType, _: &WinContext) -> bool {
+    let (complete, _) = complete_hand(hand, open, draw);
+    let suit = draw.suit();
+    open.is_empty()
+        && suit != SuitType::Zi
+        && complete.keys().all(|c| c.suit() == suit)
+        && complete.len() == 9
+        && complete
+            .iter()
+            .all(|(c, &n)| n >= if c.is_yao_jiu() { 3 } else { 1 })
+}
+
+/// 对对和: 刻子 and 杠子 only.
+fn dui_dui_he(hand: &Hand, _: &River, open: &Open, draw: CardType, context: &WinContext) -> bool {
+    any_decomposition(hand, open, draw, context, |_, cases| {
+        cases.iter().all(|c| !matches!(c, CaseType::Shun(_)))
+    })
+}
+
+/// 三色同顺: 顺子 of the same ranks in the three number suits.
+fn san_se_tong_shun(
+    hand: &Hand,
+    _: &River,
+    open: &Open,
+    draw: CardType,
+    context: &WinContext,
+) -> bool {
+    any_decomposition(hand, open, draw, context, |_, cases| {
+        three_suits(&sequences(cases))
+    })
+}
+
+/// 三色小同刻: 刻子 of the same rank in two number suits, with the pair of the rank in the third.
+fn san_se_xiao_tong_ke(
+    hand: &Hand,
+    _: &River,
+    open: &Open,
+    draw: CardType,
+    context: &WinContext,
+) -> bool {
+    any_decomposition(hand, open, draw, context, |d, cases| {
+        let triplets = triplets(cases);
+        !three_suits(&triplets) && three_suits(&[triplets, vec![d.pair]].concat())
+    })
+}
+
+/// 一气通贯: the 顺子 of 123, 456 and 789 of one suit.
+fn yi_qi_tong_guan(
+    hand: &Hand,
+    _: &River,
+    open: &Open,
+    draw: CardType,
+    context: &WinContext,
+) -> bool {
+    any_decomposition(hand, open, draw, context, |_, cases| {
+        let starts = sequences(cases);
+        starts.iter().any(|&s| {
+            s.rank() == Some(RankType::One)
+                && starts.contains(&s.next().next().next())
+                && starts.contains(&s.next().next().next().next().next().next())
+        })
+    })
+}
+
+/// 混全带幺: every case and the pair contain a terminal or an honor,
+/// with both 顺子 and honors.
+fn hun_quan_dai_yao(
+    hand: &Hand,
+    _: &River,
+    open: &Open,
+    draw: CardType,
+    context: &WinContext,
+) -> bool {
+    suits(hand, open, draw).contains(&SuitType::Zi)
+        && any_decomposition(hand, open, draw, context, |d, cases| {
+            d.pair.is_yao_jiu()
+                && !sequences(cases).is_empty()
+                && cases
+                    .iter()
+                    .all(|c| c.cards().iter().any(CardType::is_yao_jiu))
+        })
+}
+
+/// 纯全带幺: every case and the pair contain a terminal, with 顺子 and without honors.
+fn chun_quan_dai_yao(
+    hand: &Hand,
+    _: &River,
+    open: &Open,
+    draw: CardType,
+    context: &WinContext,
+) -> bool {
+    !suits(hand, open, draw).contains(&SuitType::Zi)
+        && any_decomposition(hand, open, draw, context, |d, cases| {
+            d.pair.is_yao_jiu()
+                && !sequences(cases).is_empty()
+                && cases
+                    .iter()
+                    .all(|c| c.cards().iter().any(CardType::is_yao_jiu))
+        })
+}
+
+/// 混幺九: terminals and honors only, with both of them.
+fn hun_yao_jiu(hand: &Hand, _: &River, open: &Open, draw: CardType, _: &WinContext) -> bool {
+    let cards = all_cards(hand, open, draw);
+    cards.iter().all(CardType::is_yao_jiu)
+        && cards.iter().any(|c| c.rank().is_some())
+        && cards.iter().any(|c| c.rank().is_none())
+}
+
+/// 清幺九: terminals only.
+fn qing_yao_jiu(hand: &Hand, _: &River, open: &Open, draw: CardType, _: &WinContext) -> bool {
+    all_cards(hand, open, draw)
+        .iter()
+        .all(|c| c.is_yao_jiu() && c.rank().is_some())
+}
+
+/// Returns the situation checkers of Zung Jung, named after the patterns in Chinese.
+pub fn situation_checkers() -> HashMap<&'static str, SituationChecker> {
+    let checkers: [(&'static str, SituationFn); 44] = [
+        ("平和", ping_he),
+        ("门前清", situations::men_qian_qing),
+        ("断幺", situations::duan_yao_jiu),
+        ("混一色", situations::hun_yi_se),
+        ("清一色", situations::qing_yi_se),
+        ("九莲宝灯", jiu_lian_bao_deng),
+        ("番牌白", |h, _, o, d, _| has_triplet(h, o, d, JIANS[0])),
+        ("番牌发", |h, _, o, d, _| has_triplet(h, o, d, JIANS[1])),
+        ("番牌中", |h, _, o, d, _| has_triplet(h, o, d, JIANS[2])),
+        ("小三元", |h, _, o, d, _| {
+            triplets_and_pairs(h, o, d, &JIANS) == (2, 1)
+        }),
+        ("大三元", |h, _, o, d, _| {
+            triplets_and_pairs(h, o, d, &JIANS).0 == 3
+        }),
+        ("小三风", |h, _, o, d, _| {
+            triplets_and_pairs(h, o, d, &FENGS) == (2, 1)
+        }),
+        ("大三风", |h, _, o, d, _| {
+            triplets_and_pairs(h, o, d, &FENGS) == (3, 0)
+        }),
+        ("小四喜", |h, _, o, d, _| {
+            triplets_and_pairs(h, o, d, &FENGS) == (3, 1)
+        }),
+        ("大四喜", |h, _, o, d, _| {
+            triplets_and_pairs(h, o, d, &FENGS).0 == 4
+        }),
+        ("字一色", |h, _, o, d, _| {
+            suits(h, o, d) == [SuitType::Zi]
+        }),
+        ("对对和", dui_dui_he),
+        ("二暗刻", |h, _, o, d, ctx| an_ke(h, o, d, ctx) == 2),
+        ("三暗刻", |h, _, o, d, ctx| an_ke(h, o, d, ctx) == 3),
+        ("四暗刻", |h, _, o, d, ctx| an_ke(h, o, d, ctx) == 4),
+        ("一杠", |_, _, o, _, _| gangs(o) == 1),
+        ("二杠", |_, _, o, _, _| gangs(o) == 2),
+        ("三杠", |_, _, o, _, _| gangs(o) == 3),
+        ("四杠", |_, _, o, _, _| gangs(o) == 4),
+        ("一般高", |h, _, o, d, ctx| {
+            identical_sequences(h, o, d, ctx, 2, 1)
+        }),
+        ("两般高", |h, _, o, d, ctx| {
+            identical_sequences(h, o, d, ctx, 2, 2)
+        }),
+        ("一色三同顺", |h, _, o, d, ctx| {
+            identical_sequences(h, o, d, ctx, 3, 1)
+        }),
+        ("一色四同顺", |h, _, o, d, ctx| {
+            identical_sequences(h, o, d, ctx, 4, 1)
+        }),
+        ("三色同顺", san_se_tong_shun),
+        ("三色小同刻", san_se_xiao_tong_ke),
+        ("三色同刻", |h, _, o, d, ctx| {
+            any_decomposition(h, o, d, ctx, |_, cases| three_suits(&triplets(cases)))
+        }),
+        ("一气通贯", yi_qi_tong_guan),
+        ("三连刻", |h, _, o, d, ctx| {
+            any_decomposition(h, o, d, ctx, |_, cases| consecutive_triplets(cases) == 3)
+        }),
+        ("四连刻", |h, _, o, d, ctx| {
+            any_decomposition(h, o, d, ctx, |_, cases| consecutive_triplets(cases) == 4)
+        }),
+        ("混全带幺", hun_quan_dai_yao),
+        ("纯全带幺", chun_quan_dai_yao),
+        ("混幺九", hun_yao_jiu),
+        ("清幺九", qing_yao_jiu),
+        ("海底捞月", |_, _, _, _, context| {
+            context.source == CardSource::Mountain && context.is_last
+        }),
+        ("河底捞鱼", |_, _, _, _, context| {
+            context.source == CardSource::River && context.is_last
+        }),
+        ("岭上开花", |_, _, _, _, context| {
+            context.source == CardSource::Replacement
+        }),
+        ("抢杠", |_, _, _, _, context| {
+            context.source == CardSource::Robbed
+        }),
+        ("十三幺", |h, _, o, d, ctx| {
+            is_form(h, o, d, ctx, |f| matches!(f, FormType::GuoShi(_)))
+        }),
+        ("七对", |h, _, o, d, ctx| {
+            is_form(h, o, d, ctx, |f| matches!(f, FormType::QiDui(_)))
+        }),
+    ];
+    situations::collect_checkers(checkers)
+}
+
+/// Returns the completions of Zung Jung, i.e. the patterns and their points as the fan(番数).
+///
+/// Each pattern requires the situation of its name,
+/// and is forbidden by the patterns including it, e.g. 一般高 by 两般高,
+/// the 番牌 by 小三元 and 大三元, and 混幺九 and 门前清 by 十三幺.
+/// 鸡和 forbids every other pattern.
+pub fn completions() -> Vec<Completion> {
+    let mut res: Vec<_> = [
+        ("平和", vec![], 5),
+        ("门前清", vec!["十三幺"], 5),
+        ("断幺", vec![], 5),
+        ("混一色", vec![], 40),
+        ("清一色", vec!["九莲宝灯"], 80),
+        ("九莲宝灯", vec![], 480),
+        ("番牌白", vec!["小三元", "大三元"], 10),
+        ("番牌发", vec!["小三元", "大三元"], 10),
+        ("番牌中", vec!["小三元", "大三元"], 10),
+        ("小三元", vec![], 40),
+        ("大三元", vec![], 130),
+        ("小三风", vec![], 30),
+        ("大三风", vec![], 120),
+        ("小四喜", vec![], 320),
+        ("大四喜", vec![], 400),
+        ("字一色", vec![], 320),
+        ("对对和", vec![], 30),
+        ("二暗刻", vec![], 5),
+        ("三暗刻", vec![], 30),
+        ("四暗刻", vec![], 125),
+        ("一杠", vec![], 5),
+        ("二杠", vec![], 20),
+        ("三杠", vec![], 120),
+        ("四杠", vec![], 480),
+        ("一般高", vec!["两般高", "一色三同顺", "一色四同顺"], 10),
+        ("两般高", vec!["一色四同顺"], 60),
+        ("一色三同顺", vec!["一色四同顺"], 120),
+        ("一色四同顺", vec![], 480),
+        ("三色同顺", vec![], 35),
+        ("三色小同刻", vec![], 30),
+        ("三色同刻", vec![], 120),
+        ("一气通贯", vec![], 40),
+        ("三连刻", vec!["四连刻"], 100),
+        ("四连刻", vec![], 200),
+        ("混全带幺", vec![], 40),
+        ("纯全带幺", vec![], 50),
+        ("混幺九", vec!["十三幺"], 100),
+        ("清幺九", vec![], 400),
+        ("海底捞月", vec![], 10),
+        ("河底捞鱼", vec![], 10),
+        ("岭上开花", vec![], 10),
+        ("抢杠", vec![], 10),
+        ("十三幺", vec![], 160),
+        ("七对", vec![], 30),
+    ]
+    .into_iter()
+    .map(|(name, forbidden, fan)| {
+        Completion::builder(name)
+            .require(name)
+            .forbid_all(forbidden)
+            .fan(fan)
+            .build()
+    })
+    .collect();
+    let names: Vec<_> = res.iter().map(|c| c.name).collect();
+    res.push(Completion::builder("鸡和").forbid_all(names).fan(1).build());
+    res
+}
+
+/// Registers the situations and completions of Zung Jung into the cards.
+pub fn register(cards: &mut Cards) {
+    cards.situation_checkers.extend(situation_checkers());
+    cards.completion_checkers.extend(completions());
+}
@@ -0,0 +1,133 @@
+use rahjong::{
+    analysis,
+    card_type::CardType,
+    cards::{parse_hand, Hand},
+    case_type::CaseType,
+    shanten_table,
+    tile_counts::TileCounts,
+};
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+fn card(s: &str) -> CardType {
+    s.parse().unwrap()
+}
+
+fn counts(s: &str) -> TileCounts {
+    TileCounts::from(&parse_hand(s).unwrap())
+}
+
+#[test]
+fn tile_counts_add_and_sub() {
+    let a = counts("112m");
+    let b = counts("13m");
+    assert_eq!((a + b)[card("1m")], 3);
+    assert_eq!((a + b).total(), 5);
+    assert_eq!((a - b)[card("1m")], 1);
+    assert_eq!((a - b).total(), 2);
+    let hand: Hand = (a + b).into();
+    assert!(hand == parse_hand("11123m").unwrap());
+}
+
+#[test]
+fn tile_counts_saturate() {
+    let a = counts("112m");
+    let b = counts("13m");
+    // There is no 3m to take from, and one 1m only to take two from.
+    assert_eq!((b - a)[card("3m")], 1);
+    assert_eq!((b - a)[card("1m")], 0);
+    assert_eq!((TileCounts::new() - a).total(), 0);
+    let mut full = TileCounts::new();
+    full[card("1m")] = u8::MAX;
+    full += a;
+    assert_eq!(full[card("1m")], u8::MAX);
+    assert_eq!(full[card("2m")], 1);
+}
+
+#[test]
+fn seven_pairs_shanten_is_below_standard() {
+    let breakdown = analysis::shanten_breakdown(&parse_hand("1133557799m1234z").unwrap(), &vec![]);
+    assert_eq!(breakdown.seven_pairs, Some(1));
+    assert!(breakdown.standard > 1);
+    assert_eq!(breakdown.min(), 1);
+    // Seven pairs count for concealed hands only.
+    let open = vec![CaseType::Ke(card("5z"))];
+    let breakdown = analysis::shanten_breakdown(&parse_hand("1133557799m1z").unwrap(), &open);
+    assert_eq!(breakdown.seven_pairs, None);
+}
+
+#[test]
+fn useful_tiles_of_a_two_shanten_hand() {
+    let hand = parse_hand("123m456p789s1234z").unwrap();
+    assert_eq!(analysis::shanten(&hand, &vec![]), 2);
+    // Only a pair of 字牌 gets the hand nearer, the sequences are complete already.
+    let useful = analysis::useful_tiles(&hand, &vec![]);
+    assert!(useful == [card("1z"), card("2z"), card("3z"), card("4z")]);
+    let hand = parse_hand("13m46p79s12z").unwrap();
+    let open = vec![CaseType::Ke(card("5z")), CaseType::Ke(card("6z"))];
+    assert_eq!(analysis::shanten(&hand, &open), 2);
+    let useful = analysis::useful_tiles(&hand, &open);
+    // Each card held pairs up, and 2m, 5p or 8s completes a 嵌张.
+    let expected = parse_hand("123m456p789s12z").unwrap();
+    assert!(useful == expected.keys().copied().collect::<Vec<_>>());
+}
+
+#[test]
+fn suit_tables_score_standard_hands() {
+    assert_eq!(
+        shanten_table::standard_shanten(&counts("123m456p789s11z"), 1),
+        -1
+    );
+    assert_eq!(
+        shanten_table::standard_shanten(&counts("123m456p789s1z"), 1),
+        0
+    );
+    // 16 cards of 台湾麻将 need five cases.
+    assert_eq!(
+        shanten_table::standard_shanten(&counts("123456789m123456p1z"), 0),
+        0
+    );
+    assert!(shanten_table::is_standard_complete(&counts(
+        "123456789m123456p11z"
+    )));
+    assert!(!shanten_table::is_standard_complete(&counts(
+        "123456789m12345p111z"
+    )));
+    // The tables count waiting for a card all four of which are held(空听) as ready, unlike the analysis.
+    let hand = parse_hand("123m456p789s1111z").unwrap();
+    assert_eq!(
+        shanten_table::standard_shanten(&counts("123m456p789s1111z"), 0),
+        0
+    );
+    assert_eq!(analysis::shanten(&hand, &vec![]), 1);
+}
+
+#[test]
+fn suit_tables_agree_with_the_waits() {
+    let mut rng = StdRng::seed_from_u64(7);
+    let mut wall: Vec<_> = (0..CardType::COUNT)
+        .filter_map(CardType::from_index)
+        .flat_map(|c| [c; 4])
+        .collect();
+    for _ in 0..2000 {
+        wall.shuffle(&mut rng);
+        // A single suit with the winds makes ready and complete hands common.
+        let pool: Vec<_> = wall
+            .iter()
+            .copied()
+            .filter(|c| c.index() < 9 || c.index() >= 27)
+            .collect();
+        for (size, melds) in [(13, 0), (14, 0), (10, 1), (8, 2)] {
+            let mut hand = Hand::new();
+            for &c in &pool[..size] {
+                *hand.entry(c).or_default() += 1;
+            }
+            let open = vec![CaseType::Ke(card("9s")); melds];
+            let shanten = analysis::shanten(&hand, &open);
+            if size % 3 == 2 {
+                assert_eq!(shanten == -1, analysis::is_complete(&hand, &open));
+            } else {
+                assert_eq!(shanten == 0, analysis::is_tenpai(&hand, &open));
+            }
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use rahjong::{
+    card_type::{parse_cards, CardType},
+    cards::parse_hand,
+    case_type::CaseType,
+    error::ParseCardError,
+    glyph,
+    river_type::RiverType,
+};
+
+fn card(s: &str) -> CardType {
+    s.parse().unwrap()
+}
+
+#[test]
+fn cards_are_shown_in_the_notation() {
+    assert_eq!(card("3m").to_string(), "3m");
+    assert_eq!(format!("{:?}", card("7p")), "7p");
+    assert_eq!(card("1z").to_string(), "东");
+    assert_eq!(card("7z").to_string(), "中");
+    assert_eq!(CaseType::Shun(card("1s")).to_string(), "123s");
+    assert_eq!(CaseType::AnGang(card("7z")).to_string(), "[中中中中]");
+    assert_eq!(RiverType::Drawing(card("9m")).to_string(), "9m*");
+    assert_eq!(format!("{:?}", vec![card("1m"), card("2z")]), "[1m, 南]");
+}
+
+#[test]
+fn shown_cards_parse_back() {
+    assert_eq!(card("中"), card("7z"));
+    // 0 stands for the red five(赤宝牌).
+    assert_eq!(card("0m"), card("5m"));
+    let all: Vec<_> = (0..34).map(|i| CardType::from_index(i).unwrap()).collect();
+    let text: String = all.iter().map(|c| c.to_string()).collect();
+    assert_eq!(parse_cards(&text).unwrap(), all);
+    let hand = parse_hand("123m456p789s1234567z").unwrap();
+    assert_eq!(hand.values().sum::<u8>(), 16);
+}
+
+#[test]
+fn malformed_notation_is_rejected() {
+    assert_eq!(parse_cards("8z"), Err(ParseCardError::InvalidRank('8')));
+    assert_eq!(parse_cards("12"), Err(ParseCardError::MissingSuit));
+    assert_eq!(parse_cards("1x"), Err(ParseCardError::InvalidChar('x')));
+    assert_eq!(
+        "12m".parse::<CardType>(),
+        Err(ParseCardError::NotSingleCard)
+    );
+    assert_eq!(parse_hand("11111m"), Err(ParseCardError::TooManyCopies));
+}
+
+#[test]
+fn cards_have_their_glyphs() {
+    assert_eq!(glyph::card(card("1z")), '🀀');
+    assert_eq!(glyph::card(card("中")), '🀄');
+    assert_eq!(glyph::card(card("白")), '🀆');
+    assert_eq!(glyph::card(card("1m")), '🀇');
+    assert_eq!(glyph::card(card("9s")), '🀘');
+    assert_eq!(glyph::card(card("9p")), '🀡');
+}
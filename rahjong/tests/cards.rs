@@ -0,0 +1,288 @@
+use rahjong::{
+    analysis,
+    card_type::{CardType, FengType, SuitType},
+    cards::{parse_hand, Cards},
+    case_type::CaseType,
+    error::{CallError, PlayError},
+    river_type::RiverType,
+    ruleset::RulesetId,
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+fn card(s: &str) -> CardType {
+    s.parse().unwrap()
+}
+
+#[test]
+fn remaining_tiles_after_discards() {
+    let mut cards = Cards::new();
+    cards.seen_tiles = Default::default();
+    assert_eq!(cards.remaining_tiles_of_suit(SuitType::Wan), 36);
+    cards.players[0].hand = parse_hand("2233m456p789s1122z").unwrap();
+    cards.play(RiverType::Normal(card("2m"))).unwrap();
+    cards.active_player = FengType::Nan;
+    cards.players[1].hand = parse_hand("3m456p789s112233z4z").unwrap();
+    cards.play(RiverType::Normal(card("3m"))).unwrap();
+    assert_eq!(cards.remaining_tiles_of_suit(SuitType::Wan), 34);
+    assert_eq!(cards.remaining_tiles_of_suit(SuitType::Zi), 28);
+    assert_eq!(cards.remaining_tiles_of_suit(SuitType::Tong), 36);
+}
+
+#[test]
+fn chi_keeps_other_copies_of_the_called_card() {
+    let mut cards = Cards::new();
+    cards.active_player = FengType::Nan;
+    cards.players[1].hand = parse_hand("3m456p789s112233z4z").unwrap();
+    cards.play(RiverType::Normal(card("3m"))).unwrap();
+    cards.players[2].hand = parse_hand("3345m456p789s11z").unwrap();
+    cards
+        .call(
+            CaseType::Shun(card("3m")),
+            FengType::Xi,
+            card("3m"),
+            vec![card("4m"), card("5m")],
+        )
+        .unwrap();
+    assert!(cards.players[2].hand == parse_hand("33m456p789s11z").unwrap());
+    assert!(cards.players[2].open == [CaseType::Shun(card("3m"))]);
+}
+
+#[test]
+fn tsumo_hand_is_not_counted_twice() {
+    let mut cards = Cards::new();
+    cards.players[0].hand = parse_hand("123m456p789s11222z").unwrap();
+    assert!(cards.winning_tile_is_in_hand(FengType::Dong, card("1z")));
+    let hand = cards
+        .winning_hand(FengType::Dong, card("1z"), true)
+        .unwrap();
+    assert_eq!(hand.values().map(|&n| n as usize).sum::<usize>(), 14);
+    assert_eq!(hand[&card("1z")], 2);
+    let decompositions = analysis::decompositions(&hand, &cards.players[0].open);
+    assert_eq!(decompositions.len(), 1);
+    assert!(decompositions[0].pair == card("1z"));
+    // The card drawn for 自摸 must be in the hand.
+    assert!(cards
+        .winning_hand(FengType::Dong, card("3z"), true)
+        .is_none());
+    // For 荣和 the card is added, making 15 cards here.
+    let hand = cards
+        .winning_hand(FengType::Dong, card("1z"), false)
+        .unwrap();
+    assert!(!analysis::is_complete(&hand, &cards.players[0].open));
+}
+
+#[test]
+fn turn_flags_are_cleared_for_the_next_hand() {
+    let mut cards = Cards::with_riichi_rules();
+    cards.players[0].hand = parse_hand("123m456p789s1122z5z").unwrap();
+    assert!(cards.declare_riichi(RiverType::Drawing(card("5z"))).is_ok());
+    assert!(cards.ippatsu.contains(&FengType::Dong));
+    cards.active_player = FengType::Nan;
+    let drawn = cards.draw().unwrap();
+    assert!(cards.drawn == Some(drawn));
+    cards.passed_ron.insert(FengType::Xi);
+    cards.rinshan = true;
+    cards.reset_for_next_hand(FengType::Nan);
+    assert!(cards.ippatsu.is_empty());
+    assert!(cards.passed_ron.is_empty());
+    assert!(!cards.rinshan);
+    assert!(cards.drawn.is_none());
+    assert!(!cards.is_riichi(FengType::Dong));
+    assert!(cards.players[0].river.is_empty());
+    assert!(cards.active_player == FengType::Nan);
+}
+
+#[test]
+fn draw_for_a_player_out_of_turn() {
+    let mut cards = Cards::new();
+    assert!(cards.active_player == FengType::Dong);
+    let top = *cards.card_mountain.last().unwrap();
+    let before = cards.players[1].hand.get(&top).copied().unwrap_or(0);
+    assert!(cards.draw_for(FengType::Nan) == Some(top));
+    assert_eq!(cards.players[1].hand[&top], before + 1);
+    assert!(cards.active_player == FengType::Dong);
+    // 南 holds 14 cards now, and may not draw again before playing.
+    assert!(cards.draw_for(FengType::Nan).is_none());
+    assert!(cards.drawn.is_none());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn json_value_has_the_card_states() {
+    let cards = Cards::new();
+    let value = cards.as_json_value().unwrap();
+    assert_eq!(value["card_mountain"].as_array().unwrap().len(), 70);
+    assert_eq!(value["players"].as_array().unwrap().len(), 4);
+    assert!(value["players"][0]["hand"].is_array());
+    assert_eq!(value["seen_tiles"].as_array().unwrap().len(), 34);
+    let restored = Cards::from_json_value(value).unwrap();
+    assert!(restored.card_mountain == cards.card_mountain);
+    assert!(restored.players[0].hand == cards.players[0].hand);
+}
+
+#[test]
+fn kuikae_locks_the_cards_of_the_call() {
+    let called = |kuikae: bool| {
+        let mut cards = Cards::new();
+        cards.config.kuikae = kuikae;
+        cards.players[0].hand = parse_hand("3m456p789s112233z4z").unwrap();
+        cards.play(RiverType::Normal(card("3m"))).unwrap();
+        cards.players[1].hand = parse_hand("34566m456p789s12z").unwrap();
+        cards
+            .call(
+                CaseType::Shun(card("3m")),
+                FengType::Nan,
+                card("3m"),
+                vec![card("4m"), card("5m")],
+            )
+            .unwrap();
+        cards
+    };
+    let cards = called(false);
+    // The called card itself, and the one completing the same shape(筋食替).
+    assert_eq!(cards.validate_discard(card("3m")), Err(PlayError::Kuikae));
+    assert_eq!(cards.validate_discard(card("6m")), Err(PlayError::Kuikae));
+    assert_eq!(cards.validate_discard(card("2z")), Ok(()));
+    let mut cards = called(true);
+    assert_eq!(cards.validate_discard(card("6m")), Ok(()));
+    assert!(cards.play(RiverType::Normal(card("6m"))).is_ok());
+}
+
+#[test]
+fn riichi_needs_the_points_of_the_deposit() {
+    let mut cards = Cards::new();
+    assert_eq!(cards.score(FengType::Dong), 25000);
+    cards.players[0].hand = parse_hand("123m456p789s1122z5z").unwrap();
+    assert_eq!(cards.validate_riichi(card("5z")), Ok(()));
+    *cards.score_mut(FengType::Dong) = 999;
+    assert_eq!(
+        cards.validate_riichi(card("5z")),
+        Err(PlayError::NotEnoughPoints)
+    );
+}
+
+#[test]
+fn cards_always_carry_their_rules() {
+    let cards = Cards::default();
+    assert!(cards.rules().allows_riichi());
+    assert_eq!(cards.rules().min_fan(), 0);
+    assert_eq!(Cards::with_mcr_rules().rules().min_fan(), 8);
+    assert_eq!(Cards::with_hkos_rules().rules().min_fan(), 3);
+    assert!(Cards::with_sanma_rules().view(FengType::Dong).rules == RulesetId::Sanma);
+    assert!(Cards::new().view(FengType::Dong).rules == RulesetId::Riichi);
+    let mut cards = Cards::with_hkos_rules();
+    cards.players[0].hand = parse_hand("123m456p789s1122z5z").unwrap();
+    assert_eq!(
+        cards.validate_riichi(card("5z")),
+        Err(PlayError::RiichiNotAllowed)
+    );
+}
+
+#[test]
+fn sanma_skips_bei_in_the_order_of_players() {
+    let mut cards = Cards::with_sanma_rules();
+    cards.active_player = FengType::Xi;
+    cards.players[0].hand = parse_hand("55p").unwrap();
+    cards.players[1].hand = parse_hand("555p").unwrap();
+    let calls = cards.check_call(card("5p"));
+    assert!(
+        calls
+            == [
+                (FengType::Dong, CaseType::Ke(card("5p"))),
+                (FengType::Nan, CaseType::Gang(card("5p"))),
+                (FengType::Nan, CaseType::Ke(card("5p"))),
+            ]
+    );
+    assert!(!cards.tenpai_players().contains(&FengType::Bei));
+}
+
+#[test]
+fn seeded_riichi_rules_deal_the_seeded_wall() {
+    let cards = Cards::with_riichi_rules_seeded(7);
+    assert!(cards.wall() == Cards::new_with_seed(7).wall());
+    assert!(cards.wall() == Cards::with_riichi_rules_seeded(7).wall());
+    assert!(!cards.completion_checkers.is_empty());
+    assert!(cards
+        .players()
+        .iter()
+        .all(|&side| cards.score(side) == 25000));
+}
+
+#[test]
+fn draw_for_leaves_the_turn_untouched() {
+    let mut cards = Cards::new();
+    cards.players[0].hand = parse_hand("3m456p789s112233z4z").unwrap();
+    cards.play(RiverType::Normal(card("3m"))).unwrap();
+    assert!(cards.draw_for(FengType::Xi).is_some());
+    assert!(cards.last_discard == Some((FengType::Dong, card("3m"))));
+    // Nothing changes when the mountain is empty.
+    cards.card_mountain.clear();
+    let hand = cards.players[3].hand.clone();
+    assert!(cards.draw_for(FengType::Bei).is_none());
+    assert!(cards.players[3].hand == hand);
+}
+
+#[test]
+fn calls_from_the_river_need_the_card_just_played() {
+    let mut cards = Cards::new();
+    cards.players[1].hand = parse_hand("3345m456p789s11z").unwrap();
+    let chi = |cards: &Cards, discard: &str| {
+        cards.validate_call(
+            CaseType::Shun(card("3m")),
+            FengType::Nan,
+            card(discard),
+            &[card("4m"), card("5m")],
+        )
+    };
+    // Nothing has been played yet.
+    assert_eq!(chi(&cards, "3m"), Err(CallError::NotLastDiscard));
+    cards.players[0].hand = parse_hand("6m456p789s112233z4z").unwrap();
+    cards.play(RiverType::Normal(card("6m"))).unwrap();
+    assert_eq!(chi(&cards, "3m"), Err(CallError::NotLastDiscard));
+    let pon = cards.validate_call(
+        CaseType::Ke(card("3m")),
+        FengType::Nan,
+        card("3m"),
+        &[card("3m"), card("3m")],
+    );
+    assert_eq!(pon, Err(CallError::NotLastDiscard));
+    assert!(cards
+        .call(
+            CaseType::Shun(card("4m")),
+            FengType::Nan,
+            card("6m"),
+            vec![card("4m"), card("5m")],
+        )
+        .is_ok());
+    assert!(cards.players[0].river[0].claimed);
+}
+
+#[test]
+fn cards_sampled_from_a_view_keep_the_rules() {
+    let mut rng = StdRng::seed_from_u64(1);
+    for cards in [
+        Cards::with_mcr_rules(),
+        Cards::with_hkos_rules(),
+        Cards::with_zung_jung_rules(),
+        Cards::with_sanma_rules(),
+        Cards::with_sichuan_rules(),
+    ] {
+        let view = cards.view(FengType::Dong);
+        let sample = Cards::sample_from_view(&view, &mut rng);
+        assert!(sample.rules().id() == cards.rules().id());
+        // No card is dealt more often than the rules have copies of it.
+        let mut dealt = [0u8; 34];
+        for &side in sample.players() {
+            for (card, &n) in sample.hand(side) {
+                dealt[card.index()] += n;
+            }
+        }
+        for &card in sample.card_mountain.iter().chain(&sample.dead_wall) {
+            dealt[card.index()] += 1;
+        }
+        assert!((0..34).all(|i| {
+            let card = CardType::from_index(i).unwrap();
+            dealt[i] <= sample.rules().copies(card)
+        }));
+    }
+}
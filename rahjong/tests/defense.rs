@@ -0,0 +1,13 @@
+use rahjong::{card_type::FengType, cards::Cards, defense};
+
+#[test]
+fn dangers_are_against_the_other_players() {
+    let cards = Cards::with_riichi_rules();
+    let dangers = defense::dangers(&cards, FengType::Nan);
+    assert!(dangers
+        .keys()
+        .eq(&[FengType::Dong, FengType::Xi, FengType::Bei]));
+    let cards = Cards::with_sanma_rules();
+    let dangers = defense::dangers(&cards, FengType::Dong);
+    assert!(dangers.keys().eq(&[FengType::Nan, FengType::Xi]));
+}
@@ -0,0 +1,125 @@
+use rahjong::{
+    bot::{Bot, EfficiencyBot},
+    card_type::{CardType, FengType},
+    cards::{parse_hand, Cards},
+    engine::{Action, Engine, HandEnd},
+    event::GameEvent,
+    game::{Game, GameLength, RIICHI_DEPOSIT},
+};
+
+fn card(s: &str) -> CardType {
+    s.parse().unwrap()
+}
+
+/// Plays the hand with [EfficiencyBot] for every player, as the terminal example does with `--auto`,
+/// and returns the engine when the hand ends, or after too many actions.
+fn play_with_bots(seed: u64) -> Engine {
+    let cards = Cards::with_riichi_rules_seeded(seed);
+    let mut engine = Engine::new(Game::new(cards, GameLength::default()));
+    for _ in 0..1000 {
+        if engine.result.is_some() {
+            break;
+        }
+        let acting = match &engine.claims {
+            Some(_) => engine.pending_responses(),
+            None => vec![engine.cards().active_player],
+        };
+        for side in acting {
+            let actions = engine.legal_actions(side);
+            if actions.is_empty() {
+                continue;
+            }
+            let action = EfficiencyBot.choose_action(&engine.cards().view(side), &actions);
+            assert!(actions.contains(&action));
+            assert!(engine.apply(side, action));
+        }
+    }
+    engine
+}
+
+#[test]
+fn bots_play_a_hand_to_the_end() {
+    for seed in 0..8 {
+        let engine = play_with_bots(seed);
+        let cards = engine.cards();
+        // The points move between the players, or onto the table as 立直 deposits.
+        let total: i32 = cards.players().iter().map(|&side| cards.score(side)).sum();
+        assert_eq!(
+            total + engine.game.riichi_sticks as i32 * RIICHI_DEPOSIT,
+            100000
+        );
+        match &engine.result {
+            Some(HandEnd::Tsumo(side)) => assert!(cards.players().contains(side)),
+            Some(HandEnd::Ron { winners, from }) => {
+                assert!(!winners.is_empty() && !winners.contains(from));
+            }
+            Some(HandEnd::Draw(_)) => {}
+            None => panic!("the hand of seed {seed} does not end"),
+        }
+        assert!(FengType::ALL
+            .iter()
+            .all(|&side| engine.legal_actions(side).is_empty()));
+    }
+}
+
+#[test]
+fn completions_are_settled_as_the_hand_ends() {
+    let mut cards = Cards::with_riichi_rules_seeded(1);
+    cards.players[0].hand = parse_hand("234m456p678s23s55z").unwrap();
+    *cards.card_mountain.last_mut().unwrap() = card("4s");
+    let mut engine = Engine::new(Game::new(cards, GameLength::default()));
+    let before = FengType::ALL.map(|side| engine.cards().score(side));
+    assert!(engine.apply(FengType::Dong, Action::Draw));
+    assert!(engine.apply(FengType::Dong, Action::Tsumo));
+    let [win] = &engine.wins[..] else {
+        panic!("the completion is not settled");
+    };
+    assert!(win.winner == FengType::Dong && win.points() > 0);
+    assert_eq!(
+        engine.cards().score(FengType::Dong),
+        before[0] + win.points() as i32
+    );
+    for &(side, points) in &win.payment.payers {
+        assert_eq!(
+            engine.cards().score(side),
+            before[side as usize] - points as i32
+        );
+    }
+    // The dealer repeats with a counter.
+    assert!(engine.next_hand());
+    assert!(engine.cards().dealer == FengType::Dong && engine.game.honba == 1);
+    assert!(engine.result.is_none() && engine.wins.is_empty() && engine.events.len() == 1);
+}
+
+#[test]
+fn draws_are_recorded() {
+    let cards = Cards::with_riichi_rules_seeded(1);
+    let mut engine = Engine::new(Game::new(cards, GameLength::default()));
+    let top = *engine.cards().card_mountain.last().unwrap();
+    let recorded = engine.events.len();
+    assert!(engine.apply(FengType::Dong, Action::Draw));
+    assert!(
+        engine.events[recorded..]
+            == [GameEvent::Draw {
+                side: FengType::Dong,
+                card: top,
+                replacement: false,
+            }]
+    );
+    // A replacement card(岭上牌) is recorded as one.
+    let mut cards = Cards::with_riichi_rules_seeded(1);
+    cards.players[0].hand = parse_hand("1111m234p567s234z").unwrap();
+    let mut engine = Engine::new(Game::new(cards, GameLength::default()));
+    assert!(engine.apply(FengType::Dong, Action::Draw));
+    assert!(engine.apply(FengType::Dong, Action::AnKan(card("1m"))));
+    let replacement = engine.cards().dead_wall[0];
+    assert!(engine.apply(FengType::Dong, Action::Draw));
+    assert!(
+        engine.events.last()
+            == Some(&GameEvent::Draw {
+                side: FengType::Dong,
+                card: replacement,
+                replacement: true,
+            })
+    );
+}
@@ -0,0 +1,48 @@
+#![cfg(feature = "ffi")]
+
+use std::ffi::CStr;
+
+use rahjong::ffi::*;
+
+#[test]
+fn game_through_the_c_api() {
+    unsafe {
+        let game = rahjong_game_new(true, 7);
+        assert_eq!(rahjong_active_player(game), 0);
+        let mut buffer = [RahjongAction { kind: 0, card: 0 }; 64];
+        let n = rahjong_legal_actions(game, 0, buffer.as_mut_ptr(), 64);
+        assert_eq!(n, 1);
+        assert_eq!(buffer[0].kind, RahjongActionKind::Draw as u8);
+        assert!(rahjong_apply(game, 0, buffer[0]));
+        let n = rahjong_legal_actions(game, 0, buffer.as_mut_ptr(), 64);
+        let discard = *buffer[..n]
+            .iter()
+            .find(|a| a.kind == RahjongActionKind::Discard as u8)
+            .unwrap();
+        // An unknown kind is rejected rather than read as an action.
+        let unknown = RahjongAction {
+            kind: 200,
+            card: discard.card,
+        };
+        assert!(!rahjong_apply(game, 0, unknown));
+        assert!(rahjong_apply(game, 0, discard));
+        let view = rahjong_view_json(game, 1);
+        assert!(!view.is_null());
+        assert!(CStr::from_ptr(view).to_str().unwrap().contains("\"seats\""));
+        rahjong_string_free(view);
+        assert!(rahjong_undo(game));
+        assert!(rahjong_redo(game));
+        assert_eq!(rahjong_result(game), 0);
+        rahjong_game_free(game);
+    }
+}
+
+#[test]
+fn action_kinds_round_trip() {
+    for kind in 0..=12 {
+        assert_eq!(RahjongActionKind::try_from(kind).map(|k| k as u8), Ok(kind));
+    }
+    assert!(RahjongActionKind::try_from(13).is_err());
+    let action = RahjongAction { kind: 13, card: 0 };
+    assert!(action.action().is_none());
+}
@@ -0,0 +1,76 @@
+use rahjong::{
+    card_type::FengType,
+    cards::Cards,
+    game::{Game, RIICHI_DEPOSIT},
+    scoring::{hkos_payment, riichi_payment},
+    standings::UmaOka,
+};
+
+#[test]
+fn counters_and_sticks_are_paid_in_riichi_only() {
+    let mut game = Game::new(Cards::with_riichi_rules(), Default::default());
+    game.honba = 2;
+    game.riichi_sticks = 1;
+    // 2000 base points by 荣和 of a non-dealer are 8000 points.
+    let payment = game.settle_win(riichi_payment(
+        2000,
+        FengType::Nan,
+        FengType::Dong,
+        Some(FengType::Xi),
+    ));
+    assert_eq!(payment.total(), 8000 + 600 + RIICHI_DEPOSIT as u32);
+    assert_eq!(game.riichi_sticks, 0);
+    assert_eq!(game.cards.score(FengType::Xi), 25000 - 8600);
+
+    let mut game = Game::new(Cards::with_hkos_rules(), Default::default());
+    game.honba = 2;
+    let paid = hkos_payment(3, FengType::Nan, Some(FengType::Xi));
+    let payment = game.settle_win(paid.clone());
+    assert!(payment == paid);
+}
+
+#[test]
+fn final_standings_add_uma_and_oka() {
+    let mut game = Game::new(Cards::with_riichi_rules(), Default::default());
+    for (side, score) in FengType::ALL.into_iter().zip([30000, 30000, 21000, 18000]) {
+        *game.cards.score_mut(side) = score;
+    }
+    game.riichi_sticks = 1;
+    let result = game.final_result(&UmaOka::default());
+    let places: Vec<_> = result.standings.iter().map(|s| (s.side, s.place)).collect();
+    // The earlier seat ranks higher in a tie.
+    assert!(
+        places
+            == [
+                (FengType::Dong, 1),
+                (FengType::Nan, 2),
+                (FengType::Xi, 3),
+                (FengType::Bei, 4)
+            ]
+    );
+    let first = result.standing(FengType::Dong).unwrap();
+    assert_eq!(first.score, 31000);
+    assert_eq!(first.points, 1000 + 20000 + 20000);
+    let points: Vec<_> = result.standings.iter().map(|s| s.points).collect();
+    assert_eq!(points, [41000, 10000, -19000, -32000]);
+    assert!(result.tobi.is_none());
+}
+
+#[test]
+fn points_below_the_threshold_end_the_game() {
+    let mut game = Game::new(Cards::with_riichi_rules(), Default::default());
+    game.tobi_below = Some(0);
+    // 2000 base points by 荣和 of the dealer are 12000 points, leaving no points but not below.
+    *game.cards.score_mut(FengType::Xi) = 12000;
+    let dealer_ron = || riichi_payment(2000, FengType::Dong, FengType::Dong, Some(FengType::Xi));
+    game.settle_win(dealer_ron());
+    assert_eq!(game.cards.score(FengType::Xi), 0);
+    assert!(!game.is_over());
+
+    let payment = game.settle_win(dealer_ron());
+    assert!(game.is_over());
+    let tobi = game.tobi.clone().unwrap();
+    assert!(tobi.sides == [FengType::Xi] && tobi.payment == Some(payment));
+    assert!(!game.next_hand(true, false));
+    assert!(game.final_result(&UmaOka::default()).tobi == Some(tobi));
+}
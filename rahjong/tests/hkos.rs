@@ -0,0 +1,47 @@
+use rahjong::{
+    card_type::{CardType, FengType},
+    cards::{parse_hand, Cards},
+    scoring::{hkos_payment, hkos_points},
+};
+
+fn card(s: &str) -> CardType {
+    s.parse().unwrap()
+}
+
+fn hkos(hand: &str) -> Cards {
+    let mut cards = Cards::with_hkos_rules();
+    cards.active_player = FengType::Dong;
+    cards.players[0].hand = parse_hand(hand).unwrap();
+    cards
+}
+
+#[test]
+fn faan_double_the_points() {
+    assert_eq!(hkos_points(3), 8);
+    assert_eq!(hkos_points(13), 1024);
+    let ron = hkos_payment(3, FengType::Nan, Some(FengType::Xi));
+    assert_eq!(ron.total(), 32);
+    assert!(ron.payers.contains(&(FengType::Xi, 16)));
+    assert_eq!(hkos_payment(3, FengType::Nan, None).total(), 48);
+}
+
+#[test]
+fn three_faan_are_needed() {
+    // 门前清 only, by 荣和.
+    let cards = hkos("123m456p789s222s5p");
+    assert_eq!(cards.total_fan(FengType::Dong, card("5p")), None);
+    // 门前清, 自摸 and 平和, by 自摸.
+    let cards = hkos("123m456p789s234s55p");
+    assert_eq!(cards.total_fan(FengType::Dong, card("5p")), Some(3));
+    // There is no 立直 to add to the faan.
+    assert!(!cards.rules().allows_riichi());
+}
+
+#[test]
+fn limit_hands_count_alone() {
+    let cards = hkos("1112345678999m9m");
+    assert_eq!(cards.total_fan(FengType::Dong, card("9m")), Some(10));
+    // 七对子 is no complete form.
+    let cards = hkos("1133557799m1133p");
+    assert_eq!(cards.total_fan(FengType::Dong, card("3p")), None);
+}
@@ -0,0 +1,91 @@
+use rahjong::{
+    card_type::{CardType, FengType},
+    cards::{parse_hand, Cards},
+    case_type::CaseType,
+    river_type::RiverType,
+};
+
+fn card(s: &str) -> CardType {
+    s.parse().unwrap()
+}
+
+fn names(cards: &Cards, side: FengType, last: &str) -> Vec<&'static str> {
+    let mut res: Vec<_> = cards.win(side, card(last)).map(|c| c.name).collect();
+    res.sort_unstable();
+    res
+}
+
+/// Returns the cards of MCR where 东 has drawn the last card into the hand.
+fn tsumo(hand: &str) -> Cards {
+    let mut cards = Cards::with_mcr_rules();
+    cards.players[0].hand = parse_hand(hand).unwrap();
+    cards.active_player = FengType::Dong;
+    cards
+}
+
+#[test]
+fn knitted_hands_complete() {
+    // Fourteen different cards with a whole knitted straight are 全不靠 and 组合龙 at once.
+    let cards = tsumo("147m258p369s12345z");
+    assert_eq!(
+        names(&cards, FengType::Dong, "5z"),
+        ["全不靠", "组合龙", "自摸"]
+    );
+    assert!(cards.check_tsumo(card("5z")));
+    assert_eq!(cards.total_fan(FengType::Dong, card("5z")), Some(25));
+    // 七星不靠 includes 全不靠.
+    let cards = tsumo("147m25p36s1234567z");
+    assert_eq!(names(&cards, FengType::Dong, "7z"), ["七星不靠", "自摸"]);
+    // The knitted straight counts as three 顺子 for 平和.
+    let cards = tsumo("123447m25558p369s");
+    assert_eq!(
+        names(&cards, FengType::Dong, "3m"),
+        ["不求人", "平和", "组合龙"]
+    );
+    assert!(cards.check_tsumo(card("3m")));
+    // No other rules know of the knitted forms.
+    let mut cards = Cards::with_riichi_rules();
+    cards.players[0].hand = parse_hand("147m258p369s12345z").unwrap();
+    cards.active_player = FengType::Dong;
+    assert!(!cards.check_tsumo(card("5z")));
+}
+
+#[test]
+fn self_drawn_counts_with_the_forms_not_counting_bu_qiu_ren() {
+    let cards = tsumo("1155m2288p3399s44z");
+    assert_eq!(names(&cards, FengType::Dong, "4z"), ["七对", "自摸"]);
+    let cards = tsumo("19m19p19s12345677z");
+    assert_eq!(names(&cards, FengType::Dong, "7z"), ["十三幺", "自摸"]);
+}
+
+#[test]
+fn fans_of_the_winds_and_of_the_table() {
+    // 东 is both the wind of the round and of the seat of 东.
+    let cards = tsumo("111z234m456p789s55s");
+    let fans = names(&cards, FengType::Dong, "5s");
+    assert!(fans.contains(&"圈风刻") && fans.contains(&"门风刻"));
+    assert!(!fans.contains(&"幺九刻"));
+    // 5s is the last copy(绝张) when three are in the rivers.
+    let mut cards = Cards::with_mcr_rules();
+    cards.players[0].hand = parse_hand("234m456p789s666m5s").unwrap();
+    for side in [FengType::Nan, FengType::Xi, FengType::Bei] {
+        cards[side].river.push(RiverType::Normal(card("5s")).into());
+    }
+    cards.active_player = FengType::Bei;
+    cards.last_discard = Some((FengType::Bei, card("5s")));
+    assert!(!names(&cards, FengType::Dong, "5s").contains(&"和绝张"));
+    cards[FengType::Nan]
+        .river
+        .push(RiverType::Normal(card("5s")).into());
+    assert!(names(&cards, FengType::Dong, "5s").contains(&"和绝张"));
+}
+
+#[test]
+fn a_hand_of_no_other_fan_is_chicken() {
+    let mut cards = Cards::with_mcr_rules();
+    cards.players[1].hand = parse_hand("678m34p789s11z").unwrap();
+    cards.players[1].open.push(CaseType::Shun(card("2m")));
+    cards.active_player = FengType::Xi;
+    assert_eq!(names(&cards, FengType::Nan, "5p"), ["无番和"]);
+    assert_eq!(cards.total_fan(FengType::Nan, card("5p")), Some(8));
+}
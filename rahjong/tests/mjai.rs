@@ -0,0 +1,90 @@
+#![cfg(feature = "mjai")]
+
+use rahjong::{
+    card_type::{CardType, FengType},
+    cards::{parse_hand, Cards},
+    engine::{Action, Engine, HandEnd},
+    game::{Game, GameLength},
+    mjai::{card_of_pai, pai, redact, Message, MjaiSession},
+};
+
+fn card(s: &str) -> CardType {
+    s.parse().unwrap()
+}
+
+#[test]
+fn pai_are_the_cards() {
+    for i in 0..34 {
+        let card = CardType::from_index(i).unwrap();
+        assert_eq!(card_of_pai(&pai(card, false)), Some(card));
+    }
+    assert_eq!(pai(card("5p"), true), "5pr");
+    assert_eq!(card_of_pai("5pr"), Some(card("5p")));
+    assert_eq!(pai(card("7z"), false), "C");
+    assert_eq!(card_of_pai("?"), None);
+}
+
+#[test]
+fn others_are_hidden_from_the_player() {
+    let session = MjaiSession::new(Engine::new(Game::new(
+        Cards::with_riichi_rules_seeded(1),
+        GameLength::default(),
+    )));
+    let Message::StartKyoku { tehais, .. } = redact(&session.start_kyoku(), FengType::Nan) else {
+        panic!("not a start of the hand");
+    };
+    assert!(tehais[1].iter().all(|p| p != "?"));
+    for i in [0, 2, 3] {
+        assert!(tehais[i].iter().all(|p| p == "?"));
+    }
+    let tsumo = Message::Tsumo {
+        actor: 0,
+        pai: "1m".into(),
+    };
+    assert_eq!(redact(&tsumo, FengType::Dong), tsumo);
+    assert_eq!(
+        redact(&tsumo, FengType::Nan),
+        Message::Tsumo {
+            actor: 0,
+            pai: "?".into(),
+        }
+    );
+}
+
+#[test]
+fn messages_of_bots_are_applied() {
+    let mut cards = Cards::with_riichi_rules_seeded(1);
+    cards.players[0].hand = parse_hand("12345678m1234p5z").unwrap();
+    cards.players[1].hand = parse_hand("234m456p678s55z66z").unwrap();
+    let mut session = MjaiSession::new(Engine::new(Game::new(cards, GameLength::default())));
+    // There is no card to pass on yet.
+    assert!(session.respond(FengType::Dong, &Message::None).is_none());
+
+    let drawn = session.apply(FengType::Dong, Action::Draw);
+    assert!(matches!(
+        drawn.as_deref(),
+        Some([Message::Tsumo { actor: 0, .. }])
+    ));
+    let dahai: Message =
+        serde_json::from_str(r#"{"type":"dahai","actor":0,"pai":"P","tsumogiri":false}"#).unwrap();
+    let played = session.respond(FengType::Dong, &dahai).unwrap();
+    assert_eq!(played, [dahai]);
+
+    let hora = Message::Hora {
+        actor: 1,
+        target: 0,
+        pai: "P".into(),
+    };
+    let mut messages = session.respond(FengType::Nan, &hora).unwrap();
+    for side in session.expecting() {
+        messages.extend(session.respond(side, &Message::None).unwrap());
+    }
+    assert!(messages.ends_with(&[hora, Message::EndKyoku]));
+    assert!(
+        session.engine.result
+            == Some(HandEnd::Ron {
+                winners: vec![FengType::Nan],
+                from: FengType::Dong,
+            })
+    );
+}
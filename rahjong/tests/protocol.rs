@@ -0,0 +1,62 @@
+#![cfg(feature = "serde")]
+
+use rahjong::{
+    card_type::{CardType, FengType},
+    cards::{parse_hand, Cards},
+    claim::Claim,
+    engine::{Action, Engine},
+    event::GameEvent,
+    game::{Game, GameLength},
+    protocol::{redact, Update},
+};
+
+fn card(s: &str) -> CardType {
+    s.parse().unwrap()
+}
+
+fn shown(engine: &Engine, side: FengType) -> Vec<GameEvent> {
+    engine
+        .events
+        .iter()
+        .filter_map(|event| match redact(event, side)? {
+            Update::Event { event } => Some(event),
+            Update::Draw { .. } => None,
+        })
+        .collect()
+}
+
+#[test]
+fn only_the_outcome_of_the_claims_is_shown() {
+    let mut cards = Cards::with_riichi_rules_seeded(1);
+    cards.players[0].hand = parse_hand("12345678m1234p5z").unwrap();
+    cards.players[1].hand = parse_hand("234m456p678s55z66z").unwrap();
+    let mut engine = Engine::new(Game::new(cards, GameLength::default()));
+    assert!(engine.apply(FengType::Dong, Action::Draw));
+    assert!(engine.apply(FengType::Dong, Action::Discard(card("5z"))));
+    assert!(engine.apply(FengType::Nan, Action::Ron));
+    // Whoever else can claim the card passes.
+    engine.time_out_claims();
+    let claim = GameEvent::Claim {
+        side: FengType::Nan,
+        claim: Claim::Ron,
+    };
+    let ron = GameEvent::Ron {
+        winners: vec![FengType::Nan],
+        from: FengType::Dong,
+    };
+    assert!(engine.events.contains(&claim) && engine.events.contains(&ron));
+    assert!(shown(&engine, FengType::Nan).contains(&claim));
+    for side in [FengType::Dong, FengType::Xi, FengType::Bei] {
+        let events = shown(&engine, side);
+        assert!(!events.contains(&claim) && events.contains(&ron));
+    }
+    let passes: Vec<_> = engine
+        .events
+        .iter()
+        .filter(|event| matches!(event, GameEvent::Pass { .. }))
+        .collect();
+    assert!(!passes.is_empty());
+    assert!(passes
+        .iter()
+        .all(|&pass| !shown(&engine, FengType::Nan).contains(pass)));
+}
@@ -0,0 +1,268 @@
+use std::sync::Arc;
+
+use rahjong::{
+    analysis::{self, WaitType},
+    card_type::{CardType, FengType},
+    cards::{parse_hand, Cards},
+    case_type::CaseType,
+    error::PlayError,
+    river_type::RiverType,
+    rule_config::RuleConfig,
+    ruleset::RiichiRules,
+};
+
+fn card(s: &str) -> CardType {
+    s.parse().unwrap()
+}
+
+/// Returns the cards of riichi mahjong where 东 holds the hand, and 北 is the active player,
+/// so the last card checked for 东 is played by 北.
+fn ron_table(hand: &str) -> Cards {
+    let mut cards = Cards::with_riichi_rules();
+    cards.players[0].hand = parse_hand(hand).unwrap();
+    cards.active_player = FengType::Bei;
+    cards
+}
+
+fn names(cards: &Cards, side: FengType, last: &str, winds: &[FengType]) -> Vec<&'static str> {
+    let mut res: Vec<_> = cards
+        .interpret(side, card(last), winds)
+        .map(|reading| reading.completions.iter().map(|c| c.name).collect())
+        .unwrap_or_default();
+    res.sort_unstable();
+    res
+}
+
+#[test]
+fn yaku_of_different_decompositions_do_not_add_up() {
+    // 111222333m reads either as three 暗刻 or as three 123m.
+    let cards = ron_table("111222333m45p55s");
+    let winds = [FengType::Dong, FengType::Dong];
+    assert_eq!(names(&cards, FengType::Dong, "6p", &winds), ["三暗刻"]);
+    let reading = cards.interpret(FengType::Dong, card("6p"), &winds).unwrap();
+    assert_eq!(reading.fan, 2);
+    assert_eq!(reading.fu, 50);
+}
+
+#[test]
+fn ping_he_pair_follows_the_winds() {
+    let cards = ron_table("234m456p678s23s44z");
+    // 北 is a guest wind for the 南 seat in the 东 round.
+    let guest = [FengType::Dong, FengType::Nan];
+    assert_eq!(names(&cards, FengType::Dong, "1s", &guest), ["平和"]);
+    assert_eq!(cards.fu(FengType::Dong, card("1s"), &guest), Some(30));
+    let own = [FengType::Dong, FengType::Bei];
+    assert!(names(&cards, FengType::Dong, "1s", &own).is_empty());
+    assert_eq!(cards.fu(FengType::Dong, card("1s"), &own), None);
+    // The cards count the winds of the player, 东 for the dealer in the 东 round.
+    assert!(cards
+        .win(FengType::Dong, card("1s"))
+        .map(|c| c.name)
+        .eq(["平和"]));
+    assert!(cards.check_ron(FengType::Dong, card("1s")));
+}
+
+#[test]
+fn wind_triplets_are_yaku_for_the_round_and_the_seat() {
+    // 南 and 西 wait on 1s or 4s with a triplet of 南 and no other 役.
+    let mut cards = Cards::with_riichi_rules();
+    for side in [FengType::Nan, FengType::Xi] {
+        *cards.hand_mut(side) = parse_hand("222z456p678s23s99m").unwrap();
+    }
+    cards.active_player = FengType::Dong;
+    // 南 sits at 南 in the 东 round, while 南 is a guest wind for 西.
+    let winds = cards.yakuhai_winds(FengType::Nan);
+    assert_eq!(winds, [FengType::Dong, FengType::Nan]);
+    assert_eq!(names(&cards, FengType::Nan, "1s", &winds), ["役牌 自风"]);
+    assert!(cards.check_ron(FengType::Nan, card("1s")));
+    assert!(!cards.check_ron(FengType::Xi, card("1s")));
+    // 南 counts twice for 南 in the 南 round.
+    cards.round_wind = FengType::Nan;
+    let winds = cards.yakuhai_winds(FengType::Nan);
+    assert_eq!(
+        names(&cards, FengType::Nan, "1s", &winds),
+        ["役牌 场风", "役牌 自风"]
+    );
+    assert!(cards.check_ron(FengType::Xi, card("1s")));
+    // The seats follow the dealer.
+    assert_eq!(cards.seat_wind(FengType::Bei), FengType::Bei);
+    cards.dealer = FengType::Nan;
+    assert_eq!(cards.seat_wind(FengType::Bei), FengType::Xi);
+}
+
+#[test]
+fn fu_is_of_the_interpretation_chosen() {
+    // 6m completes 456m from the side, or 567m in the middle.
+    let cards = ron_table("45567m234p678s88s");
+    let winds = [FengType::Dong, FengType::Dong];
+    assert_eq!(
+        names(&cards, FengType::Dong, "6m", &winds),
+        ["平和", "断幺九"]
+    );
+    // The 嵌张 reading counts 40 fu, but scores 断幺九 only.
+    assert_eq!(cards.fu(FengType::Dong, card("6m"), &winds), Some(30));
+}
+
+#[test]
+fn wait_changes_the_fu() {
+    let winds = [FengType::Dong, FengType::Dong];
+    let kanchan = ron_table("57m234p678s234s88s");
+    assert_eq!(names(&kanchan, FengType::Dong, "6m", &winds), ["断幺九"]);
+    assert_eq!(kanchan.fu(FengType::Dong, card("6m"), &winds), Some(40));
+    let ryanmen = ron_table("56m234p678s234s88s");
+    assert_eq!(
+        names(&ryanmen, FengType::Dong, "7m", &winds),
+        ["平和", "断幺九"]
+    );
+    assert_eq!(ryanmen.fu(FengType::Dong, card("7m"), &winds), Some(30));
+    let mut tsumo = ron_table("567m234p678s234s88s");
+    tsumo.active_player = FengType::Dong;
+    assert_eq!(tsumo.fu(FengType::Dong, card("7m"), &winds), Some(20));
+}
+
+#[test]
+fn equal_interpretations_pick_the_first() {
+    // 3m completes 123m at the edge, or 234m in the middle, both worth 2 fu.
+    let mut cards = ron_table("122334m567p678s99s");
+    cards.active_player = FengType::Dong;
+    let winds = [FengType::Dong, FengType::Dong];
+    let all = analysis::interpretations(&cards.players[0].hand, &cards.players[0].open, card("3m"));
+    assert_eq!(all.len(), 2);
+    for _ in 0..3 {
+        let reading = cards.interpret(FengType::Dong, card("3m"), &winds).unwrap();
+        assert_eq!(reading.fu, 30);
+        assert!(reading.interpretation.as_ref() == Some(&all[0]));
+        assert!(all[0].wait == Some(WaitType::QianZhang));
+    }
+}
+
+#[test]
+fn rinshan_counts_as_menzen_tsumo() {
+    use rahjong::case_type::CaseType;
+    let mut cards = Cards::with_riichi_rules();
+    cards.players[0].hand = parse_hand("2222p234m567m35s88s").unwrap();
+    cards.active_player = FengType::Dong;
+    cards
+        .call(
+            CaseType::AnGang(card("2p")),
+            FengType::Dong,
+            card("2p"),
+            vec![card("2p"); 4],
+        )
+        .unwrap();
+    cards.dead_wall[0] = card("4s");
+    assert!(cards.draw_replacement() == Some(card("4s")));
+    assert!(cards.is_rinshan(FengType::Dong) && cards.is_menzen_tsumo(FengType::Dong));
+    let res = cards.score_win(FengType::Dong, card("4s")).unwrap();
+    let mut names: Vec<_> = res.completions.iter().map(|(n, _)| n.as_str()).collect();
+    names.sort_unstable();
+    assert_eq!(names, ["岭上开花", "断幺九", "门前清自摸和"]);
+    assert!(res.from.is_none());
+    assert_eq!(res.fan, 3);
+    // 20 + 16 for the 暗杠 + 2 for the 嵌张 + 2 for 自摸, where 荣和 would count 50.
+    assert_eq!(res.fu, 40);
+}
+
+#[test]
+fn open_tanyao_follows_kuitan() {
+    use rahjong::case_type::CaseType;
+    let mut cards = ron_table("234m567p88s23s");
+    cards.players[0].open.push(CaseType::Shun(card("6p")));
+    assert!(cards.is_tanyao(FengType::Dong, card("4s")));
+    assert!(cards.qualifies_tanyao(FengType::Dong, card("4s")));
+    assert!(cards.check_ron(FengType::Dong, card("4s")));
+    cards.config.kuitan = false;
+    assert!(cards.is_tanyao(FengType::Dong, card("4s")));
+    assert!(!cards.qualifies_tanyao(FengType::Dong, card("4s")));
+    // The completions are built from the rules when the cards are created.
+    let config = RuleConfig {
+        kuitan: false,
+        ..RuleConfig::default()
+    };
+    let mut cards = Cards::with_config(Arc::new(RiichiRules), config);
+    cards.players[0].hand = parse_hand("234m567p88s23s").unwrap();
+    cards.players[0].open.push(CaseType::Shun(card("6p")));
+    cards.active_player = FengType::Bei;
+    assert!(!cards.check_ron(FengType::Dong, card("4s")));
+    // A terminal breaks the shape whatever the rules.
+    assert!(!cards.is_tanyao(FengType::Dong, card("1s")));
+}
+
+#[test]
+fn noten_riichi_is_rejected() {
+    let mut cards = Cards::with_riichi_rules();
+    cards.players[0].hand = parse_hand("123m456p789s1122z5z").unwrap();
+    cards.active_player = FengType::Dong;
+    let before = cards.players[0].hand.clone();
+    assert_eq!(
+        cards.declare_riichi(RiverType::Drawing(card("1m"))),
+        Err(PlayError::NotTenpai)
+    );
+    assert!(cards.players[0].hand == before && cards.players[0].river.is_empty());
+    assert!(!cards.is_riichi(FengType::Dong));
+    assert!(cards.declare_riichi(RiverType::Drawing(card("5z"))).is_ok());
+    assert!(cards.is_riichi(FengType::Dong));
+}
+
+#[test]
+fn furiten_player_scores_no_ron() {
+    let mut cards = ron_table("234m456p678s23s88s");
+    cards.dora_revealed = 0;
+    // 平和 and 断幺 of the dealer by 荣和 are 2900 points.
+    assert_eq!(cards.score_if_ron(FengType::Dong, card("4s")), Some(2900));
+    assert_eq!(cards.score_if_ron(FengType::Dong, card("9s")), None);
+    // 1s completes the hand as well, and is in the player's own river.
+    cards.players[0]
+        .river
+        .push(RiverType::Normal(card("1s")).into());
+    assert!(cards.is_furiten(FengType::Dong));
+    assert_eq!(cards.score_if_ron(FengType::Dong, card("4s")), None);
+    assert!(!cards.check_ron(FengType::Dong, card("4s")));
+}
+
+#[test]
+fn can_win_needs_a_yaku() {
+    // No 役 from the shape: 345m breaks 纯全带幺九, and the 边张 wait breaks 平和.
+    let cards = ron_table("345m789p789s12s99m");
+    assert!(!cards.can_win(FengType::Dong, card("3s"), false));
+    assert!(!cards.can_win(FengType::Dong, card("3s"), true));
+    // 门前清自摸和 is a 役 itself.
+    let mut cards = ron_table("345m789p789s123s99m");
+    cards.active_player = FengType::Dong;
+    assert!(cards.can_win(FengType::Dong, card("3s"), true));
+    assert!(!cards.can_win(FengType::Nan, card("3s"), true));
+    let cards = ron_table("234m456p678s23s88s");
+    assert!(cards.can_win(FengType::Dong, card("4s"), false));
+    assert!(!cards.can_win(FengType::Dong, card("5s"), false));
+}
+
+#[test]
+fn player_feeding_the_third_dragon_pays_for_daisangen() {
+    let mut cards = Cards::with_riichi_rules();
+    cards.active_player = FengType::Dong;
+    cards.players[1].hand = parse_hand("77z234m19p456s").unwrap();
+    cards.players[1].open = vec![CaseType::Ke(card("5z")), CaseType::Ke(card("6z"))];
+    cards.players[0]
+        .river
+        .push(RiverType::Normal(card("7z")).into());
+    cards.last_discard = Some((FengType::Dong, card("7z")));
+    let called = cards.call(
+        CaseType::Ke(card("7z")),
+        FengType::Nan,
+        card("7z"),
+        vec![card("7z"); 2],
+    );
+    assert!(called.is_ok());
+    assert_eq!(cards.pao.get(&FengType::Nan), Some(&FengType::Dong));
+
+    // 荣和 on another player is paid half each.
+    cards.players[1].hand = parse_hand("234m1p456s").unwrap();
+    cards.active_player = FengType::Xi;
+    let result = cards.score_win(FengType::Nan, card("1p")).unwrap();
+    assert!(result.payment.payers == [(FengType::Dong, 16000), (FengType::Xi, 16000)]);
+    // 自摸 is paid all by the liable player.
+    cards.players[1].hand = parse_hand("234m11p456s").unwrap();
+    cards.active_player = FengType::Nan;
+    let result = cards.score_win(FengType::Nan, card("1p")).unwrap();
+    assert!(result.payment.payers == [(FengType::Dong, 32000)]);
+}
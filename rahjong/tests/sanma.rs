@@ -0,0 +1,110 @@
+use rahjong::{
+    bot::EfficiencyBot,
+    card_type::FengType,
+    cards::Cards,
+    case_type::CaseType,
+    engine::Engine,
+    game::{Game, GameLength},
+    scoring::{sanma_noten_payments, sanma_payment},
+    simulation::play_out,
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+/// Returns the cards of 三麻 dealt from the wall shuffled by the seed.
+fn sanma(seed: u64) -> Cards {
+    let mut cards = Cards::with_sanma_rules();
+    cards.rng = Some(StdRng::seed_from_u64(seed));
+    cards.reset_for_next_hand(FengType::Dong);
+    cards
+}
+
+/// Returns the number of cards in the wall, the hands, the rivers, the opens and set aside as 北.
+fn card_total(cards: &Cards) -> usize {
+    let mut total = cards.card_mountain.len() + cards.dead_wall.len();
+    for side in FengType::ALL {
+        total += cards
+            .hand(side)
+            .values()
+            .map(|&n| n as usize)
+            .sum::<usize>();
+        total += cards.river(side).iter().filter(|r| !r.claimed).count();
+        total += cards
+            .open(side)
+            .iter()
+            .map(|o| o.cards().len())
+            .sum::<usize>();
+        total += *cards.nuki.get(&side).unwrap_or(&0) as usize;
+    }
+    total
+}
+
+#[test]
+fn three_players_are_dealt_without_two_to_eight_man() {
+    let cards = sanma(1);
+    assert_eq!(cards.wall().len(), 108);
+    assert!(cards.players[3].hand.is_empty());
+    assert_eq!(cards.card_mountain.len(), 55);
+    assert!(cards.wall().iter().all(|c| !matches!(c.index(), 1..=7)));
+}
+
+#[test]
+fn bots_play_sanma_without_bei() {
+    for seed in 0..8 {
+        let mut engine = Engine::new(Game::new(sanma(seed), GameLength::default()));
+        play_out(&mut engine, &mut EfficiencyBot);
+        assert!(
+            engine.result.is_some(),
+            "the hand of seed {seed} does not end"
+        );
+        let cards = engine.cards();
+        assert_eq!(card_total(cards), 108);
+        assert!(cards.river(FengType::Bei).is_empty());
+        // There is no 吃 in 三麻.
+        for side in FengType::ALL {
+            assert!(!cards
+                .open(side)
+                .iter()
+                .any(|o| matches!(o, CaseType::Shun(_))));
+        }
+
+        let mut replayed = Engine::new(Game::new(Cards::with_sanma_rules(), GameLength::default()));
+        assert!(replayed.replay(&engine.events).is_ok());
+        assert!(replayed.result == engine.result);
+    }
+}
+
+#[test]
+fn dealer_passes_between_three_players() {
+    let mut game = Game::new(Cards::with_sanma_rules(), GameLength::DongFeng);
+    assert_eq!(game.cards.seat_wind(FengType::Xi), FengType::Xi);
+    assert!(game.next_hand(false, false));
+    assert_eq!(game.cards.dealer, FengType::Nan);
+    assert_eq!(game.cards.seat_wind(FengType::Dong), FengType::Xi);
+    assert!(game.next_hand(false, false));
+    assert_eq!(game.cards.dealer, FengType::Xi);
+    // 东风战 of three players ends after three hands.
+    assert!(!game.next_hand(false, false));
+}
+
+#[test]
+fn two_players_pay() {
+    let tsumo = sanma_payment(2000, FengType::Nan, FengType::Dong, None);
+    assert!(tsumo.payers == [(FengType::Xi, 2000), (FengType::Dong, 4000)]);
+    assert_eq!(
+        sanma_noten_payments(&[FengType::Dong]),
+        [
+            (FengType::Dong, 2000),
+            (FengType::Nan, -1000),
+            (FengType::Xi, -1000)
+        ]
+    );
+}
+
+#[test]
+fn sampled_cards_keep_the_wall_of_sanma() {
+    let view = sanma(3).view(FengType::Dong);
+    let sample = Cards::sample_from_view(&view, &mut StdRng::seed_from_u64(3));
+    assert!(sample.rules().has_nuki_dora());
+    assert_eq!(card_total(&sample), 108);
+    assert!(sample.players[3].hand.is_empty());
+}
@@ -0,0 +1,78 @@
+use rahjong::{
+    card_type::{CardType, FengType},
+    cards::{parse_hand, Cards},
+    game::Game,
+    scoring::{base_points, noten_payments, riichi_payment},
+};
+
+fn card(s: &str) -> CardType {
+    s.parse().unwrap()
+}
+
+#[test]
+fn base_points_are_limited() {
+    assert_eq!(base_points(1, 30), 240);
+    assert_eq!(base_points(4, 30), 1920);
+    assert_eq!(base_points(4, 40), 2000);
+    assert_eq!(base_points(6, 30), 3000);
+    assert_eq!(base_points(13, 30), 8000);
+    assert_eq!(base_points(26, 30), 16000);
+}
+
+#[test]
+fn dealer_is_paid_and_pays_double() {
+    let ron = riichi_payment(2000, FengType::Dong, FengType::Dong, Some(FengType::Xi));
+    assert!(ron.payers == [(FengType::Xi, 12000)]);
+
+    let tsumo = riichi_payment(2000, FengType::Dong, FengType::Dong, None);
+    assert_eq!(tsumo.total(), 12000);
+    assert!(tsumo.payers.iter().all(|&(_, points)| points == 4000));
+
+    // 1 fan 30 fu by 自摸 of a non-dealer, rounded up to hundreds.
+    let tsumo = riichi_payment(240, FengType::Nan, FengType::Dong, None);
+    assert!(
+        tsumo.payers
+            == [
+                (FengType::Xi, 300),
+                (FengType::Bei, 300),
+                (FengType::Dong, 500),
+            ]
+    );
+}
+
+#[test]
+fn payments_move_the_points() {
+    let mut cards = Cards::with_riichi_rules();
+    let mut payment = riichi_payment(2000, FengType::Nan, FengType::Dong, None);
+    payment.deposit = 1000;
+    cards.apply_payment(&payment);
+    assert_eq!(cards.score(FengType::Nan), 25000 + 8000 + 1000);
+    assert_eq!(cards.score(FengType::Dong), 25000 - 4000);
+    assert_eq!(cards.score(FengType::Xi), 25000 - 2000);
+    assert_eq!(cards.score(FengType::Bei), 25000 - 2000);
+}
+
+#[test]
+fn noten_players_pay_the_tenpai_players() {
+    let payments = noten_payments(&[FengType::Dong]);
+    assert!(payments.contains(&(FengType::Dong, 3000)));
+    assert!(payments.contains(&(FengType::Nan, -1000)));
+    assert!(noten_payments(&[]).is_empty());
+    assert!(noten_payments(&FengType::ALL).is_empty());
+}
+
+#[test]
+fn ron_is_scored_against_the_player_of_the_card() {
+    let mut game = Game::new(Cards::with_riichi_rules(), Default::default());
+    game.cards.dora_revealed = 0;
+    *game.cards.hand_mut(FengType::Nan) = parse_hand("234m567p345s77z22p").unwrap();
+    let result = game.score_win(FengType::Nan, card("7z")).unwrap();
+    assert!(result.from == Some(FengType::Dong));
+    assert!(result.completions == [("役牌 中".to_string(), 1)]);
+    assert_eq!(result.fan, 1);
+    // 20 fu, 10 fu of 门前荣和 and 4 fu of the 中 triplet completed by 荣和.
+    assert_eq!(result.fu, 40);
+    assert!(result.payment.payers == [(FengType::Dong, 1300)]);
+    // 2p is no yaku(役).
+    assert!(game.score_win(FengType::Nan, card("2p")).is_none());
+}
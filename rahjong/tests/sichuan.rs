@@ -0,0 +1,91 @@
+use rahjong::{
+    bot::EfficiencyBot,
+    card_type::{CardType, FengType, SuitType},
+    cards::{parse_hand, Cards},
+    engine::{Action, Engine, HandEnd},
+    error::PlayError,
+    game::{Game, GameLength},
+    simulation::play_out,
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+fn card(s: &str) -> CardType {
+    s.parse().unwrap()
+}
+
+/// Returns the cards of 血战到底 where every player has declared 万 void.
+fn sichuan() -> Cards {
+    let mut cards = Cards::with_sichuan_rules();
+    cards.reset_for_next_hand(FengType::Dong);
+    for _ in 0..4 {
+        assert!(cards.declare_void(SuitType::Wan));
+    }
+    cards
+}
+
+#[test]
+fn finished_players_do_not_pay_for_self_drawn() {
+    let mut cards = sichuan();
+    cards.players[0].hand = parse_hand("1112345678999p5p").unwrap();
+    cards.drawn = Some(card("5p"));
+    let result = cards.score_win(FengType::Dong, card("5p")).unwrap();
+    assert_eq!(result.payment.payers.len(), 3);
+    let each = result.payment.payers[0].1;
+
+    cards.finish(&[FengType::Nan], None);
+    cards.active_player = FengType::Dong;
+    let result = cards.score_win(FengType::Dong, card("5p")).unwrap();
+    assert!(result.payment.payers == [(FengType::Xi, each), (FengType::Bei, each)]);
+}
+
+#[test]
+fn void_suit_is_played_first_and_never_called() {
+    let mut cards = sichuan();
+    assert_eq!(cards.active_player, FengType::Dong);
+    assert!(!cards.declare_void(SuitType::Tong));
+    cards.players[0].hand = parse_hand("1112345678999p1m").unwrap();
+    cards.drawn = Some(card("1m"));
+    assert_eq!(
+        cards.validate_discard(card("1p")),
+        Err(PlayError::VoidSuitFirst)
+    );
+    assert!(cards.can_discard(card("1m")));
+    // There is no 自摸 while holding the void suit.
+    assert!(!cards.check_tsumo(card("1m")));
+    cards.players[0].hand = parse_hand("1112345678999p5p").unwrap();
+    cards.drawn = Some(card("5p"));
+    assert!(cards.check_tsumo(card("5p")));
+
+    cards.players[1].hand = parse_hand("11m23456789p1234s").unwrap();
+    cards.last_discard = Some((FengType::Dong, card("1m")));
+    assert!(cards.check_call(card("1m")).is_empty());
+}
+
+#[test]
+fn bots_play_until_three_players_complete() {
+    for seed in 0..8 {
+        let mut cards = Cards::with_sichuan_rules();
+        cards.rng = Some(StdRng::seed_from_u64(seed));
+        cards.reset_for_next_hand(FengType::Dong);
+        assert_eq!(cards.wall().len(), 108);
+        let mut engine = Engine::new(Game::new(cards, GameLength::default()));
+        // Nobody draws before declaring the void suit.
+        assert!(!engine.apply(FengType::Dong, Action::Draw));
+        play_out(&mut engine, &mut EfficiencyBot);
+        let cards = engine.cards();
+        assert_eq!(cards.voids.len(), 4);
+        match engine.result.as_ref().expect("the hand does not end") {
+            HandEnd::Tsumo(_) | HandEnd::Ron { .. } => assert_eq!(cards.finished.len(), 3),
+            HandEnd::Draw(_) => assert!(cards.finished.len() < 3),
+        }
+        for &(side, _) in &cards.finished {
+            assert!(!cards.holds_void(side));
+        }
+
+        let mut replayed = Cards::with_sichuan_rules();
+        replayed.reset_for_next_hand(FengType::Dong);
+        let mut replayed = Engine::new(Game::new(replayed, GameLength::default()));
+        assert!(replayed.replay(&engine.events).is_ok());
+        assert!(replayed.cards().finished == cards.finished);
+    }
+}
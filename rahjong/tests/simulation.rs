@@ -0,0 +1,29 @@
+use rahjong::{
+    bot::EfficiencyBot,
+    card_type::{CardType, FengType},
+    cards::{parse_hand, Cards},
+    river_type::RiverType,
+    simulation::simulate_with_rng,
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+fn card(s: &str) -> CardType {
+    s.parse().unwrap()
+}
+
+#[test]
+fn games_are_played_by_the_rules_of_the_view() {
+    // 七星不靠 only completes in MCR.
+    let mut cards = Cards::with_mcr_rules();
+    cards.players[0].hand = parse_hand("147m25p36s1234567z").unwrap();
+    cards.players[0]
+        .river
+        .push(RiverType::Normal(card("9m")).into());
+    cards.drawn = Some(card("7z"));
+    cards.active_player = FengType::Dong;
+    let view = cards.view(FengType::Dong);
+    let mut rng = StdRng::seed_from_u64(1);
+    let estimate = simulate_with_rng(&view, &mut EfficiencyBot, 4, &mut rng);
+    assert_eq!(estimate.games, 4);
+    assert_eq!(estimate.wins, 4);
+}
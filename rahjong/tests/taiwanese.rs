@@ -0,0 +1,100 @@
+use rahjong::{
+    analysis,
+    bot::EfficiencyBot,
+    card_type::{CardType, FengType},
+    cards::{parse_hand, Cards},
+    engine::{Engine, HandEnd},
+    game::{Game, GameLength},
+    ruleset::RulesetId,
+    simulation::play_out,
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+fn card(s: &str) -> CardType {
+    s.parse().unwrap()
+}
+
+/// Returns the sorted names of the 台 for 东 with the given hand and the last card,
+/// by 自摸 if the hand holds the last card, otherwise by 荣和.
+fn names(cards: &mut Cards, hand: &str, last: &str) -> Vec<&'static str> {
+    cards.players[0].hand = parse_hand(hand).unwrap();
+    let mut names: Vec<_> = cards
+        .win(FengType::Dong, card(last))
+        .map(|c| c.name)
+        .collect();
+    names.sort();
+    names
+}
+
+fn taiwanese() -> Cards {
+    let mut cards = Cards::with_taiwanese_rules();
+    cards.active_player = FengType::Dong;
+    cards
+}
+
+#[test]
+fn complete_hands_are_five_cases_and_a_pair() {
+    let hand = parse_hand("123456789m123789p11z").unwrap();
+    assert!(analysis::is_complete(&hand, &vec![]));
+    let decompositions = analysis::decompositions(&hand, &vec![]);
+    assert_eq!(decompositions.len(), 1);
+    assert_eq!(decompositions[0].cases.len(), 5);
+    let hand = parse_hand("123456789m12789p11z").unwrap();
+    assert!(analysis::waits(&hand, &vec![]) == [card("3p")]);
+    let hand = parse_hand("123456789m12789p15z").unwrap();
+    assert_eq!(analysis::shanten(&hand, &vec![]), 1);
+}
+
+#[test]
+fn tai_add_up() {
+    let mut cards = taiwanese();
+    assert_eq!(
+        names(&mut cards, "123456789m123789p11z", "1z"),
+        ["底", "门清自摸"]
+    );
+    assert_eq!(cards.total_fan(FengType::Dong, card("1z")), Some(3));
+}
+
+#[test]
+fn concealed_triplets_follow_the_last_card() {
+    let mut cards = taiwanese();
+    assert_eq!(
+        names(&mut cards, "111555m222666p333s77z", "7z"),
+        ["五暗刻", "底", "碰碰胡", "门清自摸"]
+    );
+    // The 刻子 completed by 荣和 is not concealed.
+    assert_eq!(
+        names(&mut cards, "11155m222666p333s77z", "5m"),
+        ["四暗刻", "底", "碰碰胡", "门清"]
+    );
+}
+
+#[test]
+fn bots_play_with_flowers() {
+    for seed in 0..6 {
+        let mut cards = Cards::with_taiwanese_rules();
+        cards.rng = Some(StdRng::seed_from_u64(seed));
+        cards.reset_for_next_hand(FengType::Dong);
+        for side in FengType::ALL {
+            assert_eq!(cards.card_count(side), 16);
+        }
+        let mut engine = Engine::new(Game::new(cards, GameLength::default()));
+        play_out(&mut engine, &mut EfficiencyBot);
+        let cards = engine.cards();
+        match engine.result.as_ref().expect("the hand does not end") {
+            &HandEnd::Tsumo(side) => {
+                assert_eq!(cards.card_count(side), 17);
+                assert!(cards
+                    .win(side, cards.drawn.unwrap())
+                    .any(|c| c.name == "底"));
+            }
+            HandEnd::Ron { .. } | HandEnd::Draw(_) => {}
+        }
+        let flowers: usize = FengType::ALL
+            .iter()
+            .map(|&side| cards.flowers.of(side).len())
+            .sum();
+        assert!(flowers <= 8);
+        assert!(cards.view(FengType::Nan).rules == RulesetId::Taiwanese);
+    }
+}
@@ -0,0 +1,95 @@
+#![cfg(feature = "tenhou")]
+
+use rahjong::{
+    bot::{Bot, EfficiencyBot},
+    card_type::{CardType, FengType},
+    cards::Cards,
+    engine::{Engine, HandEnd},
+    error::LogError,
+    game::{Game, GameLength},
+    tenhou::{parse, TenhouHand, TenhouLog},
+};
+
+fn card(s: &str) -> CardType {
+    s.parse().unwrap()
+}
+
+/// Plays the hand with [EfficiencyBot] for every player, and returns the engine when the hand ends.
+fn play_with_bots(seed: u64) -> Engine {
+    let cards = Cards::with_riichi_rules_seeded(seed);
+    let mut engine = Engine::new(Game::new(cards, GameLength::default()));
+    while engine.result.is_none() {
+        let acting = match &engine.claims {
+            Some(_) => engine.pending_responses(),
+            None => vec![engine.cards().active_player],
+        };
+        for side in acting {
+            let actions = engine.legal_actions(side);
+            if !actions.is_empty() {
+                let action = EfficiencyBot.choose_action(&engine.cards().view(side), &actions);
+                assert!(engine.apply(side, action));
+            }
+        }
+    }
+    engine
+}
+
+#[test]
+fn exported_hands_replay_the_same() {
+    for seed in 0..8 {
+        let engine = play_with_bots(seed);
+        let log = TenhouLog {
+            names: vec!["a".into(), "b".into(), "c".into(), "d".into()],
+            hands: vec![TenhouHand::from_engine(&engine).unwrap()],
+        };
+        let json = log.to_json().unwrap();
+        let parsed = parse(&json.to_string()).unwrap();
+        assert!(parsed.names == log.names);
+        let replayed = parsed.hands[0].replay().unwrap();
+        assert!(replayed.result == engine.result, "seed {seed}");
+        for side in FengType::ALL {
+            assert!(replayed.cards().river(side) == engine.cards().river(side));
+            assert!(replayed.cards().open(side) == engine.cards().open(side));
+        }
+        assert_eq!(parsed.to_json().unwrap(), json);
+    }
+}
+
+/// A log of a hand where 东 completes on the first draw(天和).
+const TENHOU_LOG: &str = r#"{
+    "name": ["a", "b", "c", "d"],
+    "log": [[
+        [0, 0, 0],
+        [25000, 25000, 25000, 25000],
+        [36],
+        [],
+        [11, 12, 13, 24, 25, 26, 37, 38, 39, 41, 41, 42, 42], [42], [],
+        [14, 15, 16, 17, 18, 19, 21, 22, 23, 27, 28, 29, 43], [], [],
+        [31, 32, 33, 34, 35, 36, 14, 15, 16, 17, 18, 19, 44], [], [],
+        [21, 22, 23, 27, 28, 29, 31, 32, 33, 45, 46, 47, 47], [], [],
+        ["和了", [48000, -16000, -16000, -16000], [0, 0, 0, "役満48000点∀"]]
+    ]]
+}"#;
+
+#[test]
+fn imported_hands_replay() {
+    let log = parse(TENHOU_LOG).unwrap();
+    assert!(log.names == ["a", "b", "c", "d"]);
+    let [hand] = &log.hands[..] else {
+        panic!("not a hand");
+    };
+    assert!(hand.dealer() == FengType::Dong);
+    assert_eq!(hand.deltas, [48000, -16000, -16000, -16000]);
+    assert!(hand.end == Some(HandEnd::Tsumo(FengType::Dong)));
+    let engine = hand.replay().unwrap();
+    assert!(engine.result == hand.end);
+    assert!(engine.cards().dora_indicators() == [card("6s")]);
+}
+
+#[test]
+fn malformed_logs_are_rejected() {
+    assert!(matches!(parse("[]"), Err(LogError::InvalidField("log"))));
+    assert!(matches!(parse("{"), Err(LogError::InvalidFormat)));
+    let log = TENHOU_LOG.replace("[36]", "[99]");
+    assert!(matches!(parse(&log), Err(LogError::InvalidCard(99))));
+}
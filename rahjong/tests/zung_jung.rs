@@ -0,0 +1,67 @@
+use rahjong::{
+    card_type::{CardType, FengType},
+    cards::{parse_hand, Cards},
+    scoring::zung_jung_points,
+};
+
+fn card(s: &str) -> CardType {
+    s.parse().unwrap()
+}
+
+/// Returns the sorted names of the patterns of 自摸 for 东 with the given hand.
+fn tsumo(cards: &mut Cards, hand: &str, last: &str) -> Vec<&'static str> {
+    cards.players[0].hand = parse_hand(hand).unwrap();
+    let mut names: Vec<_> = cards
+        .win(FengType::Dong, card(last))
+        .map(|c| c.name)
+        .collect();
+    names.sort();
+    names
+}
+
+fn zung_jung() -> Cards {
+    let mut cards = Cards::with_zung_jung_rules();
+    cards.active_player = FengType::Dong;
+    cards
+}
+
+#[test]
+fn patterns_add_up() {
+    let mut cards = zung_jung();
+    assert_eq!(
+        tsumo(&mut cards, "234m456p678s234s55p", "5p"),
+        ["平和", "断幺", "门前清"]
+    );
+    assert_eq!(cards.total_fan(FengType::Dong, card("5p")), Some(15));
+    let names = tsumo(&mut cards, "223344m556677p99s", "9s");
+    assert!(names.contains(&"两般高") && !names.contains(&"一般高"));
+    let names = tsumo(&mut cards, "123m123p123s789s55z", "5z");
+    assert!(names.contains(&"三色同顺"));
+    let names = tsumo(&mut cards, "1155m2288p3399s44z", "4z");
+    assert!(names.contains(&"七对"));
+}
+
+#[test]
+fn limit_hands_include_the_smaller_patterns() {
+    let mut cards = zung_jung();
+    let names = tsumo(&mut cards, "1112345678999m5m", "5m");
+    assert!(names.contains(&"九莲宝灯") && !names.contains(&"清一色"));
+    assert_eq!(
+        zung_jung_points(cards.total_fan(FengType::Dong, card("5m")).unwrap()),
+        320
+    );
+    // 十三幺 is a 160 points pattern on its own, whatever the terminals(幺九) and the closed hand.
+    assert_eq!(tsumo(&mut cards, "19m19p19s12345677z", "7z"), ["十三幺"]);
+    assert_eq!(cards.total_fan(FengType::Dong, card("7z")), Some(160));
+}
+
+#[test]
+fn dragon_patterns_include_the_dragon_triplets() {
+    let mut cards = zung_jung();
+    let names = tsumo(&mut cards, "555666z77z123m789p", "7z");
+    assert!(names.contains(&"小三元"));
+    assert!(!names.iter().any(|name| name.starts_with("番牌")));
+    let names = tsumo(&mut cards, "555666777z123m99p", "9p");
+    assert!(names.contains(&"大三元"));
+    assert!(!names.iter().any(|name| name.starts_with("番牌")));
+}